@@ -0,0 +1,207 @@
+//! Arbitrary collections for verification harnesses.
+//!
+//! Building a collection through a sequence of insertions only reaches a few of its internal
+//! states: a `Vec` built with `push` has a power-of-two capacity, and a `VecDeque` built with
+//! `push_back` never wraps around. For the contiguous collections, the functions in this module
+//! instead create collections with at most `N` elements, whose length, capacity and (for
+//! `VecDeque`) head position are all symbolic, while still satisfying the invariants of the
+//! collection.
+//!
+//! `LinkedList` is still built by insertion, which covers every shape of a linked list. A
+//! `BTreeMap` is assembled from leaves of arbitrary lengths: see [`any_btree_map`].
+
+use core::kani::{self, Arbitrary};
+use core::mem::ManuallyDrop;
+use core::ptr;
+
+use crate::collections::{BTreeMap, LinkedList, VecDeque};
+use crate::ffi::CString;
+use crate::string::String;
+use crate::vec::Vec;
+
+/// Generates `len <= N` arbitrary elements.
+///
+/// The first `len` elements of the returned array are owned by the caller, who must drop the
+/// remaining ones with [`drop_tail`] once they have been moved out.
+fn any_elements<T: Arbitrary, const N: usize>() -> (ManuallyDrop<[T; N]>, usize) {
+    let len = kani::any_where(|len: &usize| *len <= N);
+    (ManuallyDrop::new(kani::any()), len)
+}
+
+/// Drops the elements of `elems` from index `len` on.
+fn drop_tail<T, const N: usize>(mut elems: ManuallyDrop<[T; N]>, len: usize) {
+    // SAFETY: `elems[len..]` has not been moved out, and `elems` is never used again.
+    unsafe { ptr::drop_in_place(&mut elems[len..]) }
+}
+
+/// Generates a vector with at most `N` elements, and with a capacity between its length and `N`.
+pub fn any_vec<T: Arbitrary, const N: usize>() -> Vec<T> {
+    let (elems, len) = any_elements::<T, N>();
+    let capacity = kani::any_where(|cap: &usize| len <= *cap && *cap <= N);
+    let mut vec = Vec::with_capacity(capacity);
+    // SAFETY: the vector has room for `len` elements, which are moved out of `elems`.
+    unsafe {
+        ptr::copy_nonoverlapping(elems.as_ptr(), vec.as_mut_ptr(), len);
+        vec.set_len(len);
+    }
+    drop_tail(elems, len);
+    vec
+}
+
+/// Generates a deque with at most `N` elements, with a capacity between its length and `N`, and
+/// whose first element can be stored anywhere in its buffer.
+pub fn any_vec_deque<T: Arbitrary, const N: usize>() -> VecDeque<T> {
+    let (elems, len) = any_elements::<T, N>();
+    let capacity = kani::any_where(|cap: &usize| len <= *cap && *cap <= N);
+    let (buf, _, capacity) = Vec::<T>::with_capacity(capacity).into_raw_parts();
+    let head = if capacity == 0 { 0 } else { kani::any_where(|head: &usize| *head < capacity) };
+    // The elements are stored in `buf[head..]` and then wrap around to `buf[..len - first]`.
+    let first = len.min(capacity - head);
+    // SAFETY: both copies stay in the buffer, which has room for `capacity >= len` elements, and
+    // the elements are moved out of `elems`. The resulting deque owns the buffer and the `len`
+    // elements starting at `head`.
+    unsafe {
+        ptr::copy_nonoverlapping(elems.as_ptr(), buf.add(head), first);
+        ptr::copy_nonoverlapping(elems.as_ptr().add(first), buf, len - first);
+        drop_tail(elems, len);
+        VecDeque::from_raw_parts_with_head(buf, capacity, head, len)
+    }
+}
+
+/// Generates a string of at most `N` bytes, with a capacity between its length and `N`.
+pub fn any_string<const N: usize>() -> String {
+    let bytes = any_vec::<u8, N>();
+    kani::assume(core::str::from_utf8(&bytes).is_ok());
+    // SAFETY: just checked that the bytes are valid UTF-8.
+    unsafe { String::from_utf8_unchecked(bytes) }
+}
+
+/// Generates a C string of at most `N` bytes, not counting its nul terminator.
+pub fn any_c_string<const N: usize>() -> CString {
+    let bytes = any_vec::<u8, N>();
+    kani::assume(!bytes.contains(&0));
+    // SAFETY: just checked that the bytes contain no nul byte.
+    unsafe { CString::from_vec_unchecked(bytes) }
+}
+
+/// Generates a linked list with at most `N` elements.
+///
+/// The shape of a linked list only depends on its length, so pushing is enough to reach every
+/// state. Harnesses using this function must unwind at least `N + 1` times.
+pub fn any_linked_list<T: Arbitrary, const N: usize>() -> LinkedList<T> {
+    let len = kani::any_where(|len: &usize| *len <= N);
+    let mut list = LinkedList::new();
+    for _ in 0..len {
+        list.push_back(kani::any());
+    }
+    list
+}
+
+/// Generates a map with at most `N` entries, which is either a single leaf or a root whose
+/// children are leaves of arbitrary lengths.
+///
+/// Inserting entries one by one only reaches the shapes that splitting full leaves creates, so
+/// the map is built directly instead: the keys are arbitrary but ascending, and the entries are
+/// split into leaves holding between `MIN_LEN` (5) and the node capacity (11) entries each. The
+/// map can have two levels if `N` is at least 11. Harnesses using this function must unwind at least
+/// `N + 1` times.
+pub fn any_btree_map<K: Arbitrary + Ord, V: Arbitrary, const N: usize>() -> BTreeMap<K, V> {
+    let leaf_lens = BTreeMap::<K, V>::LEAF_LENS;
+    let (elems, len) = any_elements::<(K, V), N>();
+    kani::assume(elems[..len].is_sorted_by(|(k1, _), (k2, _)| k1 < k2));
+    let mut lens = Vec::new();
+    if len <= *leaf_lens.end() && kani::any() {
+        lens.push(len);
+    } else {
+        // The leaves take all entries but one separating key between each pair of them.
+        let mut rest = len;
+        loop {
+            let leaf_len = kani::any_where(|leaf_len: &usize| {
+                leaf_lens.contains(leaf_len) && *leaf_len <= rest
+            });
+            lens.push(leaf_len);
+            rest -= leaf_len;
+            if rest == 0 {
+                break;
+            }
+            rest -= 1;
+        }
+        kani::assume(lens.len() <= *leaf_lens.end() + 1);
+    }
+    // SAFETY: each of the first `len` elements is moved out exactly once, as the leaves and the
+    // root hold `len` entries in total.
+    let entries = elems[..len].iter().map(|entry| unsafe { ptr::read(entry) });
+    let map = BTreeMap::from_sorted_leaves(entries, &lens);
+    drop_tail(elems, len);
+    map
+}
+
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::ub_checks::Invariant;
+
+    use super::*;
+
+    const MAX_LEN: usize = 4;
+
+    #[kani::proof]
+    pub fn check_any_vec() {
+        let vec = any_vec::<u32, MAX_LEN>();
+        assert!(vec.len() <= vec.capacity());
+        assert!(vec.capacity() <= MAX_LEN);
+        let vec = any_vec::<(), MAX_LEN>();
+        assert!(vec.len() <= MAX_LEN);
+    }
+
+    #[kani::proof]
+    pub fn check_any_vec_deque() {
+        let deque = any_vec_deque::<u32, MAX_LEN>();
+        assert!(deque.len() <= deque.capacity());
+        let (front, back) = deque.as_slices();
+        assert_eq!(front.len() + back.len(), deque.len());
+        assert_eq!(deque.iter().count(), deque.len());
+    }
+
+    #[kani::proof]
+    pub fn check_any_vec_deque_make_contiguous() {
+        let mut deque = any_vec_deque::<u8, MAX_LEN>();
+        let len = deque.len();
+        deque.make_contiguous();
+        assert_eq!(deque.as_slices().0.len(), len);
+    }
+
+    #[kani::proof]
+    pub fn check_any_string() {
+        let s = any_string::<MAX_LEN>();
+        assert!(s.len() <= s.capacity());
+        assert!(core::str::from_utf8(s.as_bytes()).is_ok());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    pub fn check_any_c_string() {
+        let s = any_c_string::<MAX_LEN>();
+        assert!(s.as_bytes().len() <= MAX_LEN);
+        assert!(s.is_safe());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(5)]
+    pub fn check_any_linked_list() {
+        let list = any_linked_list::<u32, MAX_LEN>();
+        assert!(list.len() <= MAX_LEN);
+        assert_eq!(list.iter().rev().count(), list.len());
+    }
+
+    // Enough entries for a root with two leaves.
+    const MAX_MAP_LEN: usize = 12;
+
+    #[kani::proof]
+    #[kani::unwind(13)]
+    pub fn check_any_btree_map() {
+        let map = any_btree_map::<u8, u8, MAX_MAP_LEN>();
+        assert!(map.len() <= MAX_MAP_LEN);
+        assert_eq!(map.iter().count(), map.len());
+        assert!(map.keys().is_sorted());
+    }
+}
@@ -183,3 +183,55 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>,
         }
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::kani;
+
+    use crate::arbitrary::any_btree_map;
+
+    // One more entry than fits in a node, so that the root may have underfull children to fix.
+    const MAX_LEN: usize = 12;
+
+    // `remove` fixes the node it removed from, and its ancestors, through
+    // `fix_node_and_affected_ancestors`.
+    #[kani::proof]
+    #[kani::unwind(13)]
+    fn check_fix_node_and_affected_ancestors() {
+        let mut map = any_btree_map::<u8, u8, MAX_LEN>();
+        let len = map.len();
+        let key = kani::any();
+        let removed = map.remove(&key).is_some();
+        assert_eq!(map.len(), len - removed as usize);
+        assert!(!map.contains_key(&key));
+        assert_eq!(map.iter().count(), map.len());
+    }
+
+    // `split_off` fixes the right border of the left tree and the left border of the right tree.
+    #[kani::proof]
+    #[kani::unwind(13)]
+    fn check_fix_borders() {
+        let mut left = any_btree_map::<u8, u8, MAX_LEN>();
+        let len = left.len();
+        let key = kani::any();
+        let right = left.split_off(&key);
+        assert_eq!(left.len() + right.len(), len);
+        assert_eq!(left.iter().count(), left.len());
+        assert_eq!(right.iter().count(), right.len());
+        assert!(left.keys().all(|k| *k < key) && right.keys().all(|k| *k >= key));
+    }
+
+    // `append` builds a tree by pushing entries along its right border, then stocks up that
+    // border with `fix_right_border_of_plentiful`.
+    #[kani::proof]
+    #[kani::unwind(13)]
+    fn check_fix_right_border_of_plentiful() {
+        let mut map = any_btree_map::<u8, u8, MAX_LEN>();
+        let mut other = any_btree_map::<u8, u8, 1>();
+        map.append(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(map.iter().count(), map.len());
+        assert!(map.keys().is_sorted());
+    }
+}
@@ -3516,5 +3516,52 @@ impl fmt::Display for UnorderedKeyError {
 #[unstable(feature = "btree_cursors", issue = "107540")]
 impl Error for UnorderedKeyError {}
 
+#[cfg(kani)]
+impl<K, V> BTreeMap<K, V> {
+    /// The numbers of entries a leaf holds when it is not the root.
+    pub(crate) const LEAF_LENS: core::ops::RangeInclusive<usize> = MIN_LEN..=node::CAPACITY;
+
+    /// Creates a map whose leaves hold the given numbers of entries, taken in order from `entries`.
+    /// With a single leaf, the map is that leaf; otherwise the root is an internal node whose
+    /// keys are the entries between the leaves. Only used to generate arbitrary maps for
+    /// verification.
+    ///
+    /// `entries` must yield keys in strictly ascending order, and as many as the leaves and the
+    /// root hold. If there are several leaves, there must be at most `CAPACITY + 1` of them and
+    /// each must hold a number of entries in `LEAF_LENS`.
+    pub(crate) fn from_sorted_leaves(
+        mut entries: impl Iterator<Item = (K, V)>,
+        leaf_lens: &[usize],
+    ) -> Self {
+        let fill_leaf = |entries: &mut dyn Iterator<Item = (K, V)>, len| {
+            let mut leaf = NodeRef::new_leaf(Global);
+            for _ in 0..len {
+                let (key, val) = entries.next().unwrap();
+                leaf.borrow_mut().push(key, val);
+            }
+            leaf.forget_type()
+        };
+        let (&first_len, other_lens) = leaf_lens.split_first().unwrap();
+        let mut root = fill_leaf(&mut entries, first_len);
+        let mut length = first_len;
+        if !other_lens.is_empty() {
+            assert!(leaf_lens.iter().all(|len| Self::LEAF_LENS.contains(len)));
+            let mut internal = root.push_internal_level(Global);
+            for &len in other_lens {
+                let (key, val) = entries.next().unwrap();
+                let leaf = fill_leaf(&mut entries, len);
+                internal.push(key, val, leaf);
+                length += 1 + len;
+            }
+        }
+        BTreeMap {
+            root: Some(root),
+            length,
+            alloc: ManuallyDrop::new(Global),
+            _marker: PhantomData,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests;
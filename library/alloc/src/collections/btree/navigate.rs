@@ -2,6 +2,8 @@ use core::borrow::Borrow;
 use core::ops::RangeBounds;
 use core::{hint, ptr};
 
+use safety::{ensures, requires};
+
 use super::node::ForceResult::*;
 use super::node::{Handle, NodeRef, marker};
 use super::search::SearchBound;
@@ -157,11 +159,13 @@ impl<BorrowType, K, V> LazyLeafRange<BorrowType, K, V> {
 
 impl<'a, K, V> LazyLeafRange<marker::Immut<'a>, K, V> {
     #[inline]
+    #[requires(self.front.is_some())]
     pub(super) unsafe fn next_unchecked(&mut self) -> (&'a K, &'a V) {
         unsafe { self.init_front().unwrap().next_unchecked() }
     }
 
     #[inline]
+    #[requires(self.back.is_some())]
     pub(super) unsafe fn next_back_unchecked(&mut self) -> (&'a K, &'a V) {
         unsafe { self.init_back().unwrap().next_back_unchecked() }
     }
@@ -169,11 +173,13 @@ impl<'a, K, V> LazyLeafRange<marker::Immut<'a>, K, V> {
 
 impl<'a, K, V> LazyLeafRange<marker::ValMut<'a>, K, V> {
     #[inline]
+    #[requires(self.front.is_some())]
     pub(super) unsafe fn next_unchecked(&mut self) -> (&'a K, &'a mut V) {
         unsafe { self.init_front().unwrap().next_unchecked() }
     }
 
     #[inline]
+    #[requires(self.back.is_some())]
     pub(super) unsafe fn next_back_unchecked(&mut self) -> (&'a K, &'a mut V) {
         unsafe { self.init_back().unwrap().next_back_unchecked() }
     }
@@ -190,6 +196,7 @@ impl<K, V> LazyLeafRange<marker::Dying, K, V> {
     }
 
     #[inline]
+    #[requires(self.front.is_some())]
     pub(super) unsafe fn deallocating_next_unchecked<A: Allocator + Clone>(
         &mut self,
         alloc: A,
@@ -200,6 +207,7 @@ impl<K, V> LazyLeafRange<marker::Dying, K, V> {
     }
 
     #[inline]
+    #[requires(self.back.is_some())]
     pub(super) unsafe fn deallocating_next_back_unchecked<A: Allocator + Clone>(
         &mut self,
         alloc: A,
@@ -261,6 +269,7 @@ impl<BorrowType: marker::BorrowType, K, V> NodeRef<BorrowType, K, V, marker::Lea
     /// # Safety
     /// Unless `BorrowType` is `Immut`, do not use the handles to visit the same
     /// KV twice.
+    #[ensures(|range| range.front.is_some() == range.back.is_some())]
     unsafe fn find_leaf_edges_spanning_range<Q: ?Sized, R>(
         self,
         range: R,
@@ -456,6 +465,7 @@ impl<K, V> Handle<NodeRef<marker::Dying, K, V, marker::Leaf>, marker::Edge> {
     ///   `deallocating_next_back`.
     /// - The returned KV handle is only valid to access the key and value,
     ///   and only valid until the next call to a `deallocating_` method.
+    #[ensures(|next| next.as_ref().is_none_or(|(_, kv)| kv.idx() < kv.reborrow().into_node().len()))]
     unsafe fn deallocating_next<A: Allocator + Clone>(
         self,
         alloc: A,
@@ -488,6 +498,7 @@ impl<K, V> Handle<NodeRef<marker::Dying, K, V, marker::Leaf>, marker::Edge> {
     ///   `deallocating_next`.
     /// - The returned KV handle is only valid to access the key and value,
     ///   and only valid until the next call to a `deallocating_` method.
+    #[ensures(|next| next.as_ref().is_none_or(|(_, kv)| kv.idx() < kv.reborrow().into_node().len()))]
     unsafe fn deallocating_next_back<A: Allocator + Clone>(
         self,
         alloc: A,
@@ -529,6 +540,7 @@ impl<'a, K, V> Handle<NodeRef<marker::Immut<'a>, K, V, marker::Leaf>, marker::Ed
     ///
     /// # Safety
     /// There must be another KV in the direction travelled.
+    #[requires(self.reborrow().next_kv().is_ok())]
     unsafe fn next_unchecked(&mut self) -> (&'a K, &'a V) {
         super::mem::replace(self, |leaf_edge| {
             let kv = leaf_edge.next_kv().ok().unwrap();
@@ -541,6 +553,7 @@ impl<'a, K, V> Handle<NodeRef<marker::Immut<'a>, K, V, marker::Leaf>, marker::Ed
     ///
     /// # Safety
     /// There must be another KV in the direction travelled.
+    #[requires(self.reborrow().next_back_kv().is_ok())]
     unsafe fn next_back_unchecked(&mut self) -> (&'a K, &'a V) {
         super::mem::replace(self, |leaf_edge| {
             let kv = leaf_edge.next_back_kv().ok().unwrap();
@@ -555,6 +568,7 @@ impl<'a, K, V> Handle<NodeRef<marker::ValMut<'a>, K, V, marker::Leaf>, marker::E
     ///
     /// # Safety
     /// There must be another KV in the direction travelled.
+    #[requires(self.reborrow().next_kv().is_ok())]
     unsafe fn next_unchecked(&mut self) -> (&'a K, &'a mut V) {
         let kv = super::mem::replace(self, |leaf_edge| {
             let kv = leaf_edge.next_kv().ok().unwrap();
@@ -569,6 +583,7 @@ impl<'a, K, V> Handle<NodeRef<marker::ValMut<'a>, K, V, marker::Leaf>, marker::E
     ///
     /// # Safety
     /// There must be another KV in the direction travelled.
+    #[requires(self.reborrow().next_back_kv().is_ok())]
     unsafe fn next_back_unchecked(&mut self) -> (&'a K, &'a mut V) {
         let kv = super::mem::replace(self, |leaf_edge| {
             let kv = leaf_edge.next_back_kv().ok().unwrap();
@@ -592,6 +607,7 @@ impl<K, V> Handle<NodeRef<marker::Dying, K, V, marker::Leaf>, marker::Edge> {
     ///
     /// The only safe way to proceed with the updated handle is to compare it, drop it,
     /// or call this method or counterpart `deallocating_next_back_unchecked` again.
+    #[requires(self.reborrow().next_kv().is_ok())]
     unsafe fn deallocating_next_unchecked<A: Allocator + Clone>(
         &mut self,
         alloc: A,
@@ -613,6 +629,7 @@ impl<K, V> Handle<NodeRef<marker::Dying, K, V, marker::Leaf>, marker::Edge> {
     ///
     /// The only safe way to proceed with the updated handle is to compare it, drop it,
     /// or call this method or counterpart `deallocating_next_unchecked` again.
+    #[requires(self.reborrow().next_back_kv().is_ok())]
     unsafe fn deallocating_next_back_unchecked<A: Allocator + Clone>(
         &mut self,
         alloc: A,
@@ -785,3 +802,187 @@ impl<BorrowType: marker::BorrowType, K, V> NodeRef<BorrowType, K, V, marker::Lea
         }
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::kani;
+
+    use super::*;
+    use crate::arbitrary::any_btree_map;
+    use crate::collections::BTreeMap;
+
+    // One more entry than fits in a node, so that the root may have two leaves and iteration may
+    // ascend from a leaf into its parent.
+    const MAX_LEN: usize = 12;
+
+    fn any_map() -> BTreeMap<u8, u8> {
+        any_btree_map::<u8, u8, MAX_LEN>()
+    }
+
+    #[kani::proof_for_contract(LazyLeafRange::<marker::Immut<'static>, u8, u8>::next_unchecked)]
+    #[kani::unwind(13)]
+    fn check_lazy_next_unchecked() {
+        let map = any_map();
+        let mut iter = map.iter();
+        for _ in 0..map.len() {
+            assert!(iter.next().is_some());
+        }
+        assert!(iter.next().is_none());
+    }
+
+    #[kani::proof_for_contract(LazyLeafRange::<marker::Immut<'static>, u8, u8>::next_back_unchecked)]
+    #[kani::unwind(13)]
+    fn check_lazy_next_back_unchecked() {
+        let map = any_map();
+        let mut iter = map.iter();
+        for _ in 0..map.len() {
+            assert!(iter.next_back().is_some());
+        }
+        assert!(iter.next_back().is_none());
+    }
+
+    #[kani::proof_for_contract(LazyLeafRange::<marker::ValMut<'static>, u8, u8>::next_unchecked)]
+    #[kani::unwind(13)]
+    fn check_lazy_next_unchecked_valmut() {
+        let mut map = any_map();
+        let len = map.len();
+        let mut iter = map.values_mut();
+        for _ in 0..len {
+            *iter.next().unwrap() = kani::any();
+        }
+        assert!(iter.next().is_none());
+    }
+
+    #[kani::proof_for_contract(LazyLeafRange::<marker::ValMut<'static>, u8, u8>::next_back_unchecked)]
+    #[kani::unwind(13)]
+    fn check_lazy_next_back_unchecked_valmut() {
+        let mut map = any_map();
+        let len = map.len();
+        let mut iter = map.values_mut();
+        for _ in 0..len {
+            *iter.next_back().unwrap() = kani::any();
+        }
+        assert!(iter.next_back().is_none());
+    }
+
+    #[kani::proof_for_contract(LazyLeafRange::<marker::Dying, u8, u8>::deallocating_next_unchecked)]
+    #[kani::unwind(13)]
+    fn check_lazy_deallocating_next_unchecked() {
+        let map = any_map();
+        let len = map.len();
+        let mut iter = map.into_iter();
+        for _ in 0..len {
+            assert!(iter.next().is_some());
+        }
+        assert!(iter.next().is_none());
+    }
+
+    #[kani::proof_for_contract(LazyLeafRange::<marker::Dying, u8, u8>::deallocating_next_back_unchecked)]
+    #[kani::unwind(13)]
+    fn check_lazy_deallocating_next_back_unchecked() {
+        let map = any_map();
+        let len = map.len();
+        let mut iter = map.into_iter();
+        for _ in 0..len {
+            assert!(iter.next_back().is_some());
+        }
+        assert!(iter.next_back().is_none());
+    }
+
+    #[kani::proof_for_contract(NodeRef::find_leaf_edges_spanning_range)]
+    #[kani::unwind(13)]
+    fn check_find_leaf_edges_spanning_range() {
+        let map = any_map();
+        let (start, end): (u8, u8) = (kani::any(), kani::any());
+        kani::assume(start <= end);
+        let expected = map.keys().filter(|key| (start..end).contains(*key)).count();
+        let mut range = map.range(start..end);
+        for _ in 0..expected {
+            assert!(range.next().is_some());
+        }
+        assert!(range.next().is_none());
+    }
+
+    #[kani::proof_for_contract(
+        Handle::<NodeRef<marker::Immut<'static>, u8, u8, marker::Leaf>, marker::Edge>::next_unchecked
+    )]
+    #[kani::unwind(13)]
+    fn check_next_unchecked() {
+        let map = any_map();
+        let mut iter = map.iter();
+        while iter.next().is_some() {}
+    }
+
+    #[kani::proof_for_contract(
+        Handle::<NodeRef<marker::Immut<'static>, u8, u8, marker::Leaf>, marker::Edge>::next_back_unchecked
+    )]
+    #[kani::unwind(13)]
+    fn check_next_back_unchecked() {
+        let map = any_map();
+        let mut iter = map.iter();
+        while iter.next_back().is_some() {}
+    }
+
+    #[kani::proof_for_contract(
+        Handle::<NodeRef<marker::ValMut<'static>, u8, u8, marker::Leaf>, marker::Edge>::next_unchecked
+    )]
+    #[kani::unwind(13)]
+    fn check_next_unchecked_valmut() {
+        let mut map = any_map();
+        let mut iter = map.iter_mut();
+        while let Some((_, val)) = iter.next() {
+            *val = kani::any();
+        }
+    }
+
+    #[kani::proof_for_contract(
+        Handle::<NodeRef<marker::ValMut<'static>, u8, u8, marker::Leaf>, marker::Edge>::next_back_unchecked
+    )]
+    #[kani::unwind(13)]
+    fn check_next_back_unchecked_valmut() {
+        let mut map = any_map();
+        let mut iter = map.iter_mut();
+        while let Some((_, val)) = iter.next_back() {
+            *val = kani::any();
+        }
+    }
+
+    #[kani::proof_for_contract(
+        Handle::<NodeRef<marker::Dying, u8, u8, marker::Leaf>, marker::Edge>::deallocating_next_unchecked
+    )]
+    #[kani::unwind(13)]
+    fn check_deallocating_next_unchecked() {
+        let mut iter = any_map().into_iter();
+        while iter.next().is_some() {}
+    }
+
+    #[kani::proof_for_contract(
+        Handle::<NodeRef<marker::Dying, u8, u8, marker::Leaf>, marker::Edge>::deallocating_next_back_unchecked
+    )]
+    #[kani::unwind(13)]
+    fn check_deallocating_next_back_unchecked() {
+        let mut iter = any_map().into_iter();
+        while iter.next_back().is_some() {}
+    }
+
+    // Iterating from both ends deallocates the nodes left behind on either side, until both
+    // ends meet and `deallocating_end` frees the remaining ancestors.
+    #[kani::proof_for_contract(
+        Handle::<NodeRef<marker::Dying, u8, u8, marker::Leaf>, marker::Edge>::deallocating_next
+    )]
+    #[kani::unwind(13)]
+    fn check_deallocating_next() {
+        let mut iter = any_map().into_iter();
+        while iter.next().is_some() && iter.next_back().is_some() {}
+    }
+
+    #[kani::proof_for_contract(
+        Handle::<NodeRef<marker::Dying, u8, u8, marker::Leaf>, marker::Edge>::deallocating_next_back
+    )]
+    #[kani::unwind(13)]
+    fn check_deallocating_next_back() {
+        let mut iter = any_map().into_iter();
+        while iter.next_back().is_some() && iter.next().is_some() {}
+    }
+}
@@ -35,6 +35,12 @@ use core::marker::PhantomData;
 use core::mem::{self, MaybeUninit};
 use core::ptr::{self, NonNull};
 use core::slice::SliceIndex;
+// Used only for contract verification.
+#[allow(unused_imports)]
+use core::ub_checks;
+use core::ub_checks::Invariant;
+
+use safety::{ensures, requires};
 
 use crate::alloc::{Allocator, Layout};
 use crate::boxed::Box;
@@ -67,6 +73,7 @@ struct LeafNode<K, V> {
 
 impl<K, V> LeafNode<K, V> {
     /// Initializes a new `LeafNode` in-place.
+    #[requires(ub_checks::can_write(this))]
     unsafe fn init(this: *mut Self) {
         // As a general policy, we leave fields uninitialized if they can be, as this should
         // be both slightly faster and easier to track in Valgrind.
@@ -110,6 +117,7 @@ impl<K, V> InternalNode<K, V> {
     /// An invariant of internal nodes is that they have at least one
     /// initialized and valid edge. This function does not set up
     /// such an edge.
+    #[ensures(|node| node.data.len == 0 && node.data.parent.is_none())]
     unsafe fn new<A: Allocator + Clone>(alloc: A) -> Box<Self, A> {
         unsafe {
             let mut node = Box::<Self, _>::new_uninit_in(alloc);
@@ -212,6 +220,102 @@ unsafe impl<K: Send, V: Send, Type> Send for NodeRef<marker::ValMut<'_>, K, V, T
 unsafe impl<K: Send, V: Send, Type> Send for NodeRef<marker::Owned, K, V, Type> {}
 unsafe impl<K: Send, V: Send, Type> Send for NodeRef<marker::Dying, K, V, Type> {}
 
+impl<BorrowType, K, V, Type> NodeRef<BorrowType, K, V, Type> {
+    /// Checks the invariants of a node that do not depend on `Type`: the node stores at most
+    /// `CAPACITY` key-value pairs, its parent edge at `parent_idx` points back to it, and, if
+    /// the node is internal, each of its `len + 1` children points back to the right edge.
+    ///
+    /// Only reads the `len` and link fields, so it is usable for any `BorrowType`, but the node,
+    /// its parent and its children must not have been deallocated.
+    fn is_safe_node(&self) -> bool {
+        // Reads an initialized edge without creating a reference to the `edges` array.
+        let edge_at = |node: *mut InternalNode<K, V>, idx: usize| unsafe {
+            (&raw const (*node).edges).cast::<BoxedNode<K, V>>().add(idx).read()
+        };
+        let leaf = Self::as_leaf_ptr(self);
+        let len = self.len();
+        if len > CAPACITY {
+            return false;
+        }
+        if let Some(parent) = unsafe { (*leaf).parent } {
+            let parent_idx = usize::from(unsafe { (*leaf).parent_idx.assume_init() });
+            let parent = parent.as_ptr();
+            if parent_idx > usize::from(unsafe { (*parent).data.len })
+                || edge_at(parent, parent_idx) != self.node
+            {
+                return false;
+            }
+        }
+        if self.height == 0 {
+            return true;
+        }
+        (0..=len).all(|idx| self.child_links_back(idx))
+    }
+
+    /// Checks that the child of this internal node at edge `idx` links back to that edge.
+    ///
+    /// Like `is_safe_node`, only reads the edge and the link fields of the child.
+    fn child_links_back(&self, idx: usize) -> bool {
+        let internal = Self::as_leaf_ptr(self) as *mut InternalNode<K, V>;
+        let child =
+            unsafe { (&raw const (*internal).edges).cast::<BoxedNode<K, V>>().add(idx).read() };
+        let child = child.as_ptr();
+        unsafe { (*child).parent }.map(NonNull::cast) == Some(self.node)
+            && usize::from(unsafe { (*child).parent_idx.assume_init() }) == idx
+    }
+
+    /// Checks that the children of this internal node at the edges in `range` link back to them.
+    #[allow(dead_code)] // Only used by contracts.
+    fn children_link_back<R: Iterator<Item = usize> + Clone>(&self, range: &R) -> bool {
+        range.clone().all(|idx| self.child_links_back(idx))
+    }
+
+    /// Returns the link of the node to its parent edge, if it has a parent.
+    fn parent_link(&self) -> Option<(NonNull<InternalNode<K, V>>, usize)> {
+        let leaf = Self::as_leaf_ptr(self);
+        let parent = unsafe { (*leaf).parent }?;
+        Some((parent, usize::from(unsafe { (*leaf).parent_idx.assume_init() })))
+    }
+}
+
+/// Returns whether `index` is in bounds of a storage area of `len` elements of type `T`.
+///
+/// Checks the index against a stand-in area, rather than against the area of a node, because
+/// creating a reference to the latter could invalidate outstanding references to its elements.
+#[allow(dead_code)] // Only used by contracts.
+fn is_area_index<T, I: SliceIndex<[MaybeUninit<T>]>>(index: I, len: usize) -> bool {
+    let area = [const { MaybeUninit::<T>::uninit() }; CAPACITY + 1];
+    index.get(&area[..len]).is_some()
+}
+
+/// Returns whether `borrow` lies within the storage area `area`.
+#[allow(dead_code)] // Only used by contracts.
+fn is_within_area<T, const N: usize, Output: ?Sized>(borrow: &Output, area: *const [T; N]) -> bool {
+    let (start, addr) = (area.addr(), (&raw const *borrow).addr());
+    start <= addr && addr + mem::size_of_val(borrow) <= start + mem::size_of::<[T; N]>()
+}
+
+impl<BorrowType, K, V> Invariant for NodeRef<BorrowType, K, V, marker::Leaf> {
+    /// A leaf node has height zero.
+    fn is_safe(&self) -> bool {
+        self.height == 0 && self.is_safe_node()
+    }
+}
+
+impl<BorrowType, K, V> Invariant for NodeRef<BorrowType, K, V, marker::Internal> {
+    /// An internal node has a non-zero height.
+    fn is_safe(&self) -> bool {
+        self.height > 0 && self.is_safe_node()
+    }
+}
+
+impl<BorrowType, K, V> Invariant for NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
+    /// The height tells whether the node is a leaf or an internal node.
+    fn is_safe(&self) -> bool {
+        self.is_safe_node()
+    }
+}
+
 impl<K, V> NodeRef<marker::Owned, K, V, marker::Leaf> {
     pub(super) fn new_leaf<A: Allocator + Clone>(alloc: A) -> Self {
         Self::from_new_leaf(LeafNode::new(alloc))
@@ -231,6 +335,8 @@ impl<K, V> NodeRef<marker::Owned, K, V, marker::Internal> {
 
     /// # Safety
     /// `height` must not be zero.
+    #[requires(height > 0)]
+    #[ensures(|node| node.is_safe())]
     unsafe fn from_new_internal<A: Allocator + Clone>(
         internal: Box<InternalNode<K, V>, A>,
         height: usize,
@@ -391,6 +497,7 @@ impl<K, V> NodeRef<marker::Dying, K, V, marker::LeafOrInternal> {
     /// Similar to `ascend`, gets a reference to a node's parent node, but also
     /// deallocates the current node in the process. This is unsafe because the
     /// current node will still be accessible despite being deallocated.
+    #[requires(ub_checks::can_dereference(self.node.as_ptr()))]
     pub(super) unsafe fn deallocate_and_ascend<A: Allocator + Clone>(
         self,
         alloc: A,
@@ -423,6 +530,7 @@ impl<'a, K, V, Type> NodeRef<marker::Mut<'a>, K, V, Type> {
     // FIXME(@gereeter) consider adding yet another type parameter to `NodeRef`
     // that restricts the use of navigation methods on reborrowed pointers,
     // preventing this unsafety.
+    #[ensures(|node| node.node == old(self.node) && node.height == old(self.height))]
     unsafe fn reborrow_mut(&mut self) -> NodeRef<marker::Mut<'_>, K, V, Type> {
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
     }
@@ -455,6 +563,7 @@ impl<K, V, Type> NodeRef<marker::DormantMut, K, V, Type> {
     ///
     /// The reborrow must have ended, i.e., the reference returned by `new` and
     /// all pointers and references derived from it, must not be used anymore.
+    #[ensures(|node| node.node == old(self.node) && node.height == old(self.height))]
     pub(super) unsafe fn awaken<'a>(self) -> NodeRef<marker::Mut<'a>, K, V, Type> {
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
     }
@@ -474,9 +583,11 @@ impl<'a, K: 'a, V: 'a, Type> NodeRef<marker::Mut<'a>, K, V, Type> {
     ///
     /// # Safety
     /// `index` is in bounds of 0..CAPACITY
+    #[requires(is_area_index::<K, _>(index.clone(), CAPACITY))]
+    #[ensures(|area| is_within_area(&**area, unsafe { &raw const (*old(Self::as_leaf_ptr(self))).keys }))]
     unsafe fn key_area_mut<I, Output: ?Sized>(&mut self, index: I) -> &mut Output
     where
-        I: SliceIndex<[MaybeUninit<K>], Output = Output>,
+        I: SliceIndex<[MaybeUninit<K>], Output = Output> + Clone,
     {
         // SAFETY: the caller will not be able to call further methods on self
         // until the key slice reference is dropped, as we have unique access
@@ -488,9 +599,11 @@ impl<'a, K: 'a, V: 'a, Type> NodeRef<marker::Mut<'a>, K, V, Type> {
     ///
     /// # Safety
     /// `index` is in bounds of 0..CAPACITY
+    #[requires(is_area_index::<V, _>(index.clone(), CAPACITY))]
+    #[ensures(|area| is_within_area(&**area, unsafe { &raw const (*old(Self::as_leaf_ptr(self))).vals }))]
     unsafe fn val_area_mut<I, Output: ?Sized>(&mut self, index: I) -> &mut Output
     where
-        I: SliceIndex<[MaybeUninit<V>], Output = Output>,
+        I: SliceIndex<[MaybeUninit<V>], Output = Output> + Clone,
     {
         // SAFETY: the caller will not be able to call further methods on self
         // until the value slice reference is dropped, as we have unique access
@@ -504,9 +617,11 @@ impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
     ///
     /// # Safety
     /// `index` is in bounds of 0..CAPACITY + 1
+    #[requires(is_area_index::<BoxedNode<K, V>, _>(index.clone(), CAPACITY + 1))]
+    #[ensures(|area| is_within_area(&**area, unsafe { &raw const (*old(Self::as_internal_ptr(self))).edges }))]
     unsafe fn edge_area_mut<I, Output: ?Sized>(&mut self, index: I) -> &mut Output
     where
-        I: SliceIndex<[MaybeUninit<BoxedNode<K, V>>], Output = Output>,
+        I: SliceIndex<[MaybeUninit<BoxedNode<K, V>>], Output = Output> + Clone,
     {
         // SAFETY: the caller will not be able to call further methods on self
         // until the edge slice reference is dropped, as we have unique access
@@ -518,6 +633,7 @@ impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
 impl<'a, K, V, Type> NodeRef<marker::ValMut<'a>, K, V, Type> {
     /// # Safety
     /// - The node has more than `idx` initialized elements.
+    #[requires(idx < self.len())]
     unsafe fn into_key_val_mut_at(mut self, idx: usize) -> (&'a K, &'a mut V) {
         // We only create a reference to the one element we are interested in,
         // to avoid aliasing with outstanding references to other elements,
@@ -544,7 +660,12 @@ impl<'a, K: 'a, V: 'a, Type> NodeRef<marker::Mut<'a>, K, V, Type> {
 impl<'a, K, V> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
     /// # Safety
     /// Every item returned by `range` is a valid edge index for the node.
-    unsafe fn correct_childrens_parent_links<R: Iterator<Item = usize>>(&mut self, range: R) {
+    #[requires(range.clone().all(|i| i <= self.len()))]
+    #[ensures(|_| self.children_link_back(&old(range.clone())))]
+    unsafe fn correct_childrens_parent_links<R: Iterator<Item = usize> + Clone>(
+        &mut self,
+        range: R,
+    ) {
         for i in range {
             debug_assert!(i <= self.len());
             unsafe { Handle::new_edge(self.reborrow_mut(), i) }.correct_parent_link();
@@ -651,6 +772,7 @@ impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::Leaf> {
     /// # Safety
     ///
     /// The returned handle has an unbound lifetime.
+    #[ensures(|handle| handle.idx + 1 == self.len())]
     pub(super) unsafe fn push_with_handle<'b>(
         &mut self,
         key: K,
@@ -737,6 +859,7 @@ impl<BorrowType, K, V> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
 
 impl<'a, K, V> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
     /// Unsafely asserts to the compiler the static information that this node is a `Leaf`.
+    #[requires(self.height == 0)]
     pub(super) unsafe fn cast_to_leaf_unchecked(
         self,
     ) -> NodeRef<marker::Mut<'a>, K, V, marker::Leaf> {
@@ -745,6 +868,7 @@ impl<'a, K, V> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
     }
 
     /// Unsafely asserts to the compiler the static information that this node is an `Internal`.
+    #[requires(self.height > 0)]
     unsafe fn cast_to_internal_unchecked(self) -> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
         debug_assert!(self.height > 0);
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
@@ -789,6 +913,7 @@ impl<Node, Type> Handle<Node, Type> {
 impl<BorrowType, K, V, NodeType> Handle<NodeRef<BorrowType, K, V, NodeType>, marker::KV> {
     /// Creates a new handle to a key-value pair in `node`.
     /// Unsafe because the caller must ensure that `idx < node.len()`.
+    #[requires(idx < node.len())]
     pub(super) unsafe fn new_kv(node: NodeRef<BorrowType, K, V, NodeType>, idx: usize) -> Self {
         debug_assert!(idx < node.len());
 
@@ -831,6 +956,7 @@ impl<'a, K, V, NodeType, HandleType> Handle<NodeRef<marker::Mut<'a>, K, V, NodeT
     /// dangerous.
     ///
     /// For details, see `NodeRef::reborrow_mut`.
+    #[ensures(|handle| handle.node.node == old(self.node.node) && handle.idx == old(self.idx))]
     pub(super) unsafe fn reborrow_mut(
         &mut self,
     ) -> Handle<NodeRef<marker::Mut<'_>, K, V, NodeType>, HandleType> {
@@ -855,6 +981,7 @@ impl<K, V, NodeType, HandleType> Handle<NodeRef<marker::DormantMut, K, V, NodeTy
     ///
     /// The reborrow must have ended, i.e., the reference returned by `new` and
     /// all pointers and references derived from it, must not be used anymore.
+    #[ensures(|handle| handle.node.node == old(self.node.node) && handle.idx == old(self.idx))]
     pub(super) unsafe fn awaken<'a>(
         self,
     ) -> Handle<NodeRef<marker::Mut<'a>, K, V, NodeType>, HandleType> {
@@ -865,6 +992,7 @@ impl<K, V, NodeType, HandleType> Handle<NodeRef<marker::DormantMut, K, V, NodeTy
 impl<BorrowType, K, V, NodeType> Handle<NodeRef<BorrowType, K, V, NodeType>, marker::Edge> {
     /// Creates a new handle to an edge in `node`.
     /// Unsafe because the caller must ensure that `idx <= node.len()`.
+    #[requires(idx <= node.len())]
     pub(super) unsafe fn new_edge(node: NodeRef<BorrowType, K, V, NodeType>, idx: usize) -> Self {
         debug_assert!(idx <= node.len());
 
@@ -917,6 +1045,8 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// Inserts a new key-value pair between the key-value pairs to the right and left of
     /// this edge. This method assumes that there is enough space in the node for the new
     /// pair to fit.
+    #[requires(self.node.len() < CAPACITY)]
+    #[ensures(|kv| kv.idx == old(self.idx) && kv.node.len() == old(self.node.len()) + 1)]
     unsafe fn insert_fit(
         mut self,
         key: K,
@@ -1168,6 +1298,7 @@ impl<K, V, NodeType> Handle<NodeRef<marker::Dying, K, V, NodeType>, marker::KV>
     /// Extracts the key and value that the KV handle refers to.
     /// # Safety
     /// The node that the handle refers to must not yet have been deallocated.
+    #[requires(self.idx < self.node.len())]
     pub(super) unsafe fn into_key_val(mut self) -> (K, V) {
         debug_assert!(self.idx < self.node.len());
         let leaf = self.node.as_leaf_dying();
@@ -1182,6 +1313,7 @@ impl<K, V, NodeType> Handle<NodeRef<marker::Dying, K, V, NodeType>, marker::KV>
     /// # Safety
     /// The node that the handle refers to must not yet have been deallocated.
     #[inline]
+    #[requires(self.idx < self.node.len())]
     pub(super) unsafe fn drop_key_val(mut self) {
         // Run the destructor of the value even if the destructor of the key panics.
         struct Dropper<'a, T>(&'a mut MaybeUninit<T>);
@@ -1241,6 +1373,11 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// - The key and value pointed to by this handle are extracted.
     /// - All the key-value pairs to the right of this handle are put into a newly
     ///   allocated node.
+    #[requires(self.node.is_safe())]
+    #[ensures(|result| result.left.len() == old(self.idx)
+        && result.right.len() == old(self.node.len()) - old(self.idx) - 1
+        && result.left.is_safe()
+        && result.right.is_safe())]
     pub(super) fn split<A: Allocator + Clone>(
         mut self,
         alloc: A,
@@ -1276,6 +1413,11 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>,
     /// - The key and value pointed to by this handle are extracted.
     /// - All the edges and key-value pairs to the right of this handle are put into
     ///   a newly allocated node.
+    #[requires(self.node.is_safe())]
+    #[ensures(|result| result.left.len() == old(self.idx)
+        && result.right.len() == old(self.node.len()) - old(self.idx) - 1
+        && result.left.is_safe()
+        && result.right.is_safe())]
     pub(super) fn split<A: Allocator + Clone>(
         mut self,
         alloc: A,
@@ -1355,6 +1497,19 @@ impl<'a, K, V> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
     }
 }
 
+impl<'a, K, V> Invariant for BalancingContext<'a, K, V> {
+    /// The children are the nodes on either side of the parent key-value pair.
+    fn is_safe(&self) -> bool {
+        let parent = NonNull::new(NodeRef::as_internal_ptr(&self.parent.node));
+        let idx = self.parent.idx;
+        self.parent.node.is_safe()
+            && self.left_child.is_safe()
+            && self.right_child.is_safe()
+            && self.left_child.parent_link() == parent.map(|parent| (parent, idx))
+            && self.right_child.parent_link() == parent.map(|parent| (parent, idx + 1))
+    }
+}
+
 impl<'a, K, V> BalancingContext<'a, K, V> {
     pub(super) fn left_child_len(&self) -> usize {
         self.left_child.len()
@@ -1459,6 +1614,9 @@ impl<'a, K: 'a, V: 'a> BalancingContext<'a, K, V> {
     /// the left child node and returns that child node.
     ///
     /// Panics unless we `.can_merge()`.
+    #[requires(self.is_safe())]
+    #[ensures(|child| child.len() == old(self.left_child_len() + 1 + self.right_child_len())
+        && child.is_safe())]
     pub(super) fn merge_tracking_child<A: Allocator + Clone>(
         self,
         alloc: A,
@@ -1494,6 +1652,10 @@ impl<'a, K: 'a, V: 'a> BalancingContext<'a, K, V> {
     /// of the parent, while pushing the old parent key-value pair into the right child.
     /// Returns a handle to the edge in the right child corresponding to where the original
     /// edge specified by `track_right_edge_idx` ended up.
+    #[requires(self.is_safe() && track_right_edge_idx <= self.right_child_len())]
+    #[ensures(|edge| edge.idx == old(track_right_edge_idx) + 1
+        && edge.node.len() == old(self.right_child_len()) + 1
+        && edge.node.is_safe())]
     pub(super) fn steal_left(
         mut self,
         track_right_edge_idx: usize,
@@ -1578,6 +1740,10 @@ impl<'a, K: 'a, V: 'a> BalancingContext<'a, K, V> {
     }
 
     /// The symmetric clone of `bulk_steal_left`.
+    #[requires(self.is_safe())]
+    #[ensures(|_| self.left_child_len() == old(self.left_child_len()) + count
+        && self.right_child_len() == old(self.right_child_len()) - count
+        && self.is_safe())]
     pub(super) fn bulk_steal_right(&mut self, count: usize) {
         assert!(count > 0);
         unsafe {
@@ -1687,6 +1853,7 @@ impl<BorrowType, K, V, Type> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInte
 
 impl<'a, K, V, Type> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, Type> {
     /// Unsafely asserts to the compiler the static information that the handle's node is a `Leaf`.
+    #[requires(self.node.height == 0)]
     pub(super) unsafe fn cast_to_leaf_unchecked(
         self,
     ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, Type> {
@@ -1809,6 +1976,7 @@ pub(super) mod marker {
 ///
 /// # Safety
 /// The slice has more than `idx` elements.
+#[requires(idx < slice.len())]
 unsafe fn slice_insert<T>(slice: &mut [MaybeUninit<T>], idx: usize, val: T) {
     unsafe {
         let len = slice.len();
@@ -1826,6 +1994,7 @@ unsafe fn slice_insert<T>(slice: &mut [MaybeUninit<T>], idx: usize, val: T) {
 ///
 /// # Safety
 /// The slice has more than `idx` elements.
+#[requires(idx < slice.len())]
 unsafe fn slice_remove<T>(slice: &mut [MaybeUninit<T>], idx: usize) -> T {
     unsafe {
         let len = slice.len();
@@ -1841,6 +2010,7 @@ unsafe fn slice_remove<T>(slice: &mut [MaybeUninit<T>], idx: usize) -> T {
 ///
 /// # Safety
 /// The slice has at least `distance` elements.
+#[requires(distance <= slice.len())]
 unsafe fn slice_shl<T>(slice: &mut [MaybeUninit<T>], distance: usize) {
     unsafe {
         let slice_ptr = slice.as_mut_ptr();
@@ -1852,6 +2022,7 @@ unsafe fn slice_shl<T>(slice: &mut [MaybeUninit<T>], distance: usize) {
 ///
 /// # Safety
 /// The slice has at least `distance` elements.
+#[requires(distance <= slice.len())]
 unsafe fn slice_shr<T>(slice: &mut [MaybeUninit<T>], distance: usize) {
     unsafe {
         let slice_ptr = slice.as_mut_ptr();
@@ -1871,3 +2042,454 @@ fn move_to_slice<T>(src: &mut [MaybeUninit<T>], dst: &mut [MaybeUninit<T>]) {
 
 #[cfg(test)]
 mod tests;
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::kani;
+
+    use super::*;
+    use crate::alloc::Global;
+
+    // Loops over the key-value pairs or edges of a node run at most `CAPACITY + 1` times, so the
+    // harnesses unwind `CAPACITY + 2` times.
+    fn any_len(max_len: usize) -> usize {
+        kani::any_where(|len: &usize| *len <= max_len)
+    }
+
+    /// Builds a tree of the given height whose nodes store at most `max_len` arbitrary
+    /// key-value pairs.
+    fn any_tree(height: usize, max_len: usize) -> Root<u8, u8> {
+        let len = any_len(max_len);
+        if height == 0 {
+            let mut leaf = NodeRef::new_leaf(Global);
+            for _ in 0..len {
+                leaf.borrow_mut().push(kani::any(), kani::any());
+            }
+            leaf.forget_type()
+        } else {
+            let mut node = NodeRef::new_internal(any_tree(height - 1, max_len), Global);
+            for _ in 0..len {
+                node.borrow_mut().push(kani::any(), kani::any(), any_tree(height - 1, max_len));
+            }
+            node.forget_type()
+        }
+    }
+
+    /// Builds an internal node with a single key-value pair, whose two children are trees of
+    /// height `height - 1`.
+    fn any_parent(
+        height: usize,
+        max_len: usize,
+    ) -> NodeRef<marker::Owned, u8, u8, marker::Internal> {
+        let mut parent = NodeRef::new_internal(any_tree(height - 1, max_len), Global);
+        parent.borrow_mut().push(kani::any(), kani::any(), any_tree(height - 1, max_len));
+        parent
+    }
+
+    /// Returns `node` or, if it is internal, one of its children, so that harnesses cover nodes
+    /// with and without a parent.
+    fn any_node_or_child<BorrowType: marker::BorrowType>(
+        node: NodeRef<BorrowType, u8, u8, marker::LeafOrInternal>,
+    ) -> NodeRef<BorrowType, u8, u8, marker::LeafOrInternal> {
+        match node.force() {
+            ForceResult::Internal(node) if kani::any() => {
+                let idx = any_len(node.len());
+                unsafe { Handle::new_edge(node, idx) }.descend()
+            }
+            ForceResult::Internal(node) => node.forget_type(),
+            ForceResult::Leaf(leaf) => leaf.forget_type(),
+        }
+    }
+
+    /// Returns one of the two leaves of `parent`, which has height 1.
+    fn any_child_leaf<'a>(
+        parent: NodeRef<marker::Mut<'a>, u8, u8, marker::Internal>,
+    ) -> NodeRef<marker::Mut<'a>, u8, u8, marker::Leaf> {
+        let ForceResult::Leaf(leaf) =
+            unsafe { Handle::new_edge(parent, any_len(1)) }.descend().force()
+        else {
+            unreachable!()
+        };
+        leaf
+    }
+
+    #[kani::proof_for_contract(LeafNode::init)]
+    fn check_leaf_init() {
+        let mut leaf = Box::<LeafNode<u8, u8>>::new_uninit();
+        unsafe { LeafNode::init(leaf.as_mut_ptr()) };
+        let leaf = unsafe { leaf.assume_init() };
+        assert_eq!(leaf.len, 0);
+        assert!(leaf.parent.is_none());
+    }
+
+    #[kani::proof_for_contract(InternalNode::new)]
+    fn check_internal_node_new() {
+        let _ = unsafe { InternalNode::<u8, u8>::new(Global) };
+    }
+
+    #[kani::proof_for_contract(NodeRef::from_new_internal)]
+    #[kani::unwind(13)]
+    fn check_from_new_internal() {
+        let node = NodeRef::new_internal(any_tree(0, CAPACITY), Global);
+        assert_eq!(node.height(), 1);
+        assert_eq!(node.len(), 0);
+    }
+
+    #[kani::proof_for_contract(NodeRef::deallocate_and_ascend)]
+    #[kani::unwind(13)]
+    fn check_deallocate_and_ascend() {
+        let mut parent = any_parent(1, CAPACITY);
+        let idx = any_len(1);
+        let edge = unsafe { Handle::new_edge(parent.borrow_mut(), idx) };
+        let child = edge.descend();
+        let dying = NodeRef { height: child.height, node: child.node, _marker: PhantomData };
+        let parent_edge = unsafe { dying.deallocate_and_ascend(Global) }.unwrap();
+        assert_eq!(parent_edge.idx(), idx);
+        assert!(parent_edge.into_node().node == parent.node);
+    }
+
+    #[kani::proof_for_contract(NodeRef::reborrow_mut)]
+    fn check_node_reborrow_mut() {
+        let mut root = Root::<u8, u8>::new(Global);
+        let mut node = root.borrow_mut();
+        let _ = unsafe { node.reborrow_mut() };
+    }
+
+    #[kani::proof_for_contract(NodeRef::awaken)]
+    fn check_node_awaken() {
+        let mut root = Root::<u8, u8>::new(Global);
+        let dormant = root.borrow_mut().dormant();
+        let _ = unsafe { dormant.awaken() };
+    }
+
+    #[kani::proof_for_contract(NodeRef::into_key_val_mut_at)]
+    #[kani::unwind(13)]
+    fn check_into_key_val_mut_at() {
+        let mut root = any_parent(1, CAPACITY).forget_type();
+        let node = any_node_or_child(root.borrow_valmut());
+        let len = node.len();
+        let idx = kani::any_where(|idx: &usize| *idx < len);
+        let (_, val) = unsafe { node.into_key_val_mut_at(idx) };
+        *val = kani::any();
+    }
+
+    #[kani::proof_for_contract(NodeRef::push_with_handle)]
+    #[kani::unwind(13)]
+    fn check_push_with_handle() {
+        let mut parent = any_parent(1, 0);
+        let mut leaf = any_child_leaf(parent.borrow_mut());
+        for _ in 0..any_len(CAPACITY) {
+            let _ = unsafe { leaf.reborrow_mut().push_with_handle(kani::any(), kani::any()) };
+            assert!(leaf.reborrow().is_safe());
+        }
+    }
+
+    #[kani::proof_for_contract(NodeRef::cast_to_leaf_unchecked)]
+    fn check_node_cast_to_leaf_unchecked() {
+        let mut root = Root::<u8, u8>::new(Global);
+        let leaf = unsafe { root.borrow_mut().cast_to_leaf_unchecked() };
+        assert!(leaf.is_safe());
+    }
+
+    #[kani::proof_for_contract(NodeRef::cast_to_internal_unchecked)]
+    #[kani::unwind(13)]
+    fn check_node_cast_to_internal_unchecked() {
+        let mut root = any_tree(1, 1);
+        let internal = unsafe { root.borrow_mut().cast_to_internal_unchecked() };
+        assert!(internal.is_safe());
+    }
+
+    #[kani::proof_for_contract(Handle::new_kv)]
+    #[kani::unwind(13)]
+    fn check_new_kv() {
+        let root = any_parent(1, CAPACITY).forget_type();
+        let node = any_node_or_child(root.reborrow());
+        let len = node.len();
+        let kv = unsafe { Handle::new_kv(node, kani::any_where(|idx: &usize| *idx < len)) };
+        let _ = kv.into_kv();
+    }
+
+    #[kani::proof_for_contract(Handle::new_edge)]
+    #[kani::unwind(13)]
+    fn check_new_edge() {
+        let root = any_tree(1, 1);
+        let len = root.len();
+        let edge = unsafe { Handle::new_edge(root.reborrow(), any_len(len)) };
+        if let ForceResult::Internal(edge) = edge.force() {
+            assert!(edge.descend().ascend().ok() == Some(edge));
+        }
+    }
+
+    #[kani::proof_for_contract(Handle::reborrow_mut)]
+    fn check_handle_reborrow_mut() {
+        let mut root = Root::<u8, u8>::new(Global);
+        let mut edge = root.borrow_mut().first_edge();
+        let _ = unsafe { edge.reborrow_mut() };
+    }
+
+    #[kani::proof_for_contract(Handle::awaken)]
+    fn check_handle_awaken() {
+        let mut root = Root::<u8, u8>::new(Global);
+        let dormant = root.borrow_mut().first_edge().dormant();
+        let _ = unsafe { dormant.awaken() };
+    }
+
+    #[kani::proof_for_contract(Handle::insert_fit)]
+    #[kani::unwind(13)]
+    fn check_insert_fit() {
+        let mut parent = any_parent(1, CAPACITY - 1);
+        let mut leaf = any_child_leaf(parent.borrow_mut());
+        let idx = any_len(leaf.len());
+        let edge = unsafe { Handle::new_edge(leaf.reborrow_mut(), idx) };
+        let _ = unsafe { edge.insert_fit(kani::any(), kani::any()) };
+        assert!(leaf.reborrow().is_safe());
+    }
+
+    #[kani::proof_for_contract(Handle::into_key_val)]
+    #[kani::unwind(13)]
+    fn check_into_key_val() {
+        let node = any_node_or_child(any_parent(1, CAPACITY).forget_type().into_dying());
+        let len = node.len();
+        let idx = kani::any_where(|idx: &usize| *idx < len);
+        let _ = unsafe { Handle::new_kv(node, idx).into_key_val() };
+    }
+
+    #[kani::proof_for_contract(Handle::drop_key_val)]
+    #[kani::unwind(13)]
+    fn check_drop_key_val() {
+        let node = any_node_or_child(any_parent(1, CAPACITY).forget_type().into_dying());
+        let len = node.len();
+        let idx = kani::any_where(|idx: &usize| *idx < len);
+        unsafe { Handle::new_kv(node, idx).drop_key_val() };
+    }
+
+    #[kani::proof_for_contract(Handle::cast_to_leaf_unchecked)]
+    fn check_handle_cast_to_leaf_unchecked() {
+        let mut root = Root::<u8, u8>::new(Global);
+        let edge = root.borrow_mut().first_edge();
+        let _ = unsafe { edge.cast_to_leaf_unchecked() };
+    }
+
+    #[kani::proof_for_contract(slice_insert)]
+    #[kani::unwind(13)]
+    fn check_slice_insert() {
+        let mut arr = [MaybeUninit::new(0u8); CAPACITY];
+        let len = kani::any_where(|len: &usize| 0 < *len && *len <= CAPACITY);
+        let idx = kani::any_where(|idx: &usize| *idx < len);
+        let val = kani::any();
+        unsafe { slice_insert(&mut arr[..len], idx, val) };
+        assert_eq!(unsafe { arr[idx].assume_init() }, val);
+    }
+
+    #[kani::proof_for_contract(slice_remove)]
+    #[kani::unwind(13)]
+    fn check_slice_remove() {
+        let mut arr: [MaybeUninit<u8>; CAPACITY] =
+            kani::any::<[u8; CAPACITY]>().map(MaybeUninit::new);
+        let original = arr;
+        let len = kani::any_where(|len: &usize| 0 < *len && *len <= CAPACITY);
+        let idx = kani::any_where(|idx: &usize| *idx < len);
+        let val = unsafe { slice_remove(&mut arr[..len], idx) };
+        assert_eq!(val, unsafe { original[idx].assume_init() });
+    }
+
+    #[kani::proof_for_contract(slice_shl)]
+    #[kani::unwind(13)]
+    fn check_slice_shl() {
+        let mut arr = [MaybeUninit::new(0u8); CAPACITY + 1];
+        let len = any_len(CAPACITY + 1);
+        unsafe { slice_shl(&mut arr[..len], any_len(len)) };
+    }
+
+    #[kani::proof_for_contract(slice_shr)]
+    #[kani::unwind(13)]
+    fn check_slice_shr() {
+        let mut arr = [MaybeUninit::new(0u8); CAPACITY + 1];
+        let len = any_len(CAPACITY + 1);
+        unsafe { slice_shr(&mut arr[..len], any_len(len)) };
+    }
+
+    #[kani::proof]
+    #[kani::unwind(13)]
+    fn check_push_internal() {
+        let mut node = NodeRef::new_internal(any_tree(0, 1), Global);
+        for _ in 0..any_len(CAPACITY) {
+            node.borrow_mut().push(kani::any(), kani::any(), any_tree(0, 1));
+            assert!(node.reborrow().is_safe());
+        }
+    }
+
+    #[kani::proof]
+    #[kani::unwind(13)]
+    fn check_ascend_descend() {
+        let root = any_tree(1, CAPACITY);
+        let ForceResult::Internal(node) = root.reborrow().force() else { unreachable!() };
+        let edge = unsafe { Handle::new_edge(node, any_len(node.len())) };
+        let child = edge.descend();
+        assert!(child.is_safe());
+        assert_eq!(child.height(), 0);
+        assert!(child.ascend().ok() == Some(edge));
+    }
+
+    #[kani::proof_for_contract(NodeRef::key_area_mut)]
+    #[kani::unwind(13)]
+    fn check_key_area_mut() {
+        let mut parent = any_parent(1, CAPACITY);
+        let mut node = any_node_or_child(parent.borrow_mut().forget_type());
+        let end = any_len(CAPACITY);
+        let start = any_len(end);
+        let _ = unsafe { node.key_area_mut(start..end) };
+        let _ = unsafe { node.key_area_mut(kani::any_where(|idx: &usize| *idx < CAPACITY)) };
+    }
+
+    #[kani::proof_for_contract(NodeRef::val_area_mut)]
+    #[kani::unwind(13)]
+    fn check_val_area_mut() {
+        let mut parent = any_parent(1, CAPACITY);
+        let mut node = any_node_or_child(parent.borrow_mut().forget_type());
+        let _ = unsafe { node.val_area_mut(..any_len(CAPACITY)) };
+        let _ = unsafe { node.val_area_mut(kani::any_where(|idx: &usize| *idx < CAPACITY)) };
+    }
+
+    #[kani::proof_for_contract(NodeRef::edge_area_mut)]
+    #[kani::unwind(13)]
+    fn check_edge_area_mut() {
+        let mut node = any_parent(2, 2);
+        let mut node = if kani::any() {
+            node.borrow_mut()
+        } else {
+            let edge = unsafe { Handle::new_edge(node.borrow_mut(), any_len(1)) };
+            unsafe { edge.descend().cast_to_internal_unchecked() }
+        };
+        let end = any_len(CAPACITY + 1);
+        let _ = unsafe { node.edge_area_mut(any_len(end)..end) };
+        let _ = unsafe { node.edge_area_mut(any_len(CAPACITY)) };
+    }
+
+    // Moves the children of a node to other edges, as merging and stealing do, and links them
+    // back.
+    #[kani::proof_for_contract(NodeRef::correct_childrens_parent_links)]
+    #[kani::unwind(13)]
+    fn check_correct_childrens_parent_links() {
+        let mut parent = any_parent(2, 2);
+        let edge = unsafe { Handle::new_edge(parent.borrow_mut(), any_len(1)) };
+        let mut node = unsafe { edge.descend().cast_to_internal_unchecked() };
+        let len = node.len();
+        kani::assume(len > 0);
+        unsafe {
+            node.edge_area_mut(..len + 1).reverse();
+            node.correct_childrens_parent_links(0..len + 1);
+        }
+        assert!(node.reborrow().is_safe());
+    }
+
+    #[kani::proof_for_contract(Handle::<NodeRef<marker::Mut<'static>, u8, u8, marker::Leaf>, marker::KV>::split)]
+    #[kani::unwind(13)]
+    fn check_split_leaf() {
+        let mut parent = any_parent(1, CAPACITY);
+        let mut leaf = any_child_leaf(parent.borrow_mut());
+        let len = leaf.len();
+        let idx = kani::any_where(|idx: &usize| *idx < len);
+        let result = unsafe { Handle::new_kv(leaf.reborrow_mut(), idx) }.split(Global);
+        assert_eq!(result.left.len(), idx);
+        assert_eq!(result.right.len(), len - idx - 1);
+        assert!(parent.reborrow().is_safe());
+    }
+
+    #[kani::proof_for_contract(Handle::<NodeRef<marker::Mut<'static>, u8, u8, marker::Internal>, marker::KV>::split)]
+    #[kani::unwind(13)]
+    fn check_split_internal() {
+        let mut root = any_parent(2, 2);
+        let mut node = if kani::any() {
+            root.borrow_mut()
+        } else {
+            let edge = unsafe { Handle::new_edge(root.borrow_mut(), any_len(1)) };
+            unsafe { edge.descend().cast_to_internal_unchecked() }
+        };
+        let len = node.len();
+        let idx = kani::any_where(|idx: &usize| *idx < len);
+        let result = unsafe { Handle::new_kv(node.reborrow_mut(), idx) }.split(Global);
+        assert_eq!(result.left.len(), idx);
+        assert_eq!(result.right.len(), len - idx - 1);
+    }
+
+    fn check_merge_tracking_child(height: usize, max_len: usize) {
+        let mut parent = any_parent(height, max_len);
+        let ctx = unsafe { Handle::new_kv(parent.borrow_mut(), 0) }.consider_for_balancing();
+        kani::assume(ctx.can_merge());
+        let new_len = ctx.left_child_len() + 1 + ctx.right_child_len();
+        let child = ctx.merge_tracking_child(Global);
+        assert_eq!(child.len(), new_len);
+        assert!(child.reborrow().is_safe());
+        assert_eq!(parent.len(), 0);
+        assert!(parent.reborrow().is_safe());
+    }
+
+    #[kani::proof_for_contract(BalancingContext::merge_tracking_child)]
+    #[kani::unwind(13)]
+    fn check_merge_tracking_child_leaves() {
+        check_merge_tracking_child(1, CAPACITY);
+    }
+
+    #[kani::proof_for_contract(BalancingContext::merge_tracking_child)]
+    #[kani::unwind(13)]
+    fn check_merge_tracking_child_internal() {
+        check_merge_tracking_child(2, 2);
+    }
+
+    fn check_steal_left(height: usize, max_len: usize) {
+        let mut parent = any_parent(height, max_len);
+        let ctx = unsafe { Handle::new_kv(parent.borrow_mut(), 0) }.consider_for_balancing();
+        let (left_len, right_len) = (ctx.left_child_len(), ctx.right_child_len());
+        kani::assume(left_len > 0 && right_len < CAPACITY);
+        let track_right_edge_idx = any_len(right_len);
+        let edge = ctx.steal_left(track_right_edge_idx);
+        assert_eq!(edge.idx(), track_right_edge_idx + 1);
+        let child = edge.into_node();
+        assert_eq!(child.len(), right_len + 1);
+        assert!(child.reborrow().is_safe());
+        assert!(parent.reborrow().is_safe());
+    }
+
+    #[kani::proof_for_contract(BalancingContext::steal_left)]
+    #[kani::unwind(13)]
+    fn check_steal_left_leaves() {
+        check_steal_left(1, CAPACITY);
+    }
+
+    #[kani::proof_for_contract(BalancingContext::steal_left)]
+    #[kani::unwind(13)]
+    fn check_steal_left_internal() {
+        check_steal_left(2, 2);
+    }
+
+    fn check_bulk_steal_right(height: usize, max_len: usize) {
+        let mut parent = any_parent(height, max_len);
+        let mut ctx = unsafe { Handle::new_kv(parent.borrow_mut(), 0) }.consider_for_balancing();
+        let (left_len, right_len) = (ctx.left_child_len(), ctx.right_child_len());
+        let count = kani::any_where(|count: &usize| {
+            0 < *count && left_len + *count <= CAPACITY && *count <= right_len
+        });
+        ctx.bulk_steal_right(count);
+        assert_eq!(ctx.left_child_len(), left_len + count);
+        assert_eq!(ctx.right_child_len(), right_len - count);
+        let left = ctx.into_left_child();
+        assert!(left.reborrow().is_safe());
+        assert!(parent.reborrow().is_safe());
+        let right = unsafe { Handle::new_edge(parent.borrow_mut(), 1) }.descend();
+        assert!(right.reborrow().is_safe());
+    }
+
+    #[kani::proof_for_contract(BalancingContext::bulk_steal_right)]
+    #[kani::unwind(13)]
+    fn check_bulk_steal_right_leaves() {
+        check_bulk_steal_right(1, CAPACITY);
+    }
+
+    #[kani::proof_for_contract(BalancingContext::bulk_steal_right)]
+    #[kani::unwind(13)]
+    fn check_bulk_steal_right_internal() {
+        check_bulk_steal_right(2, 2);
+    }
+}
@@ -17,8 +17,14 @@ use core::hash::{Hash, Hasher};
 use core::iter::FusedIterator;
 use core::marker::PhantomData;
 use core::ptr::NonNull;
+// Used only for contract verification.
+#[allow(unused_imports)]
+use core::ub_checks;
+use core::ub_checks::Invariant;
 use core::{fmt, mem};
 
+use safety::{ensures, requires};
+
 use super::SpecExtend;
 use crate::alloc::{Allocator, Global};
 use crate::boxed::Box;
@@ -170,6 +176,8 @@ impl<T, A: Allocator> LinkedList<T, A> {
     /// `node` must point to a valid node that was boxed and leaked using the list's allocator.
     /// This method takes ownership of the node, so the pointer should not be used again.
     #[inline]
+    #[requires(self.is_safe() && ub_checks::can_write(node.as_ptr()) && self.position(node).is_none())]
+    #[ensures(|_| self.is_safe() && self.head == Some(node) && self.len == old(self.len) + 1)]
     unsafe fn push_front_node(&mut self, node: NonNull<Node<T>>) {
         // This method takes care not to create mutable references to whole nodes,
         // to maintain validity of aliasing pointers into `element`.
@@ -215,6 +223,8 @@ impl<T, A: Allocator> LinkedList<T, A> {
     /// `node` must point to a valid node that was boxed and leaked using the list's allocator.
     /// This method takes ownership of the node, so the pointer should not be used again.
     #[inline]
+    #[requires(self.is_safe() && ub_checks::can_write(node.as_ptr()) && self.position(node).is_none())]
+    #[ensures(|_| self.is_safe() && self.tail == Some(node) && self.len == old(self.len) + 1)]
     unsafe fn push_back_node(&mut self, node: NonNull<Node<T>>) {
         // This method takes care not to create mutable references to whole nodes,
         // to maintain validity of aliasing pointers into `element`.
@@ -261,6 +271,8 @@ impl<T, A: Allocator> LinkedList<T, A> {
     /// This method takes care not to create mutable references to `element`, to
     /// maintain validity of aliasing pointers.
     #[inline]
+    #[requires(self.is_safe() && self.position(node).is_some())]
+    #[ensures(|_| self.is_safe() && self.position(node).is_none() && self.len == old(self.len) - 1)]
     unsafe fn unlink_node(&mut self, mut node: NonNull<Node<T>>) {
         let node = unsafe { node.as_mut() }; // this one is ours now, we can create an &mut.
 
@@ -284,6 +296,20 @@ impl<T, A: Allocator> LinkedList<T, A> {
     ///
     /// Warning: this will not check that the provided node belongs to the two existing lists.
     #[inline]
+    #[requires(
+        self.is_safe()
+            && Self::is_chain(splice_start, splice_end, splice_length)
+            && self.position(splice_start).is_none()
+            && match existing_prev {
+                Some(prev) => {
+                    self.position(prev).is_some()
+                        && unsafe { (*prev.as_ptr()).next } == existing_next
+                }
+                None => self.head == existing_next,
+            }
+            && (existing_next.is_some() || self.tail == existing_prev)
+    )]
+    #[ensures(|_| self.is_safe() && self.len == old(self.len) + splice_length)]
     unsafe fn splice_nodes(
         &mut self,
         existing_prev: Option<NonNull<Node<T>>>,
@@ -334,6 +360,8 @@ impl<T, A: Allocator> LinkedList<T, A> {
     }
 
     #[inline]
+    #[requires(self.is_safe() && split_node.is_none_or(|node| self.position(node) == Some(at)))]
+    #[ensures(|first| first.is_safe() && self.is_safe() && first.len + self.len == old(self.len))]
     unsafe fn split_off_before_node(
         &mut self,
         split_node: Option<NonNull<Node<T>>>,
@@ -377,6 +405,11 @@ impl<T, A: Allocator> LinkedList<T, A> {
     }
 
     #[inline]
+    #[requires(
+        self.is_safe()
+            && split_node.is_none_or(|node| self.position(node).is_some_and(|idx| idx + 1 == at))
+    )]
+    #[ensures(|second| second.is_safe() && self.is_safe() && self.len + second.len == old(self.len))]
     unsafe fn split_off_after_node(
         &mut self,
         split_node: Option<NonNull<Node<T>>>,
@@ -421,6 +454,59 @@ impl<T, A: Allocator> LinkedList<T, A> {
     }
 }
 
+impl<T, A: Allocator> LinkedList<T, A> {
+    /// Checks that following `next` from `start` reaches `end` after `len - 1` steps, and that
+    /// the `prev` link of each node on the way points back to its predecessor.
+    fn is_chain(start: NonNull<Node<T>>, end: NonNull<Node<T>>, len: usize) -> bool {
+        if len == 0 {
+            return false;
+        }
+        let mut node = start;
+        for _ in 1..len {
+            // Not creating references to nodes, to maintain validity of aliasing pointers into
+            // `element`.
+            let Some(next) = (unsafe { (*node.as_ptr()).next }) else {
+                return false;
+            };
+            if unsafe { (*next.as_ptr()).prev } != Some(node) {
+                return false;
+            }
+            node = next;
+        }
+        node == end
+    }
+
+    /// Returns the index of `node` in the list, or `None` if it is not one of the list's nodes.
+    #[allow(dead_code)] // Only used by contracts.
+    fn position(&self, node: NonNull<Node<T>>) -> Option<usize> {
+        let mut current = self.head;
+        for idx in 0..self.len {
+            let current_node = current?;
+            if current_node == node {
+                return Some(idx);
+            }
+            current = unsafe { (*current_node.as_ptr()).next };
+        }
+        None
+    }
+}
+
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T, A: Allocator> Invariant for LinkedList<T, A> {
+    /// The `len` nodes of the list are doubly linked from `head` to `tail`, and no node comes
+    /// before `head` or after `tail`.
+    fn is_safe(&self) -> bool {
+        match (self.head, self.tail) {
+            (None, None) => self.len == 0,
+            (Some(head), Some(tail)) => {
+                (unsafe { (*head.as_ptr()).prev.is_none() && (*tail.as_ptr()).next.is_none() })
+                    && Self::is_chain(head, tail, self.len)
+            }
+            _ => false,
+        }
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> Default for LinkedList<T> {
     /// Creates an empty `LinkedList<T>`.
@@ -2240,3 +2326,148 @@ unsafe impl<T: Send, A: Allocator + Send> Send for CursorMut<'_, T, A> {}
 
 #[unstable(feature = "linked_list_cursors", issue = "58533")]
 unsafe impl<T: Sync, A: Allocator + Sync> Sync for CursorMut<'_, T, A> {}
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::kani;
+
+    use super::*;
+    use crate::arbitrary::any_linked_list;
+
+    const MAX_LEN: usize = 4;
+
+    fn any_list() -> LinkedList<u8> {
+        any_linked_list::<u8, MAX_LEN>()
+    }
+
+    /// Returns a cursor to an arbitrary element of `list`, or to its "ghost" non-element.
+    fn any_cursor(list: &mut LinkedList<u8>) -> CursorMut<'_, u8> {
+        let index = kani::any_where(|index: &usize| *index <= list.len());
+        let mut cursor = list.cursor_front_mut();
+        for _ in 0..index {
+            cursor.move_next();
+        }
+        cursor
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_invariant() {
+        let mut list = any_list();
+        assert!(list.is_safe());
+        let mut other = any_list();
+        list.append(&mut other);
+        assert!(list.is_safe() && other.is_safe());
+    }
+
+    #[kani::proof_for_contract(LinkedList::push_front_node)]
+    #[kani::unwind(6)]
+    fn check_push_front_node() {
+        let mut list = any_list();
+        let elem = kani::any();
+        list.push_front(elem);
+        assert_eq!(list.front(), Some(&elem));
+    }
+
+    #[kani::proof_for_contract(LinkedList::push_back_node)]
+    #[kani::unwind(6)]
+    fn check_push_back_node() {
+        let mut list = any_list();
+        let elem = kani::any();
+        list.push_back(elem);
+        assert_eq!(list.back(), Some(&elem));
+    }
+
+    #[kani::proof_for_contract(LinkedList::unlink_node)]
+    #[kani::unwind(6)]
+    fn check_unlink_node() {
+        let mut list = any_list();
+        let len = list.len();
+        let mut cursor = any_cursor(&mut list);
+        let removed = if kani::any() {
+            cursor.remove_current().is_some()
+        } else {
+            cursor.remove_current_as_list().inspect(|removed| assert!(removed.is_safe())).is_some()
+        };
+        assert_eq!(list.len(), len - removed as usize);
+    }
+
+    #[kani::proof_for_contract(LinkedList::splice_nodes)]
+    #[kani::unwind(6)]
+    fn check_splice_nodes() {
+        let mut list = any_list();
+        let other = any_list();
+        let len = list.len() + other.len();
+        let mut cursor = any_cursor(&mut list);
+        if kani::any() {
+            cursor.splice_after(other);
+        } else {
+            cursor.splice_before(other);
+        }
+        assert_eq!(list.len(), len);
+    }
+
+    #[kani::proof_for_contract(LinkedList::splice_nodes)]
+    #[kani::unwind(6)]
+    fn check_splice_single_node() {
+        let mut list = any_list();
+        let len = list.len();
+        let mut cursor = any_cursor(&mut list);
+        if kani::any() {
+            cursor.insert_after(kani::any());
+        } else {
+            cursor.insert_before(kani::any());
+        }
+        assert_eq!(list.len(), len + 1);
+    }
+
+    #[kani::proof_for_contract(LinkedList::split_off_before_node)]
+    #[kani::unwind(6)]
+    fn check_split_off_before_node() {
+        let mut list = any_list();
+        let len = list.len();
+        let mut cursor = any_cursor(&mut list);
+        let index = cursor.index().unwrap_or(len);
+        let first = cursor.split_before();
+        assert_eq!(first.len(), index);
+        assert_eq!(list.len(), len - index);
+    }
+
+    #[kani::proof_for_contract(LinkedList::split_off_after_node)]
+    #[kani::unwind(6)]
+    fn check_split_off_after_node() {
+        let mut list = any_list();
+        let len = list.len();
+        let at = kani::any_where(|at: &usize| *at <= len);
+        let second = list.split_off(at);
+        assert_eq!(list.len(), at);
+        assert_eq!(second.len(), len - at);
+    }
+
+    #[kani::proof_for_contract(LinkedList::split_off_after_node)]
+    #[kani::unwind(6)]
+    fn check_split_after() {
+        let mut list = any_list();
+        let len = list.len();
+        let mut cursor = any_cursor(&mut list);
+        let kept = cursor.index().map_or(0, |index| index + 1);
+        let second = cursor.split_after();
+        assert_eq!(second.len(), len - kept);
+        assert_eq!(list.len(), kept);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_detach_all_nodes() {
+        let list = any_list();
+        let len = list.len();
+        match list.detach_all_nodes() {
+            None => assert_eq!(len, 0),
+            Some((head, tail, detached_len)) => {
+                assert_eq!(detached_len, len);
+                assert!(LinkedList::<u8>::is_chain(head, tail, len));
+            }
+        }
+    }
+}
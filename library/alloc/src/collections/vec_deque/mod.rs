@@ -3224,6 +3224,26 @@ impl<T, const N: usize> From<[T; N]> for VecDeque<T> {
     }
 }
 
+#[cfg(kani)]
+impl<T> VecDeque<T> {
+    /// Creates a deque from a buffer of capacity `capacity` whose `len` elements start at physical
+    /// index `head` and wrap around. Only used to generate arbitrary deques for verification.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` and `capacity` must describe an allocation of the global allocator, `head` must be
+    /// less than `capacity` (or zero if `capacity == 0`), `len` must not exceed `capacity`, and
+    /// the `len` slots starting at `head` must be initialized.
+    pub(crate) unsafe fn from_raw_parts_with_head(
+        ptr: *mut T,
+        capacity: usize,
+        head: usize,
+        len: usize,
+    ) -> Self {
+        Self { head, len, buf: unsafe { RawVec::from_raw_parts_in(ptr, capacity, Global) } }
+    }
+}
+
 #[cfg(kani)]
 #[unstable(feature = "kani", issue = "none")]
 mod verify {
@@ -5,8 +5,14 @@ use core::ffi::{CStr, c_char};
 use core::num::NonZero;
 use core::slice::memchr;
 use core::str::{self, FromStr, Utf8Error};
+// Used only for contract verification.
+#[allow(unused_imports)]
+use core::ub_checks;
+use core::ub_checks::Invariant;
 use core::{fmt, mem, ops, ptr, slice};
 
+use safety::{ensures, requires};
+
 use crate::borrow::{Cow, ToOwned};
 use crate::boxed::Box;
 use crate::rc::Rc;
@@ -332,11 +338,15 @@ impl CString {
     /// ```
     #[must_use]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[requires(!v.contains(&0))]
+    #[ensures(|s| s.is_safe())]
     pub unsafe fn from_vec_unchecked(v: Vec<u8>) -> Self {
         debug_assert!(memchr::memchr(0, &v).is_none());
         unsafe { Self::_from_vec_unchecked(v) }
     }
 
+    #[requires(!v.contains(&0))]
+    #[ensures(|s| s.is_safe())]
     unsafe fn _from_vec_unchecked(mut v: Vec<u8>) -> Self {
         v.reserve_exact(1);
         v.push(0);
@@ -394,6 +404,8 @@ impl CString {
     /// ```
     #[must_use = "call `drop(from_raw(ptr))` if you intend to drop the `CString`"]
     #[stable(feature = "cstr_memory", since = "1.4.0")]
+    #[requires(!ptr.is_null() && is_nul_terminated_raw(ptr))]
+    #[ensures(|s| s.is_safe())]
     pub unsafe fn from_raw(ptr: *mut c_char) -> CString {
         // SAFETY: This is called with a pointer that was obtained from a call
         // to `CString::into_raw` and the length has not been modified. As such,
@@ -447,6 +459,7 @@ impl CString {
     #[inline]
     #[must_use = "`self` will be dropped if the result is not used"]
     #[stable(feature = "cstr_memory", since = "1.4.0")]
+    #[ensures(|ptr| !ptr.is_null() && is_nul_terminated_raw(*ptr))]
     pub fn into_raw(self) -> *mut c_char {
         Box::into_raw(self.into_inner()) as *mut c_char
     }
@@ -627,11 +640,15 @@ impl CString {
     /// ```
     #[must_use]
     #[stable(feature = "cstring_from_vec_with_nul", since = "1.58.0")]
+    #[requires(is_nul_terminated(&v))]
+    #[ensures(|s| s.is_safe())]
     pub unsafe fn from_vec_with_nul_unchecked(v: Vec<u8>) -> Self {
         debug_assert!(memchr::memchr(0, &v).unwrap() + 1 == v.len());
         unsafe { Self::_from_vec_with_nul_unchecked(v) }
     }
 
+    #[requires(is_nul_terminated(&v))]
+    #[ensures(|s| s.is_safe())]
     unsafe fn _from_vec_with_nul_unchecked(v: Vec<u8>) -> Self {
         Self { inner: v.into_boxed_slice() }
     }
@@ -690,6 +707,33 @@ impl CString {
     }
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl Invariant for CString {
+    /// A `CString` has exactly one nul byte, as its last element.
+    fn is_safe(&self) -> bool {
+        is_nul_terminated(&self.inner)
+    }
+}
+
+/// Checks that `bytes` ends with a nul byte and has no other nul byte.
+fn is_nul_terminated(bytes: &[u8]) -> bool {
+    matches!(bytes.split_last(), Some((0, rest)) if !rest.contains(&0))
+}
+
+/// Checks that the bytes starting at `ptr` can be read up to and including a nul byte.
+#[allow(dead_code)] // Only used by contracts.
+fn is_nul_terminated_raw(ptr: *const c_char) -> bool {
+    let mut next = ptr;
+    while ub_checks::can_dereference(next) {
+        // SAFETY: just checked that `next` can be read.
+        if unsafe { *next } == 0 {
+            return true;
+        }
+        next = next.wrapping_add(1);
+    }
+    false
+}
+
 // Turns this `CString` into an empty string to prevent
 // memory-unsafe code from working by accident. Inline
 // to prevent LLVM from optimizing it away in debug builds.
@@ -1203,3 +1247,116 @@ impl core::error::Error for IntoStringError {
         Some(&self.error)
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::kani;
+
+    use super::*;
+    use crate::arbitrary::{any_c_string, any_vec};
+
+    const MAX_LEN: usize = 4;
+
+    #[kani::proof_for_contract(CString::from_vec_unchecked)]
+    #[kani::unwind(6)]
+    fn check_from_vec_unchecked() {
+        let bytes = any_vec::<u8, MAX_LEN>();
+        let expected = bytes.clone();
+        let s = unsafe { CString::from_vec_unchecked(bytes) };
+        assert_eq!(s.as_bytes(), &expected[..]);
+    }
+
+    #[kani::proof_for_contract(CString::_from_vec_unchecked)]
+    #[kani::unwind(6)]
+    fn check_from_vec_unchecked_inner() {
+        let bytes = any_vec::<u8, MAX_LEN>();
+        let _ = unsafe { CString::_from_vec_unchecked(bytes) };
+    }
+
+    #[kani::proof_for_contract(CString::from_vec_with_nul_unchecked)]
+    #[kani::unwind(6)]
+    fn check_from_vec_with_nul_unchecked() {
+        let bytes = any_vec::<u8, MAX_LEN>();
+        let expected = bytes.clone();
+        let s = unsafe { CString::from_vec_with_nul_unchecked(bytes) };
+        assert_eq!(s.as_bytes_with_nul(), &expected[..]);
+    }
+
+    #[kani::proof_for_contract(CString::_from_vec_with_nul_unchecked)]
+    #[kani::unwind(6)]
+    fn check_from_vec_with_nul_unchecked_inner() {
+        let bytes = any_vec::<u8, MAX_LEN>();
+        let _ = unsafe { CString::_from_vec_with_nul_unchecked(bytes) };
+    }
+
+    #[kani::proof_for_contract(CString::into_raw)]
+    #[kani::unwind(6)]
+    fn check_into_raw() {
+        let s = any_c_string::<MAX_LEN>();
+        let raw = s.into_raw();
+        // Reclaim the allocation.
+        let _ = unsafe { CString::from_raw(raw) };
+    }
+
+    #[kani::proof_for_contract(CString::from_raw)]
+    #[kani::unwind(6)]
+    fn check_from_raw() {
+        let s = any_c_string::<MAX_LEN>();
+        let expected = s.clone();
+        let raw = s.into_raw();
+        let s = unsafe { CString::from_raw(raw) };
+        assert_eq!(s, expected);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_into_raw_from_raw_round_trip() {
+        let s = any_c_string::<MAX_LEN>();
+        let expected = s.clone();
+        let raw = s.into_raw();
+        for (i, &byte) in expected.as_bytes_with_nul().iter().enumerate() {
+            assert_eq!(unsafe { *raw.add(i) } as u8, byte);
+        }
+        let s = unsafe { CString::from_raw(raw) };
+        assert!(s.is_safe());
+        assert_eq!(s.as_bytes_with_nul(), expected.as_bytes_with_nul());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_into_bytes_with_nul_round_trip() {
+        let s = any_c_string::<MAX_LEN>();
+        let expected = s.clone();
+        let bytes = s.into_bytes_with_nul();
+        assert_eq!(bytes.last(), Some(&0));
+        let s = CString::from_vec_with_nul(bytes).unwrap();
+        assert!(s.is_safe());
+        assert_eq!(s, expected);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_from_vec_with_nul() {
+        let bytes = any_vec::<u8, MAX_LEN>();
+        let is_valid = is_nul_terminated(&bytes);
+        match CString::from_vec_with_nul(bytes) {
+            Ok(s) => assert!(is_valid && s.is_safe()),
+            Err(_) => assert!(!is_valid),
+        }
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_new() {
+        let bytes = any_vec::<u8, MAX_LEN>();
+        let has_nul = bytes.contains(&0);
+        match CString::new(bytes) {
+            Ok(s) => assert!(!has_nul && s.is_safe()),
+            Err(e) => {
+                let position = e.nul_position();
+                assert!(has_nul && e.into_vec()[position] == 0);
+            }
+        }
+    }
+}
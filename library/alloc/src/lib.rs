@@ -155,6 +155,7 @@
 #![feature(try_trait_v2)]
 #![feature(try_with_capacity)]
 #![feature(tuple_trait)]
+#![feature(ub_checks)]
 #![feature(unicode_internals)]
 #![feature(unsize)]
 #![feature(unwrap_infallible)]
@@ -207,6 +208,10 @@ mod raw_vec;
 
 // Heaps provided for low-level allocation strategies
 pub mod alloc;
+// Arbitrary collections for verification harnesses
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+pub mod arbitrary;
 
 // Primitive types using the heaps above
 
@@ -7,7 +7,10 @@
 use core::marker::PhantomData;
 use core::mem::{ManuallyDrop, MaybeUninit, SizedTypeProperties};
 use core::ptr::{self, Alignment, NonNull, Unique};
-use core::{cmp, hint};
+use core::ub_checks::Invariant;
+use core::{cmp, hint, ub_checks};
+
+use safety::{ensures, requires};
 
 #[cfg(not(no_global_oom_handling))]
 use crate::alloc::handle_alloc_error;
@@ -44,6 +47,7 @@ const ZERO_CAP: Cap = unsafe { Cap::new_unchecked(0) };
 /// `Cap(cap)`, except if `T` is a ZST then `Cap::ZERO`.
 ///
 /// # Safety: cap must be <= `isize::MAX`.
+#[requires(T::IS_ZST || cap <= isize::MAX as usize)]
 unsafe fn new_cap<T>(cap: usize) -> Cap {
     if T::IS_ZST { ZERO_CAP } else { unsafe { Cap::new_unchecked(cap) } }
 }
@@ -226,6 +230,7 @@ impl<T, A: Allocator> RawVec<T, A> {
     ///
     /// Note, that the requested capacity and `self.capacity()` could differ, as
     /// an allocator could overallocate and return a greater memory block than requested.
+    #[requires(len <= self.capacity())]
     pub(crate) unsafe fn into_box(self, len: usize) -> Box<[MaybeUninit<T>], A> {
         // Sanity-check one half of the safety requirement (we cannot check the other half).
         debug_assert!(
@@ -251,6 +256,8 @@ impl<T, A: Allocator> RawVec<T, A> {
     /// If the `ptr` and `capacity` come from a `RawVec` created via `alloc`, then this is
     /// guaranteed.
     #[inline]
+    #[requires(RawVecInner::<A>::is_valid_raw_parts(ptr.cast(), capacity, T::LAYOUT))]
+    #[ensures(|raw_vec| raw_vec.is_safe() && raw_vec.ptr() == ptr)]
     pub(crate) unsafe fn from_raw_parts_in(ptr: *mut T, capacity: usize, alloc: A) -> Self {
         // SAFETY: Precondition passed to the caller
         unsafe {
@@ -269,6 +276,8 @@ impl<T, A: Allocator> RawVec<T, A> {
     ///
     /// See [`RawVec::from_raw_parts_in`].
     #[inline]
+    #[requires(RawVecInner::<A>::is_valid_raw_parts(ptr.as_ptr().cast(), capacity, T::LAYOUT))]
+    #[ensures(|raw_vec| raw_vec.is_safe() && raw_vec.non_null() == ptr)]
     pub(crate) unsafe fn from_nonnull_in(ptr: NonNull<T>, capacity: usize, alloc: A) -> Self {
         // SAFETY: Precondition passed to the caller
         unsafe {
@@ -493,11 +502,22 @@ impl<A: Allocator> RawVecInner<A> {
     }
 
     #[inline]
+    #[requires(!ptr.is_null())]
+    #[requires(
+        cap.as_inner() == 0
+            || ub_checks::can_dereference(ptr::slice_from_raw_parts(ptr, cap.as_inner()))
+    )]
+    #[ensures(|inner| inner.is_safe())]
     unsafe fn from_raw_parts_in(ptr: *mut u8, cap: Cap, alloc: A) -> Self {
         Self { ptr: unsafe { Unique::new_unchecked(ptr) }, cap, alloc }
     }
 
     #[inline]
+    #[requires(
+        cap.as_inner() == 0
+            || ub_checks::can_dereference(ptr::slice_from_raw_parts(ptr.as_ptr(), cap.as_inner()))
+    )]
+    #[ensures(|inner| inner.is_safe())]
     unsafe fn from_nonnull_in(ptr: NonNull<u8>, cap: Cap, alloc: A) -> Self {
         Self { ptr: Unique::from(ptr), cap, alloc }
     }
@@ -628,6 +648,7 @@ impl<A: Allocator> RawVecInner<A> {
     }
 
     #[inline]
+    #[requires(cap <= isize::MAX as usize)]
     unsafe fn set_ptr_and_cap(&mut self, ptr: NonNull<[u8]>, cap: usize) {
         // Allocators currently return a `NonNull<[u8]>` whose length matches
         // the size requested. If that ever changes, the capacity here should
@@ -710,6 +731,8 @@ impl<A: Allocator> RawVecInner<A> {
     /// # Safety
     /// `cap <= self.capacity()`
     #[cfg(not(no_global_oom_handling))]
+    #[requires(cap <= self.capacity(elem_layout.size()) && self.is_safe_for(elem_layout))]
+    #[ensures(|_| self.is_safe_for(elem_layout))]
     unsafe fn shrink_unchecked(
         &mut self,
         cap: usize,
@@ -751,6 +774,7 @@ impl<A: Allocator> RawVecInner<A> {
     /// after this function returns.
     /// Ideally this function would take `self` by move, but it cannot because it exists to be
     /// called from a `Drop` impl.
+    #[requires(self.is_safe_for(elem_layout))]
     unsafe fn deallocate(&mut self, elem_layout: Layout) {
         if let Some((ptr, layout)) = self.current_memory(elem_layout) {
             unsafe {
@@ -760,6 +784,55 @@ impl<A: Allocator> RawVecInner<A> {
     }
 }
 
+impl<A: Allocator> RawVecInner<A> {
+    /// Checks the requirements of `from_raw_parts_in` for elements of layout `elem_layout`: if the
+    /// elements are zero-sized or `cap` is zero, `ptr` is the dangling pointer that a `RawVec`
+    /// would hold, otherwise it is aligned and points to an allocation of `cap * size` bytes,
+    /// which cannot exceed `isize::MAX`.
+    fn is_valid_raw_parts(ptr: *mut u8, cap: usize, elem_layout: Layout) -> bool {
+        if elem_layout.size() == 0 || cap == 0 {
+            ptr.addr() == elem_layout.align()
+        } else {
+            !ptr.is_null()
+                && ptr.addr() % elem_layout.align() == 0
+                && elem_layout.size().checked_mul(cap).is_some_and(|size| {
+                    size <= isize::MAX as usize
+                        && ub_checks::can_dereference(ptr::slice_from_raw_parts(ptr, size))
+                })
+        }
+    }
+
+    /// Checks the invariant of a `RawVecInner` holding elements of layout `elem_layout`. The
+    /// pointer is dangling if the capacity is zero, which it always is for zero-sized elements.
+    /// Otherwise, the pointer and capacity describe the current allocation.
+    fn is_safe_for(&self, elem_layout: Layout) -> bool {
+        let cap = self.cap.as_inner();
+        (elem_layout.size() != 0 || cap == 0)
+            && Self::is_valid_raw_parts(self.ptr.as_ptr(), cap, elem_layout)
+            && self.is_safe()
+    }
+}
+
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<A: Allocator> Invariant for RawVecInner<A> {
+    /// The capacity fits in `Cap`, and a nonzero capacity comes with an allocation of at least
+    /// `cap` bytes, since elements are never zero-sized when the capacity is nonzero.
+    fn is_safe(&self) -> bool {
+        let cap = self.cap.as_inner();
+        cap <= isize::MAX as usize
+            && (cap == 0
+                || ub_checks::can_dereference(ptr::slice_from_raw_parts(self.ptr.as_ptr(), cap)))
+    }
+}
+
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T, A: Allocator> Invariant for RawVec<T, A> {
+    /// The buffer is either dangling or allocated with the layout of `[T; cap]`.
+    fn is_safe(&self) -> bool {
+        self.inner.is_safe_for(T::LAYOUT)
+    }
+}
+
 // not marked inline(never) since we want optimizers to be able to observe the specifics of this
 // function, see tests/codegen/vec-reserve-extend.rs.
 #[cold]
@@ -820,3 +893,233 @@ fn alloc_guard(alloc_size: usize) -> Result<(), TryReserveError> {
 fn layout_array(cap: usize, elem_layout: Layout) -> Result<Layout, TryReserveError> {
     elem_layout.repeat(cap).map(|(layout, _pad)| layout).map_err(|_| CapacityOverflow.into())
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::kani;
+
+    use super::*;
+
+    /// Allocations are kept small, except in the paths where the capacity computation overflows.
+    const MAX_CAP: usize = 8;
+
+    /// Returns an arbitrary element layout, whose size is a multiple of its alignment, and large
+    /// enough to reach every branch of `min_non_zero_cap`.
+    fn any_elem_layout() -> Layout {
+        let align = 1 << kani::any_where(|shift: &u32| *shift <= 4);
+        let size = kani::any_where(|size: &usize| *size <= 2048 && *size % align == 0);
+        Layout::from_size_align(size, align).unwrap()
+    }
+
+    /// Returns a buffer for elements of layout `elem_layout`, with a capacity of at most
+    /// `MAX_CAP`.
+    fn any_raw_vec_inner(elem_layout: Layout) -> RawVecInner {
+        let cap = kani::any_where(|cap: &usize| *cap <= MAX_CAP);
+        RawVecInner::try_with_capacity_in(cap, Global, elem_layout).unwrap()
+    }
+
+    /// Returns the pointer and capacity of a leaked `RawVec<T>`.
+    fn any_raw_parts<T>() -> (*mut T, usize) {
+        let cap = kani::any_where(|cap: &usize| *cap <= MAX_CAP);
+        let raw_vec = ManuallyDrop::new(RawVec::<T>::try_with_capacity_in(cap, Global).unwrap());
+        (raw_vec.ptr(), raw_vec.capacity())
+    }
+
+    /// Whether `len + additional` elements either fit in `MAX_CAP`, or overflow the capacity
+    /// computation.
+    fn is_small_or_overflowing(len: usize, additional: usize, elem_layout: Layout) -> bool {
+        len.checked_add(additional)
+            .is_none_or(|cap| cap <= MAX_CAP || layout_array(cap, elem_layout).is_err())
+    }
+
+    #[kani::proof]
+    #[kani::solver(kissat)]
+    fn check_layout_array() {
+        let elem_layout = any_elem_layout();
+        let cap = kani::any();
+        match layout_array(cap, elem_layout) {
+            Ok(layout) => {
+                assert_eq!(layout.size(), elem_layout.size() * cap);
+                assert_eq!(layout.align(), elem_layout.align());
+            }
+            Err(_) => assert!(
+                elem_layout
+                    .size()
+                    .checked_mul(cap)
+                    .is_none_or(|size| size > isize::MAX as usize - (elem_layout.align() - 1))
+            ),
+        }
+    }
+
+    #[kani::proof]
+    #[kani::solver(kissat)]
+    fn check_try_with_capacity_in() {
+        let elem_layout = any_elem_layout();
+        let cap = kani::any();
+        kani::assume(
+            cap <= MAX_CAP || elem_layout.size() == 0 || layout_array(cap, elem_layout).is_err(),
+        );
+        match RawVecInner::try_with_capacity_in(cap, Global, elem_layout) {
+            Ok(mut inner) => {
+                assert!(inner.is_safe_for(elem_layout));
+                assert!(!inner.needs_to_grow(0, cap, elem_layout));
+                unsafe { inner.deallocate(elem_layout) };
+            }
+            Err(err) => {
+                assert!(err.kind() != CapacityOverflow || layout_array(cap, elem_layout).is_err())
+            }
+        }
+    }
+
+    // Covers `reserve` and `grow_one`, which only call `grow_amortized` when the buffer is full.
+    #[kani::proof]
+    #[kani::solver(kissat)]
+    fn check_grow_amortized() {
+        let elem_layout = any_elem_layout();
+        let mut inner = any_raw_vec_inner(elem_layout);
+        let len = kani::any();
+        let additional = kani::any_where(|additional: &usize| *additional > 0);
+        kani::assume(inner.needs_to_grow(len, additional, elem_layout));
+        kani::assume(is_small_or_overflowing(len, additional, elem_layout));
+        let old_cap = inner.capacity(elem_layout.size());
+        match inner.grow_amortized(len, additional, elem_layout) {
+            Ok(()) => {
+                let cap = inner.capacity(elem_layout.size());
+                assert!(!inner.needs_to_grow(len, additional, elem_layout));
+                assert!(cap >= 2 * old_cap && cap >= min_non_zero_cap(elem_layout.size()));
+            }
+            Err(_) => assert_eq!(inner.capacity(elem_layout.size()), old_cap),
+        }
+        assert!(inner.is_safe_for(elem_layout));
+        unsafe { inner.deallocate(elem_layout) };
+    }
+
+    #[kani::proof]
+    #[kani::solver(kissat)]
+    fn check_grow_exact() {
+        let elem_layout = any_elem_layout();
+        let mut inner = any_raw_vec_inner(elem_layout);
+        let len = kani::any();
+        let additional = kani::any();
+        kani::assume(inner.needs_to_grow(len, additional, elem_layout));
+        kani::assume(is_small_or_overflowing(len, additional, elem_layout));
+        let old_cap = inner.capacity(elem_layout.size());
+        match inner.grow_exact(len, additional, elem_layout) {
+            Ok(()) => assert_eq!(inner.capacity(elem_layout.size()), len + additional),
+            Err(_) => assert_eq!(inner.capacity(elem_layout.size()), old_cap),
+        }
+        assert!(inner.is_safe_for(elem_layout));
+        unsafe { inner.deallocate(elem_layout) };
+    }
+
+    #[kani::proof]
+    #[kani::solver(kissat)]
+    fn check_try_reserve() {
+        let elem_layout = any_elem_layout();
+        let mut inner = any_raw_vec_inner(elem_layout);
+        let len = kani::any();
+        let additional = kani::any();
+        kani::assume(is_small_or_overflowing(len, additional, elem_layout));
+        let old_cap = inner.capacity(elem_layout.size());
+        let result = if kani::any() {
+            inner.try_reserve(len, additional, elem_layout)
+        } else {
+            inner.try_reserve_exact(len, additional, elem_layout)
+        };
+        match result {
+            Ok(()) => assert!(!inner.needs_to_grow(len, additional, elem_layout)),
+            Err(_) => assert_eq!(inner.capacity(elem_layout.size()), old_cap),
+        }
+        assert!(inner.is_safe_for(elem_layout));
+        unsafe { inner.deallocate(elem_layout) };
+    }
+
+    #[kani::proof_for_contract(RawVecInner::<Global>::shrink_unchecked)]
+    #[kani::solver(kissat)]
+    fn check_shrink_unchecked() {
+        let elem_layout = any_elem_layout();
+        let mut inner = any_raw_vec_inner(elem_layout);
+        let old_cap = inner.capacity(elem_layout.size());
+        let cap = kani::any_where(|cap: &usize| *cap <= old_cap);
+        if unsafe { inner.shrink_unchecked(cap, elem_layout) }.is_ok() {
+            let expected = if elem_layout.size() == 0 { old_cap } else { cap };
+            assert_eq!(inner.capacity(elem_layout.size()), expected);
+        }
+        unsafe { inner.deallocate(elem_layout) };
+    }
+
+    #[kani::proof_for_contract(RawVecInner::<Global>::deallocate)]
+    fn check_deallocate() {
+        let elem_layout = any_elem_layout();
+        let mut inner = any_raw_vec_inner(elem_layout);
+        unsafe { inner.deallocate(elem_layout) };
+    }
+
+    #[kani::proof_for_contract(RawVecInner::<Global>::set_ptr_and_cap)]
+    fn check_set_ptr_and_cap() {
+        let elem_layout = any_elem_layout();
+        let mut inner = any_raw_vec_inner(elem_layout);
+        let cap = kani::any_where(|cap: &usize| *cap <= MAX_CAP);
+        let layout = layout_array(cap, elem_layout).unwrap();
+        let ptr = Global.allocate(layout).unwrap();
+        unsafe { inner.deallocate(elem_layout) };
+        unsafe { inner.set_ptr_and_cap(ptr, cap) };
+        assert_eq!(inner.non_null::<u8>(), ptr.cast());
+        if elem_layout.size() != 0 {
+            assert!(inner.is_safe_for(elem_layout));
+        }
+        unsafe { inner.deallocate(elem_layout) };
+    }
+
+    #[kani::proof_for_contract(new_cap::<u32>)]
+    fn check_new_cap() {
+        let cap = kani::any();
+        assert_eq!(unsafe { new_cap::<u32>(cap) }.as_inner(), cap);
+    }
+
+    #[kani::proof_for_contract(RawVecInner::<Global>::from_raw_parts_in)]
+    fn check_inner_from_raw_parts_in() {
+        let (ptr, cap) = any_raw_parts::<u8>();
+        let inner = unsafe { RawVecInner::from_raw_parts_in(ptr, Cap::new(cap).unwrap(), Global) };
+        assert_eq!(inner.ptr::<u8>(), ptr);
+    }
+
+    #[kani::proof_for_contract(RawVecInner::<Global>::from_nonnull_in)]
+    fn check_inner_from_nonnull_in() {
+        let (ptr, cap) = any_raw_parts::<u8>();
+        let ptr = NonNull::new(ptr).unwrap();
+        let inner = unsafe { RawVecInner::from_nonnull_in(ptr, Cap::new(cap).unwrap(), Global) };
+        assert_eq!(inner.non_null::<u8>(), ptr);
+    }
+
+    #[kani::proof_for_contract(RawVec::<u32>::from_raw_parts_in)]
+    fn check_from_raw_parts_in() {
+        let (ptr, cap) = any_raw_parts::<u32>();
+        let raw_vec = unsafe { RawVec::from_raw_parts_in(ptr, cap, Global) };
+        assert_eq!(raw_vec.capacity(), cap);
+    }
+
+    #[kani::proof_for_contract(RawVec::<()>::from_raw_parts_in)]
+    fn check_from_raw_parts_in_zst() {
+        let (ptr, _) = any_raw_parts::<()>();
+        let raw_vec = unsafe { RawVec::from_raw_parts_in(ptr, kani::any(), Global) };
+        assert_eq!(raw_vec.capacity(), usize::MAX);
+    }
+
+    #[kani::proof_for_contract(RawVec::<u32>::from_nonnull_in)]
+    fn check_from_nonnull_in() {
+        let (ptr, cap) = any_raw_parts::<u32>();
+        let raw_vec = unsafe { RawVec::from_nonnull_in(NonNull::new(ptr).unwrap(), cap, Global) };
+        assert_eq!(raw_vec.capacity(), cap);
+    }
+
+    #[kani::proof_for_contract(RawVec::<u32>::into_box)]
+    fn check_into_box() {
+        let cap = kani::any_where(|cap: &usize| *cap <= MAX_CAP);
+        let raw_vec = RawVec::<u32>::try_with_capacity_in(cap, Global).unwrap();
+        // The length must also be at least the requested capacity, which the contract cannot check.
+        let boxed = unsafe { raw_vec.into_box(cap) };
+        assert_eq!(boxed.len(), cap);
+    }
+}
@@ -262,7 +262,10 @@ use core::pin::PinCoerceUnsized;
 use core::ptr::{self, NonNull, drop_in_place};
 #[cfg(not(no_global_oom_handling))]
 use core::slice::from_raw_parts_mut;
-use core::{borrow, fmt, hint};
+use core::ub_checks::Invariant;
+use core::{borrow, fmt, hint, ub_checks};
+
+use safety::{ensures, requires};
 
 #[cfg(not(no_global_oom_handling))]
 use crate::alloc::handle_alloc_error;
@@ -1208,6 +1211,7 @@ impl<T, A: Allocator> Rc<mem::MaybeUninit<T>, A> {
     /// ```
     #[stable(feature = "new_uninit", since = "1.82.0")]
     #[inline]
+    #[requires(ub_checks::can_dereference(Rc::as_ptr(&self) as *const T))]
     pub unsafe fn assume_init(self) -> Rc<T, A> {
         let (ptr, alloc) = Rc::into_inner_with_allocator(self);
         unsafe { Rc::from_inner_in(ptr.cast(), alloc) }
@@ -1248,6 +1252,7 @@ impl<T, A: Allocator> Rc<[mem::MaybeUninit<T>], A> {
     /// ```
     #[stable(feature = "new_uninit", since = "1.82.0")]
     #[inline]
+    #[requires(ub_checks::can_dereference(Rc::as_ptr(&self) as *const [T]))]
     pub unsafe fn assume_init(self) -> Rc<[T], A> {
         let (ptr, alloc) = Rc::into_inner_with_allocator(self);
         unsafe { Rc::from_ptr_in(ptr.as_ptr() as _, alloc) }
@@ -1318,6 +1323,10 @@ impl<T: ?Sized> Rc<T> {
     /// ```
     #[inline]
     #[stable(feature = "rc_raw", since = "1.17.0")]
+    #[requires(
+        ub_checks::can_dereference(ptr)
+            && counts_from_raw(ptr).is_some_and(|(strong, _)| strong >= 1)
+    )]
     pub unsafe fn from_raw(ptr: *const T) -> Self {
         unsafe { Self::from_raw_in(ptr, Global) }
     }
@@ -1354,6 +1363,10 @@ impl<T: ?Sized> Rc<T> {
     /// ```
     #[inline]
     #[stable(feature = "rc_mutate_strong_count", since = "1.53.0")]
+    #[requires(
+        ub_checks::can_dereference(ptr)
+            && counts_from_raw(ptr).is_some_and(|(strong, _)| strong >= 1)
+    )]
     pub unsafe fn increment_strong_count(ptr: *const T) {
         unsafe { Self::increment_strong_count_in(ptr, Global) }
     }
@@ -1391,6 +1404,10 @@ impl<T: ?Sized> Rc<T> {
     /// ```
     #[inline]
     #[stable(feature = "rc_mutate_strong_count", since = "1.53.0")]
+    #[requires(
+        ub_checks::can_dereference(ptr)
+            && counts_from_raw(ptr).is_some_and(|(strong, _)| strong >= 1)
+    )]
     pub unsafe fn decrement_strong_count(ptr: *const T) {
         unsafe { Self::decrement_strong_count_in(ptr, Global) }
     }
@@ -1555,6 +1572,10 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     /// }
     /// ```
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(
+        ub_checks::can_dereference(ptr)
+            && counts_from_raw(ptr).is_some_and(|(strong, _)| strong >= 1)
+    )]
     pub unsafe fn from_raw_in(ptr: *const T, alloc: A) -> Self {
         let offset = unsafe { data_offset(ptr) };
 
@@ -1659,6 +1680,10 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(
+        ub_checks::can_dereference(ptr)
+            && counts_from_raw(ptr).is_some_and(|(strong, _)| strong >= 1)
+    )]
     pub unsafe fn increment_strong_count_in(ptr: *const T, alloc: A)
     where
         A: Clone,
@@ -1705,6 +1730,10 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(
+        ub_checks::can_dereference(ptr)
+            && counts_from_raw(ptr).is_some_and(|(strong, _)| strong >= 1)
+    )]
     pub unsafe fn decrement_strong_count_in(ptr: *const T, alloc: A) {
         unsafe { drop(Rc::from_raw_in(ptr, alloc)) };
     }
@@ -1808,6 +1837,13 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "get_mut_unchecked", issue = "63292")]
+    // Only checks that the allocation is alive, which its weak count tells, since the strong
+    // count may already be zero when the value is moved out of the last strong reference. The
+    // aliasing requirement is out of scope for the contract: it is about how the caller uses the
+    // other `Rc` and `Weak` pointers while the returned borrow is alive, which the arguments do
+    // not reveal. Requiring a unique `Rc` instead would reject valid callers, which may keep
+    // other pointers as long as they do not dereference them.
+    #[requires(this.inner().weak() >= 1)]
     pub unsafe fn get_mut_unchecked(this: &mut Self) -> &mut T {
         // We are careful to *not* create a reference covering the "count" fields, as
         // this would conflict with accesses to the reference counts (e.g. by `Weak`).
@@ -2041,6 +2077,7 @@ impl<A: Allocator> Rc<dyn Any, A> {
     /// [`downcast`]: Self::downcast
     #[inline]
     #[unstable(feature = "downcast_unchecked", issue = "90850")]
+    #[requires((*self).is::<T>())]
     pub unsafe fn downcast_unchecked<T: Any>(self) -> Rc<T, A> {
         unsafe {
             let (ptr, alloc) = Rc::into_inner_with_allocator(self);
@@ -2056,6 +2093,8 @@ impl<T: ?Sized> Rc<T> {
     /// The function `mem_to_rc_inner` is called with the data pointer
     /// and must return back a (potentially fat)-pointer for the `RcInner<T>`.
     #[cfg(not(no_global_oom_handling))]
+    #[ensures(|inner| rc_inner_layout_for_value_layout(value_layout) == unsafe { Layout::for_value_raw(*inner) }
+        && counts_from_raw(unsafe { &raw const (**inner).value }) == Some((1, 1)))]
     unsafe fn allocate_for_layout(
         value_layout: Layout,
         allocate: impl FnOnce(Layout) -> Result<NonNull<[u8]>, AllocError>,
@@ -3121,6 +3160,7 @@ impl<T: ?Sized> Weak<T> {
     /// [`new`]: Weak::new
     #[inline]
     #[stable(feature = "weak_into_raw", since = "1.45.0")]
+    #[requires(is_dangling(ptr) || counts_from_raw(ptr).is_some_and(|(_, weak)| weak >= 1))]
     pub unsafe fn from_raw(ptr: *const T) -> Self {
         unsafe { Self::from_raw_in(ptr, Global) }
     }
@@ -3293,6 +3333,7 @@ impl<T: ?Sized, A: Allocator> Weak<T, A> {
     /// [`new`]: Weak::new
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_dangling(ptr) || counts_from_raw(ptr).is_some_and(|(_, weak)| weak >= 1))]
     pub unsafe fn from_raw_in(ptr: *const T, alloc: A) -> Self {
         // See Weak::as_ptr for context on how the input pointer is derived.
 
@@ -3658,6 +3699,25 @@ impl<T: ?Sized, A: Allocator> AsRef<T> for Rc<T, A> {
 #[stable(feature = "pin", since = "1.33.0")]
 impl<T: ?Sized, A: Allocator> Unpin for Rc<T, A> {}
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T: ?Sized, A: Allocator> Invariant for Rc<T, A> {
+    /// An `Rc` keeps its allocation alive: it is one of the strong references, and the strong
+    /// references collectively own one weak reference.
+    fn is_safe(&self) -> bool {
+        let inner = self.inner();
+        inner.strong() >= 1 && inner.weak() >= 1
+    }
+}
+
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T: ?Sized, A: Allocator> Invariant for Weak<T, A> {
+    /// A `Weak` is either dangling, or it is one of the weak references keeping the allocation
+    /// alive, in addition to the weak reference owned by the strong references, if there are any.
+    fn is_safe(&self) -> bool {
+        self.inner().is_none_or(|inner| inner.weak() >= 1 + (inner.strong() > 0) as usize)
+    }
+}
+
 /// Gets the offset within an `RcInner` for the payload behind a pointer.
 ///
 /// # Safety
@@ -3680,6 +3740,23 @@ fn data_offset_align(align: usize) -> usize {
     layout.size() + layout.padding_needed_for(align)
 }
 
+/// Reads the strong and weak counts of the `RcInner` that `ptr` points into, assuming `ptr` was
+/// returned by [`Rc::into_raw`] or [`Weak::into_raw`]. Returns `None` if the counts cannot be
+/// read, e.g. because `ptr` comes from [`Weak::new`].
+#[allow(dead_code)] // Only used by contracts.
+fn counts_from_raw<T: ?Sized>(ptr: *const T) -> Option<(usize, usize)> {
+    if is_dangling(ptr) {
+        return None;
+    }
+    // SAFETY: `data_offset` only relies on the metadata of `ptr`, which is valid for `T` since
+    // `ptr` was derived from an `Rc<T>` or a `Weak<T>`.
+    let offset = unsafe { data_offset(ptr) };
+    // The counts are the first fields of `RcInner`, whatever the type of the value.
+    let inner = ptr.wrapping_byte_sub(offset) as *const RcInner<()>;
+    // SAFETY: just checked that `inner` can be read.
+    ub_checks::can_dereference(inner).then(|| unsafe { ((*inner).strong(), (*inner).weak()) })
+}
+
 /// A uniquely owned [`Rc`].
 ///
 /// This represents an `Rc` that is known to be uniquely owned -- that is, have exactly one strong
@@ -4159,3 +4236,321 @@ impl<T: ?Sized, A: Allocator> Drop for UniqueRcUninit<T, A> {
         }
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::kani;
+
+    use super::*;
+    use crate::arbitrary::any_vec;
+
+    const MAX_LEN: usize = 4;
+
+    /// Arbitrarily creates another strong reference and a weak reference to the allocation of
+    /// `rc`, so that harnesses cover unique, shared and weakly referenced allocations.
+    fn any_sharing<T: ?Sized>(rc: &Rc<T>) -> (Option<Rc<T>>, Option<Weak<T>>) {
+        let strong = kani::any::<bool>().then(|| Rc::clone(rc));
+        let weak = kani::any::<bool>().then(|| Rc::downgrade(rc));
+        (strong, weak)
+    }
+
+    #[kani::proof_for_contract(Rc::<mem::MaybeUninit<u8>>::assume_init)]
+    fn check_assume_init() {
+        let mut rc = Rc::<u8>::new_uninit();
+        let value = kani::any();
+        Rc::get_mut(&mut rc).unwrap().write(value);
+        let rc = unsafe { rc.assume_init() };
+        assert!(rc.is_safe());
+        assert_eq!(*rc, value);
+    }
+
+    #[kani::proof_for_contract(Rc::<[mem::MaybeUninit<u8>]>::assume_init)]
+    #[kani::unwind(5)]
+    fn check_assume_init_slice() {
+        let len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        let mut rc = Rc::<[u8]>::new_uninit_slice(len);
+        for elem in Rc::get_mut(&mut rc).unwrap() {
+            elem.write(kani::any());
+        }
+        let rc = unsafe { rc.assume_init() };
+        assert!(rc.is_safe());
+        assert_eq!(rc.len(), len);
+    }
+
+    #[kani::proof_for_contract(Rc::<u8>::from_raw)]
+    fn check_from_raw() {
+        let rc = Rc::new(kani::any::<u8>());
+        let (_other, _weak) = any_sharing(&rc);
+        let ptr = Rc::into_raw(rc);
+        let rc = unsafe { Rc::from_raw(ptr) };
+        assert!(rc.is_safe());
+    }
+
+    #[kani::proof_for_contract(Rc::<[u8]>::from_raw)]
+    #[kani::unwind(5)]
+    fn check_from_raw_slice() {
+        let rc = Rc::<[u8]>::from(any_vec::<u8, MAX_LEN>());
+        let len = rc.len();
+        let (_other, _weak) = any_sharing(&rc);
+        let ptr = Rc::into_raw(rc);
+        let rc = unsafe { Rc::from_raw(ptr) };
+        assert!(rc.is_safe());
+        assert_eq!(rc.len(), len);
+    }
+
+    #[kani::proof_for_contract(Rc::<u8>::from_raw_in)]
+    fn check_from_raw_in() {
+        let rc = Rc::new(kani::any::<u8>());
+        let (_other, _weak) = any_sharing(&rc);
+        let (ptr, alloc) = Rc::into_raw_with_allocator(rc);
+        let rc = unsafe { Rc::from_raw_in(ptr, alloc) };
+        assert!(rc.is_safe());
+    }
+
+    #[kani::proof_for_contract(Rc::<u8>::increment_strong_count)]
+    fn check_increment_strong_count() {
+        let rc = Rc::new(kani::any::<u8>());
+        let ptr = Rc::as_ptr(&rc);
+        unsafe { Rc::increment_strong_count(ptr) };
+        assert_eq!(Rc::strong_count(&rc), 2);
+        unsafe { Rc::decrement_strong_count(ptr) };
+    }
+
+    #[kani::proof_for_contract(Rc::<u8>::increment_strong_count_in)]
+    fn check_increment_strong_count_in() {
+        let rc = Rc::new(kani::any::<u8>());
+        let ptr = Rc::as_ptr(&rc);
+        unsafe { Rc::increment_strong_count_in(ptr, Global) };
+        assert_eq!(Rc::strong_count(&rc), 2);
+        unsafe { Rc::decrement_strong_count_in(ptr, Global) };
+    }
+
+    #[kani::proof_for_contract(Rc::<u8>::decrement_strong_count)]
+    fn check_decrement_strong_count() {
+        let rc = Rc::new(kani::any::<u8>());
+        let weak = Rc::downgrade(&rc);
+        let ptr = Rc::into_raw(rc);
+        unsafe { Rc::decrement_strong_count(ptr) };
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[kani::proof_for_contract(Rc::<u8>::decrement_strong_count_in)]
+    fn check_decrement_strong_count_in() {
+        let rc = Rc::new(kani::any::<u8>());
+        let other = Rc::clone(&rc);
+        let (ptr, alloc) = Rc::into_raw_with_allocator(other);
+        unsafe { Rc::decrement_strong_count_in(ptr, alloc) };
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    // The other pointers are not dereferenced while the returned borrow is alive, and see the
+    // new value afterwards.
+    #[kani::proof_for_contract(Rc::<u8>::get_mut_unchecked)]
+    fn check_get_mut_unchecked() {
+        let mut rc = Rc::new(kani::any::<u8>());
+        let (other, weak) = any_sharing(&rc);
+        let value = kani::any();
+        *unsafe { Rc::get_mut_unchecked(&mut rc) } = value;
+        assert_eq!(*rc, value);
+        assert!(other.is_none_or(|other| *other == value));
+        assert!(weak.is_none_or(|weak| weak.upgrade().is_some_and(|rc| *rc == value)));
+    }
+
+    #[kani::proof_for_contract(Rc::<[u8]>::allocate_for_layout)]
+    fn check_allocate_for_layout() {
+        let len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        let value_layout = Layout::array::<u8>(len).unwrap();
+        let inner = unsafe {
+            Rc::<[u8]>::allocate_for_layout(
+                value_layout,
+                |layout| Global.allocate(layout),
+                |mem| ptr::slice_from_raw_parts_mut(mem, len) as *mut RcInner<[u8]>,
+            )
+        };
+        let layout = rc_inner_layout_for_value_layout(value_layout);
+        unsafe { Global.deallocate(NonNull::new_unchecked(inner.cast()), layout) };
+    }
+
+    // The weak reference handed to `data_fn` cannot be upgraded until the value is initialized,
+    // and then refers to the new allocation.
+    #[kani::proof]
+    fn check_new_cyclic() {
+        let value = kani::any::<u8>();
+        let keep = kani::any::<bool>();
+        let mut kept = None;
+        let rc = Rc::new_cyclic(|weak| {
+            assert!(weak.upgrade().is_none());
+            assert_eq!(weak.strong_count(), 0);
+            kept = keep.then(|| weak.clone());
+            value
+        });
+        assert!(rc.is_safe() && *rc == value);
+        assert_eq!(Rc::strong_count(&rc), 1);
+        assert_eq!(Rc::weak_count(&rc), keep as usize);
+        if let Some(weak) = kept {
+            assert!(weak.is_safe() && Rc::ptr_eq(&weak.upgrade().unwrap(), &rc));
+        }
+    }
+
+    #[kani::proof_for_contract(Rc::<dyn Any>::downcast_unchecked)]
+    fn check_downcast_unchecked() {
+        let value = kani::any::<u8>();
+        let rc: Rc<dyn Any> = Rc::new(value);
+        let rc = unsafe { rc.downcast_unchecked::<u8>() };
+        assert!(rc.is_safe());
+        assert_eq!(*rc, value);
+    }
+
+    /// Returns a weak reference to `rc` or a dangling one.
+    fn any_weak<T>(rc: &Rc<T>) -> Weak<T> {
+        if kani::any() { Rc::downgrade(rc) } else { Weak::new() }
+    }
+
+    #[kani::proof_for_contract(Weak::<u8>::from_raw)]
+    fn check_weak_from_raw() {
+        let rc = Rc::new(kani::any::<u8>());
+        let weak = any_weak(&rc);
+        if kani::any() {
+            drop(rc);
+        }
+        let weak = unsafe { Weak::from_raw(weak.into_raw()) };
+        assert!(weak.is_safe());
+    }
+
+    #[kani::proof_for_contract(Weak::<u8>::from_raw_in)]
+    fn check_weak_from_raw_in() {
+        let rc = Rc::new(kani::any::<u8>());
+        let weak = any_weak(&rc);
+        if kani::any() {
+            drop(rc);
+        }
+        let (ptr, alloc) = weak.into_raw_with_allocator();
+        let weak = unsafe { Weak::from_raw_in(ptr, alloc) };
+        assert!(weak.is_safe());
+    }
+
+    /// Checks that `make_mut` makes `rc` unique, moving the value to a new allocation if and only
+    /// if there are other references, without changing what these references point to.
+    fn check_make_mut_of<T: ?Sized + CloneToUninit + PartialEq>(mut rc: Rc<T>) {
+        let (other, weak) = any_sharing(&rc);
+        let old_ptr = Rc::as_ptr(&rc);
+        Rc::make_mut(&mut rc);
+        assert!(rc.is_safe());
+        assert!(Rc::strong_count(&rc) == 1 && Rc::weak_count(&rc) == 0);
+        assert_eq!(ptr::addr_eq(Rc::as_ptr(&rc), old_ptr), other.is_none() && weak.is_none());
+        if let Some(other) = &other {
+            assert!(other.is_safe() && ptr::addr_eq(Rc::as_ptr(other), old_ptr));
+            assert!(**other == *rc);
+        }
+        if let Some(weak) = weak {
+            assert!(weak.is_safe());
+            assert_eq!(weak.upgrade().is_some(), other.is_some());
+        }
+    }
+
+    #[kani::proof]
+    fn check_make_mut() {
+        check_make_mut_of(Rc::new(kani::any::<u8>()));
+    }
+
+    #[kani::proof]
+    fn check_make_mut_zst() {
+        check_make_mut_of(Rc::new(()));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_make_mut_slice() {
+        check_make_mut_of(Rc::<[u8]>::from(any_vec::<u8, MAX_LEN>()));
+    }
+
+    /// Checks that `try_unwrap` returns the value if and only if `rc` is the only strong
+    /// reference, after which the weak references can no longer be upgraded.
+    fn check_try_unwrap_of<T: Clone + PartialEq>(value: T) {
+        let rc = Rc::new(value.clone());
+        let (other, weak) = any_sharing(&rc);
+        match Rc::try_unwrap(rc) {
+            Ok(unwrapped) => {
+                assert!(other.is_none() && unwrapped == value);
+                assert!(weak.is_none_or(|weak| weak.is_safe() && weak.upgrade().is_none()));
+            }
+            Err(rc) => {
+                assert!(other.is_some() && rc.is_safe());
+                assert_eq!(Rc::strong_count(&rc), 2);
+            }
+        }
+    }
+
+    #[kani::proof]
+    fn check_try_unwrap() {
+        check_try_unwrap_of(kani::any::<u8>());
+    }
+
+    #[kani::proof]
+    fn check_try_unwrap_zst() {
+        check_try_unwrap_of(());
+    }
+
+    /// Checks that when two strong references are consumed by `into_inner`, exactly one of them
+    /// returns the value.
+    fn check_into_inner_of<T: Clone + PartialEq>(value: T) {
+        let rc = Rc::new(value.clone());
+        let (other, weak) = any_sharing(&rc);
+        let first = Rc::into_inner(rc);
+        assert_eq!(first.is_some(), other.is_none());
+        let second = other.and_then(Rc::into_inner);
+        assert!(first.or(second).is_some_and(|inner| inner == value));
+        assert!(weak.is_none_or(|weak| weak.is_safe() && weak.upgrade().is_none()));
+    }
+
+    #[kani::proof]
+    fn check_into_inner() {
+        check_into_inner_of(kani::any::<u8>());
+    }
+
+    #[kani::proof]
+    fn check_into_inner_zst() {
+        check_into_inner_of(());
+    }
+
+    /// Checks that a weak reference to `rc` can be upgraded exactly as long as a strong reference
+    /// exists.
+    fn check_upgrade_of<T: ?Sized>(rc: Rc<T>) {
+        let weak = Rc::downgrade(&rc);
+        let other = kani::any::<bool>().then(|| Rc::clone(&rc));
+        assert!(weak.is_safe());
+        let upgraded = weak.upgrade().unwrap();
+        assert!(upgraded.is_safe() && Rc::ptr_eq(&upgraded, &rc));
+        assert_eq!(Rc::strong_count(&rc), 2 + other.is_some() as usize);
+        drop(upgraded);
+        drop(rc);
+        assert_eq!(weak.upgrade().is_some(), other.is_some());
+        drop(other);
+        assert!(weak.is_safe() && weak.upgrade().is_none());
+    }
+
+    #[kani::proof]
+    fn check_upgrade() {
+        check_upgrade_of(Rc::new(kani::any::<u8>()));
+        assert!(Weak::<u8>::new().upgrade().is_none());
+    }
+
+    #[kani::proof]
+    fn check_upgrade_zst() {
+        check_upgrade_of(Rc::new(()));
+        assert!(Weak::<()>::new().upgrade().is_none());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_upgrade_slice() {
+        check_upgrade_of(Rc::<[u8]>::from(any_vec::<u8, MAX_LEN>()));
+    }
+
+    #[kani::proof]
+    fn check_upgrade_dyn() {
+        let rc: Rc<dyn Any> = Rc::new(kani::any::<u8>());
+        check_upgrade_of(rc);
+    }
+}
@@ -49,6 +49,8 @@ pub use core::str::{from_raw_parts, from_raw_parts_mut};
 use core::unicode::conversions;
 use core::{mem, ptr};
 
+use safety::requires;
+
 use crate::borrow::ToOwned;
 use crate::boxed::Box;
 use crate::slice::{Concat, Join, SliceIndex};
@@ -615,6 +617,7 @@ impl str {
 #[stable(feature = "str_box_extras", since = "1.20.0")]
 #[must_use]
 #[inline]
+#[requires(from_utf8(&v).is_ok())]
 pub unsafe fn from_boxed_utf8_unchecked(v: Box<[u8]>) -> Box<str> {
     unsafe { Box::from_raw(Box::into_raw(v) as *mut str) }
 }
@@ -707,8 +710,38 @@ pub fn convert_while_ascii(s: &str, convert: fn(&u8) -> u8) -> (String, &str) {
 #[allow(dead_code)]
 /// Faster implementation of string replacement for ASCII to ASCII cases.
 /// Should produce fast vectorized code.
+#[requires(from_utf8(utf8_bytes).is_ok() && from.is_ascii() && to.is_ascii())]
 unsafe fn replace_ascii(utf8_bytes: &[u8], from: u8, to: u8) -> String {
     let result: Vec<u8> = utf8_bytes.iter().map(|b| if *b == from { to } else { *b }).collect();
     // SAFETY: We replaced ascii with ascii on valid utf8 strings.
     unsafe { String::from_utf8_unchecked(result) }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::kani;
+
+    use super::*;
+    use crate::arbitrary::{any_string, any_vec};
+
+    const MAX_LEN: usize = 4;
+
+    #[kani::proof_for_contract(from_boxed_utf8_unchecked)]
+    #[kani::unwind(6)]
+    fn check_from_boxed_utf8_unchecked() {
+        let bytes = any_vec::<u8, MAX_LEN>().into_boxed_slice();
+        let len = bytes.len();
+        let s = unsafe { from_boxed_utf8_unchecked(bytes) };
+        assert_eq!(s.len(), len);
+    }
+
+    #[kani::proof_for_contract(replace_ascii)]
+    #[kani::unwind(6)]
+    fn check_replace_ascii() {
+        let s = any_string::<MAX_LEN>();
+        let replaced = unsafe { replace_ascii(s.as_bytes(), kani::any(), kani::any()) };
+        assert_eq!(replaced.len(), s.len());
+        assert!(from_utf8(replaced.as_bytes()).is_ok());
+    }
+}
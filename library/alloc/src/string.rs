@@ -54,8 +54,14 @@ use core::ops::AddAssign;
 use core::ops::Bound::{Excluded, Included, Unbounded};
 use core::ops::{self, Range, RangeBounds};
 use core::str::pattern::{Pattern, Utf8Pattern};
+// Used only for contract verification.
+#[allow(unused_imports)]
+use core::ub_checks;
+use core::ub_checks::Invariant;
 use core::{fmt, hash, ptr, slice};
 
+use safety::{ensures, requires};
+
 #[cfg(not(no_global_oom_handling))]
 use crate::alloc::Allocator;
 #[cfg(not(no_global_oom_handling))]
@@ -991,6 +997,8 @@ impl String {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[requires(length <= capacity && is_utf8_raw(buf, length))]
+    #[ensures(|s| s.is_safe() && s.len() == length)]
     pub unsafe fn from_raw_parts(buf: *mut u8, length: usize, capacity: usize) -> String {
         unsafe { String { vec: Vec::from_raw_parts(buf, length, capacity) } }
     }
@@ -1024,6 +1032,7 @@ impl String {
     #[inline]
     #[must_use]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[requires(str::from_utf8(&bytes).is_ok())]
     pub unsafe fn from_utf8_unchecked(bytes: Vec<u8>) -> String {
         String { vec: bytes }
     }
@@ -2131,6 +2140,23 @@ impl String {
     }
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl Invariant for String {
+    /// The bytes of a `String` are valid UTF-8.
+    fn is_safe(&self) -> bool {
+        str::from_utf8(self.vec.as_slice()).is_ok()
+    }
+}
+
+/// Checks that the `length` bytes starting at `buf` can be read and are valid UTF-8.
+#[allow(dead_code)] // Only used by contracts.
+fn is_utf8_raw(buf: *const u8, length: usize) -> bool {
+    !buf.is_null()
+        && ub_checks::can_dereference(ptr::slice_from_raw_parts(buf, length))
+        // SAFETY: just checked that the bytes can be read.
+        && str::from_utf8(unsafe { slice::from_raw_parts(buf, length) }).is_ok()
+}
+
 impl FromUtf8Error {
     /// Returns a slice of [`u8`]s bytes that were attempted to convert to a `String`.
     ///
@@ -3504,3 +3530,278 @@ impl From<char> for String {
         c.to_string()
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::kani;
+    use core::mem::ManuallyDrop;
+
+    use super::*;
+    use crate::arbitrary::{any_string, any_vec};
+
+    const MAX_LEN: usize = 4;
+
+    fn any_str() -> String {
+        any_string::<MAX_LEN>()
+    }
+
+    /// Returns an arbitrary char boundary of `s`, including its end.
+    fn any_boundary(s: &str) -> usize {
+        kani::any_where(|idx: &usize| s.is_char_boundary(*idx))
+    }
+
+    /// Returns an arbitrary range of `s` whose bounds are char boundaries.
+    fn any_range(s: &str) -> Range<usize> {
+        let start = any_boundary(s);
+        let end = any_boundary(s);
+        kani::assume(start <= end);
+        start..end
+    }
+
+    /// Returns an arbitrary slice of at most `2 * MAX_LEN` bytes, to be decoded as UTF-16.
+    fn any_utf16_bytes(bytes: &[u8; 2 * MAX_LEN]) -> &[u8] {
+        &bytes[..kani::any_where(|len: &usize| *len <= 2 * MAX_LEN)]
+    }
+
+    #[kani::proof_for_contract(String::from_utf8_unchecked)]
+    #[kani::unwind(6)]
+    fn check_from_utf8_unchecked() {
+        let bytes = any_vec::<u8, MAX_LEN>();
+        let len = bytes.len();
+        let s = unsafe { String::from_utf8_unchecked(bytes) };
+        assert!(s.is_safe());
+        assert_eq!(s.len(), len);
+    }
+
+    #[kani::proof_for_contract(String::from_raw_parts)]
+    #[kani::unwind(6)]
+    fn check_from_raw_parts() {
+        let mut s = ManuallyDrop::new(any_str());
+        let s = unsafe { String::from_raw_parts(s.as_mut_ptr(), s.len(), s.capacity()) };
+        assert!(s.len() <= s.capacity());
+    }
+
+    // The bytes only have to be UTF-8 again once the caller is done mutating them, so the
+    // obligation is checked after mutations which keep them UTF-8.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_as_mut_vec() {
+        let mut s = any_str();
+        let len = s.len();
+        let new_len = kani::any_where(|n: &usize| *n <= len && s.is_char_boundary(*n));
+        let byte = kani::any_where(|b: &u8| b.is_ascii());
+        let vec = unsafe { s.as_mut_vec() };
+        assert_eq!(vec.len(), len);
+        if kani::any() {
+            vec.truncate(new_len);
+        } else {
+            vec.push(byte);
+        }
+        assert!(s.is_safe());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_from_utf16() {
+        let bytes = kani::any();
+        let bytes = any_utf16_bytes(&bytes);
+        if let Ok(s) = String::from_utf16le(bytes) {
+            assert!(s.is_safe());
+        }
+        if let Ok(s) = String::from_utf16be(bytes) {
+            assert!(s.is_safe());
+        }
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_from_utf16_lossy() {
+        let bytes = kani::any();
+        let bytes = any_utf16_bytes(&bytes);
+        assert!(String::from_utf16le_lossy(bytes).is_safe());
+        assert!(String::from_utf16be_lossy(bytes).is_safe());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_push() {
+        let mut s = any_str();
+        let len = s.len();
+        let ch: char = kani::any();
+        s.push(ch);
+        assert!(s.is_safe());
+        assert_eq!(s.len(), len + ch.len_utf8());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(10)]
+    fn check_push_str() {
+        let mut s = any_str();
+        let other = any_str();
+        s.push_str(&other);
+        assert!(s.is_safe());
+        assert!(s.ends_with(other.as_str()));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(10)]
+    fn check_extend_from_within() {
+        let mut s = any_str();
+        let len = s.len();
+        let range = any_range(&s);
+        s.extend_from_within(range.clone());
+        assert!(s.is_safe());
+        assert_eq!(s.len(), len + range.len());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_truncate() {
+        let mut s = any_str();
+        let new_len = any_boundary(&s);
+        s.truncate(new_len);
+        assert!(s.is_safe());
+        assert_eq!(s.len(), new_len);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_pop() {
+        let mut s = any_str();
+        let len = s.len();
+        match s.pop() {
+            Some(ch) => assert_eq!(s.len(), len - ch.len_utf8()),
+            None => assert_eq!(len, 0),
+        }
+        assert!(s.is_safe());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_remove() {
+        let mut s = any_str();
+        let len = s.len();
+        let idx = any_boundary(&s);
+        kani::assume(idx < len);
+        let ch = s.remove(idx);
+        assert!(s.is_safe());
+        assert_eq!(s.len(), len - ch.len_utf8());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_remove_matches() {
+        let mut s = any_str();
+        let ch: char = kani::any();
+        s.remove_matches(ch);
+        assert!(s.is_safe());
+        assert!(!s.contains(ch));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_retain() {
+        let mut s = any_str();
+        let len = s.len();
+        s.retain(|_| kani::any());
+        assert!(s.is_safe());
+        assert!(s.len() <= len);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_insert() {
+        let mut s = any_str();
+        let len = s.len();
+        let idx = any_boundary(&s);
+        let ch: char = kani::any();
+        s.insert(idx, ch);
+        assert!(s.is_safe());
+        assert_eq!(s.len(), len + ch.len_utf8());
+        assert_eq!(s[idx..].chars().next(), Some(ch));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(10)]
+    fn check_insert_str() {
+        let mut s = any_str();
+        let len = s.len();
+        let idx = any_boundary(&s);
+        let other = any_str();
+        s.insert_str(idx, &other);
+        assert!(s.is_safe());
+        assert_eq!(s.len(), len + other.len());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_split_off() {
+        let mut s = any_str();
+        let len = s.len();
+        let at = any_boundary(&s);
+        let other = s.split_off(at);
+        assert!(s.is_safe() && other.is_safe());
+        assert_eq!(s.len(), at);
+        assert_eq!(other.len(), len - at);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_clear() {
+        let mut s = any_str();
+        s.clear();
+        assert!(s.is_safe() && s.is_empty());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_drain() {
+        let mut s = any_str();
+        let len = s.len();
+        let range = any_range(&s);
+        let mut drain = s.drain(range.clone());
+        if kani::any() {
+            let _ = drain.next();
+        }
+        if kani::any() {
+            let _ = drain.next_back();
+        }
+        assert!(str::from_utf8(drain.as_str().as_bytes()).is_ok());
+        drop(drain);
+        assert!(s.is_safe());
+        assert_eq!(s.len(), len - range.len());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(10)]
+    fn check_replace_range() {
+        let mut s = any_str();
+        let len = s.len();
+        let range = any_range(&s);
+        let replace_with = any_str();
+        s.replace_range(range.clone(), &replace_with);
+        assert!(s.is_safe());
+        assert_eq!(s.len(), len - range.len() + replace_with.len());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_into_boxed_str() {
+        let s = any_str();
+        let len = s.len();
+        let boxed = s.into_boxed_str();
+        assert!(str::from_utf8(boxed.as_bytes()).is_ok());
+        assert_eq!(boxed.len(), len);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_leak() {
+        let s = any_str();
+        let len = s.len();
+        let leaked = s.leak();
+        assert!(str::from_utf8(leaked.as_bytes()).is_ok());
+        assert_eq!(leaked.len(), len);
+    }
+}
@@ -28,7 +28,10 @@ use core::ptr::{self, NonNull};
 use core::slice::from_raw_parts_mut;
 use core::sync::atomic::Ordering::{Acquire, Relaxed, Release};
 use core::sync::atomic::{self, Atomic};
-use core::{borrow, fmt, hint};
+use core::ub_checks::Invariant;
+use core::{borrow, fmt, hint, ub_checks};
+
+use safety::{ensures, requires};
 
 #[cfg(not(no_global_oom_handling))]
 use crate::alloc::handle_alloc_error;
@@ -1353,6 +1356,7 @@ impl<T, A: Allocator> Arc<mem::MaybeUninit<T>, A> {
     #[stable(feature = "new_uninit", since = "1.82.0")]
     #[must_use = "`self` will be dropped if the result is not used"]
     #[inline]
+    #[requires(ub_checks::can_dereference(Arc::as_ptr(&self) as *const T))]
     pub unsafe fn assume_init(self) -> Arc<T, A> {
         let (ptr, alloc) = Arc::into_inner_with_allocator(self);
         unsafe { Arc::from_inner_in(ptr.cast(), alloc) }
@@ -1394,6 +1398,7 @@ impl<T, A: Allocator> Arc<[mem::MaybeUninit<T>], A> {
     #[stable(feature = "new_uninit", since = "1.82.0")]
     #[must_use = "`self` will be dropped if the result is not used"]
     #[inline]
+    #[requires(ub_checks::can_dereference(Arc::as_ptr(&self) as *const [T]))]
     pub unsafe fn assume_init(self) -> Arc<[T], A> {
         let (ptr, alloc) = Arc::into_inner_with_allocator(self);
         unsafe { Arc::from_ptr_in(ptr.as_ptr() as _, alloc) }
@@ -1464,6 +1469,10 @@ impl<T: ?Sized> Arc<T> {
     /// ```
     #[inline]
     #[stable(feature = "rc_raw", since = "1.17.0")]
+    #[requires(
+        ub_checks::can_dereference(ptr)
+            && counts_from_raw(ptr).is_some_and(|(strong, _)| strong >= 1)
+    )]
     pub unsafe fn from_raw(ptr: *const T) -> Self {
         unsafe { Arc::from_raw_in(ptr, Global) }
     }
@@ -1502,6 +1511,10 @@ impl<T: ?Sized> Arc<T> {
     /// ```
     #[inline]
     #[stable(feature = "arc_mutate_strong_count", since = "1.51.0")]
+    #[requires(
+        ub_checks::can_dereference(ptr)
+            && counts_from_raw(ptr).is_some_and(|(strong, _)| strong >= 1)
+    )]
     pub unsafe fn increment_strong_count(ptr: *const T) {
         unsafe { Arc::increment_strong_count_in(ptr, Global) }
     }
@@ -1542,6 +1555,10 @@ impl<T: ?Sized> Arc<T> {
     /// ```
     #[inline]
     #[stable(feature = "arc_mutate_strong_count", since = "1.51.0")]
+    #[requires(
+        ub_checks::can_dereference(ptr)
+            && counts_from_raw(ptr).is_some_and(|(strong, _)| strong >= 1)
+    )]
     pub unsafe fn decrement_strong_count(ptr: *const T) {
         unsafe { Arc::decrement_strong_count_in(ptr, Global) }
     }
@@ -1708,6 +1725,10 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(
+        ub_checks::can_dereference(ptr)
+            && counts_from_raw(ptr).is_some_and(|(strong, _)| strong >= 1)
+    )]
     pub unsafe fn from_raw_in(ptr: *const T, alloc: A) -> Self {
         unsafe {
             let offset = data_offset(ptr);
@@ -1864,6 +1885,10 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(
+        ub_checks::can_dereference(ptr)
+            && counts_from_raw(ptr).is_some_and(|(strong, _)| strong >= 1)
+    )]
     pub unsafe fn increment_strong_count_in(ptr: *const T, alloc: A)
     where
         A: Clone,
@@ -1913,6 +1938,10 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(
+        ub_checks::can_dereference(ptr)
+            && counts_from_raw(ptr).is_some_and(|(strong, _)| strong >= 1)
+    )]
     pub unsafe fn decrement_strong_count_in(ptr: *const T, alloc: A) {
         unsafe { drop(Arc::from_raw_in(ptr, alloc)) };
     }
@@ -1975,6 +2004,8 @@ impl<T: ?Sized> Arc<T> {
     /// The function `mem_to_arcinner` is called with the data pointer
     /// and must return back a (potentially fat)-pointer for the `ArcInner<T>`.
     #[cfg(not(no_global_oom_handling))]
+    #[ensures(|inner| arcinner_layout_for_value_layout(value_layout) == unsafe { Layout::for_value_raw(*inner) }
+        && counts_from_raw(unsafe { &raw const (**inner).data }) == Some((1, 1)))]
     unsafe fn allocate_for_layout(
         value_layout: Layout,
         allocate: impl FnOnce(Layout) -> Result<NonNull<[u8]>, AllocError>,
@@ -2520,6 +2551,13 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "get_mut_unchecked", issue = "63292")]
+    // Only checks that the allocation is alive, which its weak count tells: the strong count is
+    // already zero when `into_inner` moves the value out of the last strong reference. The
+    // aliasing requirement is out of scope for the contract: it is about how the caller uses the
+    // other `Arc` and `Weak` pointers while the returned borrow is alive, which the arguments do
+    // not reveal. Requiring a unique `Arc` instead would reject valid callers, which may keep
+    // other pointers as long as they do not dereference them.
+    #[requires(this.inner().weak.load(Relaxed) >= 1)]
     pub unsafe fn get_mut_unchecked(this: &mut Self) -> &mut T {
         // We are careful to *not* create a reference covering the "count" fields, as
         // this would alias with concurrent access to the reference counts (e.g. by `Weak`).
@@ -2751,6 +2789,7 @@ impl<A: Allocator> Arc<dyn Any + Send + Sync, A> {
     /// [`downcast`]: Self::downcast
     #[inline]
     #[unstable(feature = "downcast_unchecked", issue = "90850")]
+    #[requires((*self).is::<T>())]
     pub unsafe fn downcast_unchecked<T>(self) -> Arc<T, A>
     where
         T: Any + Send + Sync,
@@ -2861,6 +2900,7 @@ impl<T: ?Sized> Weak<T> {
     /// [`upgrade`]: Weak::upgrade
     #[inline]
     #[stable(feature = "weak_into_raw", since = "1.45.0")]
+    #[requires(is_dangling(ptr) || counts_from_raw(ptr).is_some_and(|(_, weak)| weak >= 1))]
     pub unsafe fn from_raw(ptr: *const T) -> Self {
         unsafe { Weak::from_raw_in(ptr, Global) }
     }
@@ -3032,6 +3072,7 @@ impl<T: ?Sized, A: Allocator> Weak<T, A> {
     /// [`upgrade`]: Weak::upgrade
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_dangling(ptr) || counts_from_raw(ptr).is_some_and(|(_, weak)| weak >= 1))]
     pub unsafe fn from_raw_in(ptr: *const T, alloc: A) -> Self {
         // See Weak::as_ptr for context on how the input pointer is derived.
 
@@ -4014,6 +4055,28 @@ impl<T: ?Sized, A: Allocator> AsRef<T> for Arc<T, A> {
 #[stable(feature = "pin", since = "1.33.0")]
 impl<T: ?Sized, A: Allocator> Unpin for Arc<T, A> {}
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T: ?Sized, A: Allocator> Invariant for Arc<T, A> {
+    /// An `Arc` keeps its allocation alive: it is one of the strong references, and the strong
+    /// references collectively own one weak reference. `is_unique` may temporarily lock the weak
+    /// count to `usize::MAX`, which still satisfies this.
+    fn is_safe(&self) -> bool {
+        let inner = self.inner();
+        inner.strong.load(Relaxed) >= 1 && inner.weak.load(Relaxed) >= 1
+    }
+}
+
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T: ?Sized, A: Allocator> Invariant for Weak<T, A> {
+    /// A `Weak` is either dangling, or it is one of the weak references keeping the allocation
+    /// alive, in addition to the weak reference owned by the strong references, if there are any.
+    fn is_safe(&self) -> bool {
+        self.inner().is_none_or(|inner| {
+            inner.weak.load(Relaxed) >= 1 + (inner.strong.load(Relaxed) > 0) as usize
+        })
+    }
+}
+
 /// Gets the offset within an `ArcInner` for the payload behind a pointer.
 ///
 /// # Safety
@@ -4036,6 +4099,24 @@ fn data_offset_align(align: usize) -> usize {
     layout.size() + layout.padding_needed_for(align)
 }
 
+/// Reads the strong and weak counts of the `ArcInner` that `ptr` points into, assuming `ptr` was
+/// returned by [`Arc::into_raw`] or [`Weak::into_raw`]. Returns `None` if the counts cannot be
+/// read, e.g. because `ptr` comes from [`Weak::new`].
+#[allow(dead_code)] // Only used by contracts.
+fn counts_from_raw<T: ?Sized>(ptr: *const T) -> Option<(usize, usize)> {
+    if is_dangling(ptr) {
+        return None;
+    }
+    // SAFETY: `data_offset` only relies on the metadata of `ptr`, which is valid for `T` since
+    // `ptr` was derived from an `Arc<T>` or a `Weak<T>`.
+    let offset = unsafe { data_offset(ptr) };
+    // The counts are the first fields of `ArcInner`, whatever the type of the value.
+    let inner = ptr.wrapping_byte_sub(offset) as *const ArcInner<()>;
+    // SAFETY: just checked that `inner` can be read.
+    ub_checks::can_dereference(inner)
+        .then(|| unsafe { ((*inner).strong.load(Relaxed), (*inner).weak.load(Relaxed)) })
+}
+
 /// A unique owning pointer to an [`ArcInner`] **that does not imply the contents are initialized,**
 /// but will deallocate it (without dropping the value) when dropped.
 ///
@@ -4529,3 +4610,321 @@ unsafe impl<#[may_dangle] T: ?Sized, A: Allocator> Drop for UniqueArc<T, A> {
         unsafe { ptr::drop_in_place(&mut (*self.ptr.as_ptr()).data) };
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::kani;
+
+    use super::*;
+    use crate::arbitrary::any_vec;
+
+    const MAX_LEN: usize = 4;
+
+    /// Arbitrarily creates another strong reference and a weak reference to the allocation of
+    /// `arc`, so that harnesses cover unique, shared and weakly referenced allocations.
+    fn any_sharing<T: ?Sized>(arc: &Arc<T>) -> (Option<Arc<T>>, Option<Weak<T>>) {
+        let strong = kani::any::<bool>().then(|| Arc::clone(arc));
+        let weak = kani::any::<bool>().then(|| Arc::downgrade(arc));
+        (strong, weak)
+    }
+
+    #[kani::proof_for_contract(Arc::<mem::MaybeUninit<u8>>::assume_init)]
+    fn check_assume_init() {
+        let mut arc = Arc::<u8>::new_uninit();
+        let value = kani::any();
+        Arc::get_mut(&mut arc).unwrap().write(value);
+        let arc = unsafe { arc.assume_init() };
+        assert!(arc.is_safe());
+        assert_eq!(*arc, value);
+    }
+
+    #[kani::proof_for_contract(Arc::<[mem::MaybeUninit<u8>]>::assume_init)]
+    #[kani::unwind(5)]
+    fn check_assume_init_slice() {
+        let len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        let mut arc = Arc::<[u8]>::new_uninit_slice(len);
+        for elem in Arc::get_mut(&mut arc).unwrap() {
+            elem.write(kani::any());
+        }
+        let arc = unsafe { arc.assume_init() };
+        assert!(arc.is_safe());
+        assert_eq!(arc.len(), len);
+    }
+
+    #[kani::proof_for_contract(Arc::<u8>::from_raw)]
+    fn check_from_raw() {
+        let arc = Arc::new(kani::any::<u8>());
+        let (_other, _weak) = any_sharing(&arc);
+        let ptr = Arc::into_raw(arc);
+        let arc = unsafe { Arc::from_raw(ptr) };
+        assert!(arc.is_safe());
+    }
+
+    #[kani::proof_for_contract(Arc::<[u8]>::from_raw)]
+    #[kani::unwind(5)]
+    fn check_from_raw_slice() {
+        let arc = Arc::<[u8]>::from(any_vec::<u8, MAX_LEN>());
+        let len = arc.len();
+        let (_other, _weak) = any_sharing(&arc);
+        let ptr = Arc::into_raw(arc);
+        let arc = unsafe { Arc::from_raw(ptr) };
+        assert!(arc.is_safe());
+        assert_eq!(arc.len(), len);
+    }
+
+    #[kani::proof_for_contract(Arc::<u8>::from_raw_in)]
+    fn check_from_raw_in() {
+        let arc = Arc::new(kani::any::<u8>());
+        let (_other, _weak) = any_sharing(&arc);
+        let (ptr, alloc) = Arc::into_raw_with_allocator(arc);
+        let arc = unsafe { Arc::from_raw_in(ptr, alloc) };
+        assert!(arc.is_safe());
+    }
+
+    #[kani::proof_for_contract(Arc::<u8>::increment_strong_count)]
+    fn check_increment_strong_count() {
+        let arc = Arc::new(kani::any::<u8>());
+        let ptr = Arc::as_ptr(&arc);
+        unsafe { Arc::increment_strong_count(ptr) };
+        assert_eq!(Arc::strong_count(&arc), 2);
+        unsafe { Arc::decrement_strong_count(ptr) };
+    }
+
+    #[kani::proof_for_contract(Arc::<u8>::increment_strong_count_in)]
+    fn check_increment_strong_count_in() {
+        let arc = Arc::new(kani::any::<u8>());
+        let ptr = Arc::as_ptr(&arc);
+        unsafe { Arc::increment_strong_count_in(ptr, Global) };
+        assert_eq!(Arc::strong_count(&arc), 2);
+        unsafe { Arc::decrement_strong_count_in(ptr, Global) };
+    }
+
+    #[kani::proof_for_contract(Arc::<u8>::decrement_strong_count)]
+    fn check_decrement_strong_count() {
+        let arc = Arc::new(kani::any::<u8>());
+        let weak = Arc::downgrade(&arc);
+        let ptr = Arc::into_raw(arc);
+        unsafe { Arc::decrement_strong_count(ptr) };
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[kani::proof_for_contract(Arc::<u8>::decrement_strong_count_in)]
+    fn check_decrement_strong_count_in() {
+        let arc = Arc::new(kani::any::<u8>());
+        let other = Arc::clone(&arc);
+        let (ptr, alloc) = Arc::into_raw_with_allocator(other);
+        unsafe { Arc::decrement_strong_count_in(ptr, alloc) };
+        assert_eq!(Arc::strong_count(&arc), 1);
+    }
+
+    // The other pointers are not dereferenced while the returned borrow is alive, and see the
+    // new value afterwards.
+    #[kani::proof_for_contract(Arc::<u8>::get_mut_unchecked)]
+    fn check_get_mut_unchecked() {
+        let mut arc = Arc::new(kani::any::<u8>());
+        let (other, weak) = any_sharing(&arc);
+        let value = kani::any();
+        *unsafe { Arc::get_mut_unchecked(&mut arc) } = value;
+        assert_eq!(*arc, value);
+        assert!(other.is_none_or(|other| *other == value));
+        assert!(weak.is_none_or(|weak| weak.upgrade().is_some_and(|arc| *arc == value)));
+    }
+
+    #[kani::proof_for_contract(Arc::<[u8]>::allocate_for_layout)]
+    fn check_allocate_for_layout() {
+        let len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        let value_layout = Layout::array::<u8>(len).unwrap();
+        let inner = unsafe {
+            Arc::<[u8]>::allocate_for_layout(
+                value_layout,
+                |layout| Global.allocate(layout),
+                |mem| ptr::slice_from_raw_parts_mut(mem, len) as *mut ArcInner<[u8]>,
+            )
+        };
+        let layout = arcinner_layout_for_value_layout(value_layout);
+        unsafe { Global.deallocate(NonNull::new_unchecked(inner.cast()), layout) };
+    }
+
+    // The weak reference handed to `data_fn` cannot be upgraded until the value is initialized,
+    // and then refers to the new allocation.
+    #[kani::proof]
+    fn check_new_cyclic() {
+        let value = kani::any::<u8>();
+        let keep = kani::any::<bool>();
+        let mut kept = None;
+        let arc = Arc::new_cyclic(|weak| {
+            assert!(weak.upgrade().is_none());
+            assert_eq!(weak.strong_count(), 0);
+            kept = keep.then(|| weak.clone());
+            value
+        });
+        assert!(arc.is_safe() && *arc == value);
+        assert_eq!(Arc::strong_count(&arc), 1);
+        assert_eq!(Arc::weak_count(&arc), keep as usize);
+        if let Some(weak) = kept {
+            assert!(weak.is_safe() && Arc::ptr_eq(&weak.upgrade().unwrap(), &arc));
+        }
+    }
+
+    #[kani::proof_for_contract(Arc::<dyn Any + Send + Sync>::downcast_unchecked)]
+    fn check_downcast_unchecked() {
+        let value = kani::any::<u8>();
+        let arc: Arc<dyn Any + Send + Sync> = Arc::new(value);
+        let arc = unsafe { arc.downcast_unchecked::<u8>() };
+        assert!(arc.is_safe());
+        assert_eq!(*arc, value);
+    }
+
+    /// Returns a weak reference to `arc` or a dangling one.
+    fn any_weak<T>(arc: &Arc<T>) -> Weak<T> {
+        if kani::any() { Arc::downgrade(arc) } else { Weak::new() }
+    }
+
+    #[kani::proof_for_contract(Weak::<u8>::from_raw)]
+    fn check_weak_from_raw() {
+        let arc = Arc::new(kani::any::<u8>());
+        let weak = any_weak(&arc);
+        if kani::any() {
+            drop(arc);
+        }
+        let weak = unsafe { Weak::from_raw(weak.into_raw()) };
+        assert!(weak.is_safe());
+    }
+
+    #[kani::proof_for_contract(Weak::<u8>::from_raw_in)]
+    fn check_weak_from_raw_in() {
+        let arc = Arc::new(kani::any::<u8>());
+        let weak = any_weak(&arc);
+        if kani::any() {
+            drop(arc);
+        }
+        let (ptr, alloc) = weak.into_raw_with_allocator();
+        let weak = unsafe { Weak::from_raw_in(ptr, alloc) };
+        assert!(weak.is_safe());
+    }
+
+    /// Checks that `make_mut` makes `arc` unique, moving the value to a new allocation if and only
+    /// if there are other references, without changing what these references point to.
+    fn check_make_mut_of<T: ?Sized + CloneToUninit + PartialEq>(mut arc: Arc<T>) {
+        let (other, weak) = any_sharing(&arc);
+        let old_ptr = Arc::as_ptr(&arc);
+        Arc::make_mut(&mut arc);
+        assert!(arc.is_safe());
+        assert!(Arc::strong_count(&arc) == 1 && Arc::weak_count(&arc) == 0);
+        assert_eq!(ptr::addr_eq(Arc::as_ptr(&arc), old_ptr), other.is_none() && weak.is_none());
+        if let Some(other) = &other {
+            assert!(other.is_safe() && ptr::addr_eq(Arc::as_ptr(other), old_ptr));
+            assert!(**other == *arc);
+        }
+        if let Some(weak) = weak {
+            assert!(weak.is_safe());
+            assert_eq!(weak.upgrade().is_some(), other.is_some());
+        }
+    }
+
+    #[kani::proof]
+    fn check_make_mut() {
+        check_make_mut_of(Arc::new(kani::any::<u8>()));
+    }
+
+    #[kani::proof]
+    fn check_make_mut_zst() {
+        check_make_mut_of(Arc::new(()));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_make_mut_slice() {
+        check_make_mut_of(Arc::<[u8]>::from(any_vec::<u8, MAX_LEN>()));
+    }
+
+    /// Checks that `try_unwrap` returns the value if and only if `arc` is the only strong
+    /// reference, after which the weak references can no longer be upgraded.
+    fn check_try_unwrap_of<T: Clone + PartialEq>(value: T) {
+        let arc = Arc::new(value.clone());
+        let (other, weak) = any_sharing(&arc);
+        match Arc::try_unwrap(arc) {
+            Ok(unwrapped) => {
+                assert!(other.is_none() && unwrapped == value);
+                assert!(weak.is_none_or(|weak| weak.is_safe() && weak.upgrade().is_none()));
+            }
+            Err(arc) => {
+                assert!(other.is_some() && arc.is_safe());
+                assert_eq!(Arc::strong_count(&arc), 2);
+            }
+        }
+    }
+
+    #[kani::proof]
+    fn check_try_unwrap() {
+        check_try_unwrap_of(kani::any::<u8>());
+    }
+
+    #[kani::proof]
+    fn check_try_unwrap_zst() {
+        check_try_unwrap_of(());
+    }
+
+    /// Checks that when two strong references are consumed by `into_inner`, exactly one of them
+    /// returns the value.
+    fn check_into_inner_of<T: Clone + PartialEq>(value: T) {
+        let arc = Arc::new(value.clone());
+        let (other, weak) = any_sharing(&arc);
+        let first = Arc::into_inner(arc);
+        assert_eq!(first.is_some(), other.is_none());
+        let second = other.and_then(Arc::into_inner);
+        assert!(first.or(second).is_some_and(|inner| inner == value));
+        assert!(weak.is_none_or(|weak| weak.is_safe() && weak.upgrade().is_none()));
+    }
+
+    #[kani::proof]
+    fn check_into_inner() {
+        check_into_inner_of(kani::any::<u8>());
+    }
+
+    #[kani::proof]
+    fn check_into_inner_zst() {
+        check_into_inner_of(());
+    }
+
+    /// Checks that a weak reference to `arc` can be upgraded exactly as long as a strong reference
+    /// exists.
+    fn check_upgrade_of<T: ?Sized>(arc: Arc<T>) {
+        let weak = Arc::downgrade(&arc);
+        let other = kani::any::<bool>().then(|| Arc::clone(&arc));
+        assert!(weak.is_safe());
+        let upgraded = weak.upgrade().unwrap();
+        assert!(upgraded.is_safe() && Arc::ptr_eq(&upgraded, &arc));
+        assert_eq!(Arc::strong_count(&arc), 2 + other.is_some() as usize);
+        drop(upgraded);
+        drop(arc);
+        assert_eq!(weak.upgrade().is_some(), other.is_some());
+        drop(other);
+        assert!(weak.is_safe() && weak.upgrade().is_none());
+    }
+
+    #[kani::proof]
+    fn check_upgrade() {
+        check_upgrade_of(Arc::new(kani::any::<u8>()));
+        assert!(Weak::<u8>::new().upgrade().is_none());
+    }
+
+    #[kani::proof]
+    fn check_upgrade_zst() {
+        check_upgrade_of(Arc::new(()));
+        assert!(Weak::<()>::new().upgrade().is_none());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_upgrade_slice() {
+        check_upgrade_of(Arc::<[u8]>::from(any_vec::<u8, MAX_LEN>()));
+    }
+
+    #[kani::proof]
+    fn check_upgrade_dyn() {
+        let arc: Arc<dyn Any + Send + Sync> = Arc::new(kani::any::<u8>());
+        check_upgrade_of(arc);
+    }
+}
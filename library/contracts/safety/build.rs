@@ -1,4 +1,13 @@
 fn main() {
     // We add the configurations here to be checked.
     println!("cargo:rustc-check-cfg=cfg(kani_host)");
+    println!("cargo:rustc-check-cfg=cfg(runtime_contracts)");
+
+    // Proc-macros do not get the target `RUSTFLAGS` when cross-compiling the standard library, so
+    // the runtime contract checks can also be enabled through the environment.
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SAFETY_RUNTIME_CONTRACTS");
+    if std::env::var_os("SAFETY_RUNTIME_CONTRACTS").is_some() {
+        println!("cargo:rustc-cfg=runtime_contracts");
+    }
 }
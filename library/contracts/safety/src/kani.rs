@@ -2,6 +2,8 @@ use proc_macro::TokenStream;
 use quote::{format_ident, quote};
 use syn::{parse_macro_input, ItemFn, Stmt};
 
+use crate::Quantifier;
+
 pub(crate) fn requires(attr: TokenStream, item: TokenStream) -> TokenStream {
     rewrite_attr(attr, item, "requires")
 }
@@ -14,6 +16,14 @@ pub(crate) fn loop_invariant(attr: TokenStream, stmt: TokenStream) -> TokenStrea
     rewrite_stmt_attr(attr, stmt, "loop_invariant")
 }
 
+pub(crate) fn forall(quantifier: Quantifier) -> TokenStream {
+    quote!(::core::kani::forall!(#quantifier)).into()
+}
+
+pub(crate) fn exists(quantifier: Quantifier) -> TokenStream {
+    quote!(::core::kani::exists!(#quantifier)).into()
+}
+
 fn rewrite_stmt_attr(attr: TokenStream, stmt_stream: TokenStream, name: &str) -> TokenStream {
     let args = proc_macro2::TokenStream::from(attr);
     let stmt = parse_macro_input!(stmt_stream as Stmt);
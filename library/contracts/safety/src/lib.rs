@@ -1,12 +1,19 @@
 //! Implement a few placeholders for contract attributes until they get implemented upstream.
 //! Each tool should implement their own version in a separate module of this crate.
 
+use std::collections::HashSet;
+
 use proc_macro::TokenStream;
-use proc_macro_error::proc_macro_error;
+use proc_macro_error::{abort, proc_macro_error};
 use quote::{format_ident, quote, quote_spanned};
 use syn::{
-    parse_macro_input, parse_quote, spanned::Spanned, Data, DataEnum, DeriveInput, Fields,
-    GenericParam, Generics, Ident, Index, ItemStruct,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    parse_macro_input, parse_quote,
+    spanned::Spanned,
+    visit::Visit,
+    Attribute, Data, DataEnum, DeriveInput, Expr, Fields, GenericParam, Generics, Ident, Index,
+    ItemStruct, Path, Token,
 };
 
 #[cfg(kani_host)]
@@ -107,31 +114,62 @@ pub fn invariant(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///    }
 /// }
 /// ```
+///
+/// Additional constraints can be specified with the `#[safety_constraint(...)]` attribute, either
+/// on a struct field or on the type itself. They are checked in addition to the `is_safe` of every
+/// field. In a struct, each named field is bound by reference to a variable with the field's name,
+/// and `self` can be used to access fields of tuple structs. Enums only support type-level
+/// constraints.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(Invariant)]
+/// #[safety_constraint(*width <= *max_width)]
+/// struct Rectangle {
+///     #[safety_constraint(*width != 0)]
+///     width: u32,
+///     max_width: u32,
+/// }
+/// ```
+///
+/// expands to:
+/// ```ignore
+/// impl core::ub_checks::Invariant for Rectangle {
+///   fn is_safe(&self) -> bool {
+///     let width = &self.width;
+///     let max_width = &self.max_width;
+///     self.width.is_safe() && (*width != 0) && self.max_width.is_safe() && (*width <= *max_width)
+///   }
+/// }
+/// ```
+///
+/// A bound `T: Invariant` is added to every type parameter `T` that is used by a field.
 /// For more information on the Invariant trait, see its documentation in core::ub_checks.
 #[proc_macro_error]
-#[proc_macro_derive(Invariant)]
+#[proc_macro_derive(Invariant, attributes(safety_constraint))]
 pub fn derive_invariant(item: TokenStream) -> TokenStream {
     let derive_item = parse_macro_input!(item as DeriveInput);
     let item_name = &derive_item.ident;
-    let safe_body = match derive_item.data {
+    let type_constraints = safety_constraints(&derive_item.attrs);
+    let (safe_body, bindings) = match &derive_item.data {
         Data::Struct(struct_data) => {
-            safe_body(&struct_data.fields)
+            (safe_body(&struct_data.fields), field_bindings(&struct_data.fields))
         },
         Data::Enum(enum_data) => {
             let variant_checks = variant_checks(enum_data, item_name);
 
-            quote! {
+            (quote! {
                 match self {
                     #(#variant_checks),*
                 }
-            }
+            }, quote! {})
         },
         Data::Union(..) => unimplemented!("Attempted to derive Invariant on a union; Invariant can only be derived for structs and enums."),
     };
 
-    // Add a bound `T: Invariant` to every type parameter T.
-    let generics = add_trait_bound_invariant(derive_item.generics);
-    // Generate an expression to sum up the heap size of each field.
+    // Add a bound `T: Invariant` to every type parameter T used by a field.
+    let generics = add_trait_bound_invariant(derive_item.generics.clone(), &derive_item.data);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let expanded = quote! {
@@ -139,7 +177,8 @@ pub fn derive_invariant(item: TokenStream) -> TokenStream {
         #[unstable(feature="invariant", issue="none")]
         impl #impl_generics core::ub_checks::Invariant for #item_name #ty_generics #where_clause {
             fn is_safe(&self) -> bool {
-                #safe_body
+                #bindings
+                (#safe_body) #(&& (#type_constraints))*
             }
         }
     };
@@ -164,25 +203,165 @@ pub fn loop_invariant(attr: TokenStream, stmt_stream: TokenStream) -> TokenStrea
     tool::loop_invariant(attr, stmt_stream)
 }
 
-/// Add a bound `T: Invariant` to every type parameter T.
-fn add_trait_bound_invariant(mut generics: Generics) -> Generics {
+/// Bounded universal quantifier: `forall!(|i in (lower, upper)| predicate)` holds if `predicate`
+/// holds for every `i: usize` such that `lower <= i < upper`.
+///
+/// # Example
+///
+/// ```ignore
+/// #[ensures(|_| forall!(|i in (0, count)| unsafe { *dst.add(i) == val }))]
+/// ```
+///
+/// With Kani, this is lowered to `kani::forall!`. Otherwise, it is lowered to a loop over the
+/// range, so it can be evaluated by the runtime contract checks.
+///
+/// Inside a postcondition, `old(expr)` can be used in the predicate as long as `expr` does not
+/// refer to the quantified variable.
+#[proc_macro_error]
+#[proc_macro]
+pub fn forall(input: TokenStream) -> TokenStream {
+    let quantifier = parse_macro_input!(input as Quantifier);
+    tool::forall(quantifier)
+}
+
+/// Bounded existential quantifier: `exists!(|i in (lower, upper)| predicate)` holds if
+/// `predicate` holds for some `i: usize` such that `lower <= i < upper`.
+///
+/// See [`forall!`] for how it is lowered.
+#[proc_macro_error]
+#[proc_macro]
+pub fn exists(input: TokenStream) -> TokenStream {
+    let quantifier = parse_macro_input!(input as Quantifier);
+    tool::exists(quantifier)
+}
+
+/// The arguments of a quantifier: `|var in (lower, upper)| predicate`.
+struct Quantifier {
+    var: Ident,
+    lower: Expr,
+    upper: Expr,
+    predicate: Expr,
+}
+
+impl Parse for Quantifier {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![|]>()?;
+        let var = input.parse()?;
+        input.parse::<Token![in]>()?;
+        let bounds;
+        parenthesized!(bounds in input);
+        let lower = bounds.parse()?;
+        bounds.parse::<Token![,]>()?;
+        let upper = bounds.parse()?;
+        bounds.parse::<Option<Token![,]>>()?;
+        input.parse::<Token![|]>()?;
+        let predicate = input.parse()?;
+        Ok(Quantifier {
+            var,
+            lower,
+            upper,
+            predicate,
+        })
+    }
+}
+
+impl quote::ToTokens for Quantifier {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Quantifier {
+            var,
+            lower,
+            upper,
+            predicate,
+        } = self;
+        tokens.extend(quote!(|#var in (#lower, #upper)| #predicate));
+    }
+}
+
+/// Add a bound `T: Invariant` to every type parameter T that is used by a field of `data`.
+fn add_trait_bound_invariant(mut generics: Generics, data: &Data) -> Generics {
+    let mut used = UsedTypeParams {
+        params: generics
+            .type_params()
+            .map(|param| param.ident.clone())
+            .collect(),
+        used: HashSet::new(),
+    };
+    match data {
+        Data::Struct(struct_data) => used.visit_fields(&struct_data.fields),
+        Data::Enum(enum_data) => enum_data
+            .variants
+            .iter()
+            .for_each(|variant| used.visit_fields(&variant.fields)),
+        Data::Union(union_data) => used.visit_fields_named(&union_data.fields),
+    }
     generics.params.iter_mut().for_each(|param| {
         if let GenericParam::Type(type_param) = param {
-            type_param
-                .bounds
-                .push(parse_quote!(core::ub_checks::Invariant));
+            if used.used.contains(&type_param.ident) {
+                type_param
+                    .bounds
+                    .push(parse_quote!(core::ub_checks::Invariant));
+            }
         }
     });
     generics
 }
 
+/// Collect the type parameters that are mentioned by field types.
+struct UsedTypeParams {
+    params: HashSet<Ident>,
+    used: HashSet<Ident>,
+}
+
+impl<'ast> Visit<'ast> for UsedTypeParams {
+    fn visit_path(&mut self, path: &'ast Path) {
+        if let Some(first) = path.segments.first() {
+            if path.leading_colon.is_none() && self.params.contains(&first.ident) {
+                self.used.insert(first.ident.clone());
+            }
+        }
+        syn::visit::visit_path(self, path);
+    }
+}
+
+/// Parse the expressions of every `#[safety_constraint(...)]` attribute in `attrs`.
+fn safety_constraints(attrs: &[Attribute]) -> Vec<Expr> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("safety_constraint"))
+        .map(|attr| match attr.parse_args::<Expr>() {
+            Ok(expr) => expr,
+            Err(err) => abort!(attr, "invalid `safety_constraint`: {}", err),
+        })
+        .collect()
+}
+
+/// Bind every named field to a variable with the same name, so that safety constraints can refer
+/// to them.
+fn field_bindings(fields: &Fields) -> proc_macro2::TokenStream {
+    let Fields::Named(fields) = fields else {
+        return quote! {};
+    };
+    let names = fields.named.iter().map(|field| &field.ident);
+    quote! {
+        #(
+            #[allow(unused_variables)]
+            let #names = &self.#names;
+        )*
+    }
+}
+
 /// Generate safety checks for each variant of an enum
-fn variant_checks(enum_data: DataEnum, item_name: &Ident) -> Vec<proc_macro2::TokenStream> {
+fn variant_checks(enum_data: &DataEnum, item_name: &Ident) -> Vec<proc_macro2::TokenStream> {
     enum_data
         .variants
         .iter()
         .map(|variant| {
             let variant_name = &variant.ident;
+            if let Some(field) = variant.fields.iter().find(|field| {
+                !safety_constraints(&field.attrs).is_empty()
+            }) {
+                abort!(field, "`safety_constraint` is not supported on enum fields; add it to the enum instead");
+            }
             match &variant.fields {
                 Fields::Unnamed(fields) => {
                     let field_names: Vec<_> = fields
@@ -215,7 +394,7 @@ fn variant_checks(enum_data: DataEnum, item_name: &Ident) -> Vec<proc_macro2::To
 }
 
 /// Generate the body for the `is_safe` method.
-/// For each field of the type, enforce that it is safe.
+/// For each field of the type, enforce that it is safe and that its safety constraints hold.
 fn safe_body(fields: &Fields) -> proc_macro2::TokenStream {
     match fields {
         Fields::Named(ref fields) => {
@@ -224,8 +403,9 @@ fn safe_body(fields: &Fields) -> proc_macro2::TokenStream {
                 .iter()
                 .map(|field| {
                     let name = &field.ident;
+                    let constraints = safety_constraints(&field.attrs);
                     quote_spanned! {field.span()=>
-                        self.#name.is_safe()
+                        self.#name.is_safe() #(&& (#constraints))*
                     }
                 })
                 .collect();
@@ -242,8 +422,9 @@ fn safe_body(fields: &Fields) -> proc_macro2::TokenStream {
                 .enumerate()
                 .map(|(idx, field)| {
                     let field_idx = Index::from(idx);
+                    let constraints = safety_constraints(&field.attrs);
                     quote_spanned! {field.span()=>
-                        self.#field_idx.is_safe()
+                        self.#field_idx.is_safe() #(&& (#constraints))*
                     }
                 })
                 .collect();
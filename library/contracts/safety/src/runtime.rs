@@ -1,22 +1,337 @@
+//! Runtime expansion of the contract attributes.
+//!
+//! By default, contracts are no-ops at runtime. When the `safety` crate is built with the
+//! `runtime_contracts` configuration (either with `--cfg runtime_contracts` or by setting the
+//! `SAFETY_RUNTIME_CONTRACTS` environment variable), the contracts are turned into checks that
+//! behave like `assert_unsafe_precondition!(check_library_ub, ...)`: they only run when UB checks
+//! are enabled for the caller (e.g. debug builds and Miri), they are skipped during const-eval,
+//! and a violation results in a non-unwinding panic.
+//!
+//! The checks are emitted as calls to `core::ub_checks::check_contract` and
+//! `core::ub_checks::check_contract_ensures`, and the `old` snapshots are taken with
+//! `core::ub_checks::contract_old`, so the crate using the contracts must enable the
+//! `ub_checks` feature. As with Kani, the conditions may call unsafe functions: they are evaluated
+//! in an `unsafe` block, which requires the `stmt_expr_attributes` feature.
+//!
+//! Quantifiers are lowered to loops over their range, whether or not the contracts are checked.
 use proc_macro::TokenStream;
+use proc_macro2::Span;
+use proc_macro_error::abort;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
+use syn::{
+    parse_macro_input, parse_quote, parse_quote_spanned, Expr, ExprClosure, ExprMacro, Item,
+    ItemFn, Stmt,
+};
 
-/// For now, runtime requires is a no-op.
+use crate::Quantifier;
+
+/// At runtime, `requires` checks the precondition before the function body executes.
+pub(crate) fn requires(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !cfg!(runtime_contracts) {
+        return item;
+    }
+    let cond = proc_macro2::TokenStream::from(attr);
+    let mut fn_item = parse_macro_input!(item as ItemFn);
+    let msg = contract_message(&fn_item, "requires", &cond);
+    let unsafe_cond = unsafe_condition(&cond);
+    let check: Stmt = parse_quote!(
+        ::core::ub_checks::check_contract(|| #unsafe_cond, #msg);
+    );
+    fn_item.block.stmts.insert(0, check);
+    quote!(#fn_item).into()
+}
+
+/// At runtime, `ensures` checks the postcondition on every value returned by the function,
+/// including early returns and the ones produced by the `?` operator.
 ///
-/// TODO: At runtime the `requires` should become an assert unsafe precondition.
-pub(crate) fn requires(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    item
+/// Any `old(expr)` in the postcondition is evaluated when entering the function, right after the
+/// preconditions are checked, and its value is used when checking the postcondition. When `expr`
+/// is a place, such as `old(self)` or `old(self.len)`, its value is copied rather than moved out,
+/// so that the body can still use it, which requires the place to be `Copy`. Any other expression,
+/// such as `old(v.len())` or `old(v.clone())`, is captured by value.
+pub(crate) fn ensures(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !cfg!(runtime_contracts) {
+        return item;
+    }
+    let attr_tokens = proc_macro2::TokenStream::from(attr.clone());
+    let mut cond = parse_macro_input!(attr as ExprClosure);
+    let mut fn_item = parse_macro_input!(item as ItemFn);
+    let msg = contract_message(&fn_item, "ensures", &attr_tokens);
+
+    let mut old_captures = OldCaptures::default();
+    old_captures.visit_expr_mut(&mut cond.body);
+    let body = &cond.body;
+    cond.body = Box::new(unsafe_condition(&quote!(#body)));
+    // The postcondition does not have to mention every part of the returned value.
+    cond.attrs.push(parse_quote!(#[allow(unused_variables)]));
+
+    let mut returns = WrapReturns {
+        cond: &cond,
+        msg: &msg,
+        in_try_block: false,
+    };
+    returns.visit_block_mut(&mut fn_item.block);
+
+    // Keep the precondition checks first, so the `old` expressions may rely on them.
+    let mut preconditions = std::mem::take(&mut fn_item.block.stmts);
+    let body = preconditions.split_off(
+        preconditions
+            .iter()
+            .take_while(|s| is_requires_check(s))
+            .count(),
+    );
+    let (old_idents, old_exprs): (Vec<_>, Vec<_>) = old_captures.captures.into_iter().unzip();
+    *fn_item.block = parse_quote!({
+        #(#preconditions)*
+        #(let #old_idents = #old_exprs;)*
+        // The body may diverge, e.g. when it only leaves through `return` in a loop.
+        #[allow(unreachable_code)]
+        ::core::ub_checks::check_contract_ensures({ #(#body)* }, #cond, #msg)
+    });
+    quote!(#fn_item).into()
+}
+
+/// At runtime, `loop_invariant` checks the invariant every time the loop head is reached.
+///
+/// For `while` loops, the check runs before every evaluation of the loop condition, including the
+/// last one. For `while let`, `for` and `loop`, it runs at the start of every iteration.
+pub(crate) fn loop_invariant(attr: TokenStream, stmt_stream: TokenStream) -> TokenStream {
+    if !cfg!(runtime_contracts) {
+        return stmt_stream;
+    }
+    let inv = proc_macro2::TokenStream::from(attr);
+    let mut stmt = parse_macro_input!(stmt_stream as Stmt);
+    let msg = format!(": loop_invariant `{inv}`");
+    let unsafe_inv = unsafe_condition(&inv);
+    let check: Stmt = parse_quote!(
+        ::core::ub_checks::check_contract(|| #unsafe_inv, concat!("loop invariant violated: ", module_path!(), #msg));
+    );
+    let expr = match &mut stmt {
+        Stmt::Expr(expr, _) => expr,
+        _ => abort!(stmt, "`loop_invariant` can only be applied to loops"),
+    };
+    match expr {
+        Expr::While(while_loop) if !matches!(*while_loop.cond, Expr::Let(_)) => {
+            let cond = &while_loop.cond;
+            *while_loop.cond = parse_quote!({ #check #cond });
+        }
+        Expr::While(while_loop) => while_loop.body.stmts.insert(0, check),
+        Expr::ForLoop(for_loop) => for_loop.body.stmts.insert(0, check),
+        Expr::Loop(loop_expr) => loop_expr.body.stmts.insert(0, check),
+        _ => abort!(expr, "`loop_invariant` can only be applied to loops"),
+    }
+    quote!(#stmt).into()
+}
+
+/// `forall!` evaluates the predicate on each value of the range, and stops at the first one for
+/// which it does not hold.
+pub(crate) fn forall(quantifier: Quantifier) -> TokenStream {
+    quantifier_loop(quantifier, true)
+}
+
+/// `exists!` evaluates the predicate on each value of the range, and stops at the first one for
+/// which it holds.
+pub(crate) fn exists(quantifier: Quantifier) -> TokenStream {
+    quantifier_loop(quantifier, false)
+}
+
+/// Lower a quantifier to a `while` loop, which is allowed in `const` contexts.
+fn quantifier_loop(quantifier: Quantifier, is_forall: bool) -> TokenStream {
+    let Quantifier {
+        var,
+        lower,
+        upper,
+        predicate,
+    } = quantifier;
+    // Use hygienic names, so they cannot clash with the variables used in the quantifier.
+    let index = format_ident!("index", span = Span::mixed_site());
+    let upper_bound = format_ident!("upper_bound", span = Span::mixed_site());
+    let result = format_ident!("result", span = Span::mixed_site());
+    let stop = if is_forall {
+        quote!(!(#predicate))
+    } else {
+        quote!(#predicate)
+    };
+    quote!({
+        let mut #index: usize = #lower;
+        let #upper_bound: usize = #upper;
+        let mut #result = #is_forall;
+        while #index < #upper_bound {
+            let #var: usize = #index;
+            if #stop {
+                #result = !#is_forall;
+                break;
+            }
+            #index += 1;
+        }
+        #result
+    })
+    .into()
 }
 
-/// For now, runtime ensures is a no-op.
+/// Build the message reported when the contract `kind` with condition `cond` is violated.
+fn contract_message(fn_item: &ItemFn, kind: &str, cond: &proc_macro2::TokenStream) -> Expr {
+    let msg = format!(
+        "::{}: {kind} `{cond}`\n\nThis indicates a bug in the program. \
+        This contract check is optional, and cannot be relied on for safety.",
+        fn_item.sig.ident
+    );
+    let prefix = if kind == "ensures" {
+        "postcondition violated: "
+    } else {
+        "unsafe precondition(s) violated: "
+    };
+    parse_quote!(concat!(#prefix, module_path!(), #msg))
+}
+
+/// Evaluate the condition `cond` in an `unsafe` block, since contracts may call unsafe functions.
 ///
-/// TODO: At runtime the `ensures` should become an assert as well.
-pub(crate) fn ensures(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    item
+/// Conditions are specifications, so the lints about suspicious or redundant code are silenced.
+fn unsafe_condition(cond: &proc_macro2::TokenStream) -> Expr {
+    parse_quote!(
+        #[allow(
+            unused_unsafe,
+            unused_parens,
+            ambiguous_wide_pointer_comparisons,
+            useless_ptr_null_checks
+        )]
+        unsafe { #cond }
+    )
+}
+
+/// Whether `stmt` is a precondition check emitted by [`requires`].
+fn is_requires_check(stmt: &Stmt) -> bool {
+    let Stmt::Expr(Expr::Call(call), Some(_)) = stmt else {
+        return false;
+    };
+    let Expr::Path(func) = &*call.func else {
+        return false;
+    };
+    let path = &func.path;
+    path.leading_colon.is_some()
+        && path
+            .segments
+            .iter()
+            .map(|seg| &seg.ident)
+            .eq(["core", "ub_checks", "check_contract"])
+}
+
+/// Whether `expr` denotes a place, which evaluating it by value would move out of.
+fn is_place(expr: &Expr) -> bool {
+    match expr {
+        Expr::Path(_) | Expr::Field(_) | Expr::Index(_) => true,
+        Expr::Unary(unary) => matches!(unary.op, syn::UnOp::Deref(_)),
+        Expr::Paren(paren) => is_place(&paren.expr),
+        _ => false,
+    }
+}
+
+/// Replace every `old(expr)` call by a fresh variable, and collect the expressions that must be
+/// evaluated on function entry.
+#[derive(Default)]
+struct OldCaptures {
+    captures: Vec<(syn::Ident, Expr)>,
 }
 
-/// For now, runtime loop_invariant is a no-op.
+impl VisitMut for OldCaptures {
+    fn visit_expr_macro_mut(&mut self, mac: &mut ExprMacro) {
+        // Look for `old` inside the quantifiers, since they are usually written in postconditions.
+        let is_quantifier = mac
+            .mac
+            .path
+            .segments
+            .last()
+            .is_some_and(|seg| seg.ident == "forall" || seg.ident == "exists");
+        if !is_quantifier {
+            return;
+        }
+        if let Ok(mut quantifier) = mac.mac.parse_body::<Quantifier>() {
+            self.visit_expr_mut(&mut quantifier.lower);
+            self.visit_expr_mut(&mut quantifier.upper);
+            self.visit_expr_mut(&mut quantifier.predicate);
+            mac.mac.tokens = quote!(#quantifier);
+        }
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let Expr::Call(call) = expr {
+            if let Expr::Path(func) = &*call.func {
+                if func.path.is_ident("old") && call.args.len() == 1 {
+                    let ident = format_ident!("__contract_old_{}", self.captures.len());
+                    let mut captured = call.args.pop().unwrap().into_value();
+                    if is_place(&captured) {
+                        // Copy the place instead of moving out of it, and point a missing `Copy`
+                        // bound at the captured expression.
+                        captured = parse_quote_spanned!(captured.span()=>
+                            ::core::ub_checks::contract_old(&(#captured))
+                        );
+                    }
+                    self.captures.push((ident.clone(), captured));
+                    *expr = parse_quote!(#ident);
+                    return;
+                }
+            }
+        }
+        syn::visit_mut::visit_expr_mut(self, expr);
+    }
+}
+
+/// Check the postcondition on every exit point of a function body.
 ///
-/// TODO: At runtime the `loop_invariant` should become an assert as well.
-pub(crate) fn loop_invariant(_attr: TokenStream, stmt_stream: TokenStream) -> TokenStream {
-    stmt_stream
+/// Closures, async blocks and nested items are left alone, since their `return` and `?` do not
+/// leave the function being checked.
+struct WrapReturns<'a> {
+    cond: &'a ExprClosure,
+    msg: &'a Expr,
+    /// `?` inside a `try` block does not return from the function.
+    in_try_block: bool,
+}
+
+impl VisitMut for WrapReturns<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        let (cond, msg) = (self.cond, self.msg);
+        match expr {
+            Expr::Closure(_) | Expr::Async(_) | Expr::Const(_) => {}
+            Expr::TryBlock(try_block) => {
+                let in_try_block = std::mem::replace(&mut self.in_try_block, true);
+                self.visit_block_mut(&mut try_block.block);
+                self.in_try_block = in_try_block;
+            }
+            Expr::Return(ret) => {
+                syn::visit_mut::visit_expr_return_mut(self, ret);
+                let value = ret
+                    .expr
+                    .take()
+                    .unwrap_or_else(|| Box::new(parse_quote!(())));
+                ret.expr = Some(parse_quote!(
+                    ::core::ub_checks::check_contract_ensures(#value, #cond, #msg)
+                ));
+            }
+            Expr::Try(try_expr) if !self.in_try_block => {
+                syn::visit_mut::visit_expr_try_mut(self, try_expr);
+                let value = &try_expr.expr;
+                *expr = parse_quote!(
+                    match ::core::ops::Try::branch(#value) {
+                        ::core::ops::ControlFlow::Continue(__contract_val) => __contract_val,
+                        ::core::ops::ControlFlow::Break(__contract_residual) => {
+                            return ::core::ub_checks::check_contract_ensures(
+                                ::core::ops::FromResidual::from_residual(__contract_residual),
+                                #cond,
+                                #msg,
+                            );
+                        }
+                    }
+                );
+            }
+            _ => syn::visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_expr_macro_mut(&mut self, _: &mut ExprMacro) {
+        // We cannot see through macro invocations.
+    }
+
+    fn visit_item_mut(&mut self, _: &mut Item) {}
 }
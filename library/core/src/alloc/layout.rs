@@ -6,7 +6,8 @@
 
 use safety::{Invariant, ensures, requires};
 
-#[cfg(kani)]
+// Used only for contract verification.
+#[allow(unused_imports)]
 use crate::cmp;
 use crate::error::Error;
 use crate::intrinsics::{unchecked_add, unchecked_mul, unchecked_sub};
@@ -49,6 +50,7 @@ const fn size_align<T>() -> (usize, usize) {
 #[derive(Invariant)]
 pub struct Layout {
     // size of the requested block of memory, measured in bytes.
+    #[safety_constraint(*size <= Layout::max_size_for_align(*align))]
     size: usize,
 
     // alignment of the requested block of memory, measured in bytes.
@@ -656,6 +658,12 @@ mod verify {
         assert!(layout.align().is_power_of_two());
     }
 
+    #[kani::proof]
+    pub fn check_invariant() {
+        let layout = kani::any::<Layout>();
+        assert!(layout.is_safe());
+    }
+
     // pub const fn new<T>() -> Self
     #[kani::proof_for_contract(Layout::new)]
     pub fn check_new_i32() {
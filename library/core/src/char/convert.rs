@@ -291,3 +291,35 @@ pub(super) const fn from_digit(num: u32, radix: u32) -> Option<char> {
         None
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::ub_checks::Invariant;
+
+    #[kani::proof_for_contract(from_u32_unchecked)]
+    fn check_from_u32_unchecked() {
+        let i: u32 = kani::any();
+        let c = unsafe { from_u32_unchecked(i) };
+        assert!(c.is_safe());
+    }
+
+    #[kani::proof]
+    fn check_char_from_u32_unchecked() {
+        let i: u32 = kani::any_where(|i| from_u32(*i).is_some());
+        let c = unsafe { char::from_u32_unchecked(i) };
+        assert_eq!(Some(c), from_u32(i));
+        assert_eq!(u32::from(c), i);
+    }
+
+    #[kani::proof]
+    fn check_char_try_from_u32() {
+        let i: u32 = kani::any();
+        let is_scalar_value = i < 0xD800 || (0xE000..=char::MAX as u32).contains(&i);
+        match char_try_from_u32(i) {
+            Ok(c) => assert!(is_scalar_value && c as u32 == i),
+            Err(_) => assert!(!is_scalar_value),
+        }
+    }
+}
@@ -217,7 +217,7 @@ impl Invariant for &CStr {
 }
 
 // Helper function
-#[cfg(kani)]
+#[allow(dead_code)] // Only used by contracts.
 #[requires(!ptr.is_null())]
 fn is_null_terminated(ptr: *const c_char) -> bool {
     let mut next = ptr;
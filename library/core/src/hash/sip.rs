@@ -2,7 +2,12 @@
 
 #![allow(deprecated)] // the types in this module are deprecated
 
+use safety::{ensures, requires};
+
+#[cfg(kani)]
+use crate::kani;
 use crate::marker::PhantomData;
+use crate::ub_checks::Invariant;
 use crate::{cmp, ptr};
 
 /// An implementation of SipHash 1-3.
@@ -57,6 +62,14 @@ struct Hasher<S: Sip> {
     _marker: PhantomData<S>,
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<S: Sip> Invariant for Hasher<S> {
+    /// At most 7 bytes are buffered in `tail`, and its unused high bytes are zero.
+    fn is_safe(&self) -> bool {
+        self.ntail < 8 && self.tail >> (8 * self.ntail) == 0
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 #[repr(C)]
 struct State {
@@ -117,6 +130,8 @@ macro_rules! load_int_le {
 /// Safety: this performs unchecked indexing of `buf` at `start..start+len`, so
 /// that must be in-bounds.
 #[inline]
+#[requires(len < 8 && start <= buf.len() && len <= buf.len() - start)]
+#[ensures(|out| *out == buf[start..start + len].iter().rev().fold(0, |acc, &b| (acc << 8) | b as u64))]
 unsafe fn u8to64_le(buf: &[u8], start: usize, len: usize) -> u64 {
     debug_assert!(len < 8);
     let mut i = 0; // current byte index (from LSB) in the output u64
@@ -387,3 +402,140 @@ impl Sip for Sip24Rounds {
         compress!(state);
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::hash::Hasher as _;
+
+    const MAX_LEN: usize = 20;
+
+    /// Returns a hasher with arbitrary keys that has already processed an arbitrary prefix of
+    /// fewer than 8 bytes, so that its tail buffer may be partially filled.
+    fn any_hasher<S: Sip>() -> Hasher<S> {
+        let mut hasher = Hasher::<S>::new_with_keys(kani::any(), kani::any());
+        let prefix: [u8; 7] = kani::any();
+        hasher.write(kani::slice::any_slice_of_array(&prefix));
+        hasher
+    }
+
+    // `load_int_le!` cannot carry a contract, so it is checked through these functions.
+
+    #[requires(i <= buf.len() && 2 <= buf.len() - i)]
+    #[ensures(|out| out.to_le_bytes() == buf[i..i + 2])]
+    unsafe fn load_u16_le(buf: &[u8], i: usize) -> u16 {
+        unsafe { load_int_le!(buf, i, u16) }
+    }
+
+    #[requires(i <= buf.len() && 4 <= buf.len() - i)]
+    #[ensures(|out| out.to_le_bytes() == buf[i..i + 4])]
+    unsafe fn load_u32_le(buf: &[u8], i: usize) -> u32 {
+        unsafe { load_int_le!(buf, i, u32) }
+    }
+
+    #[requires(i <= buf.len() && 8 <= buf.len() - i)]
+    #[ensures(|out| out.to_le_bytes() == buf[i..i + 8])]
+    unsafe fn load_u64_le(buf: &[u8], i: usize) -> u64 {
+        unsafe { load_int_le!(buf, i, u64) }
+    }
+
+    #[kani::proof_for_contract(load_u16_le)]
+    fn check_load_int_le_u16() {
+        let bytes: [u8; 16] = kani::any();
+        let buf = kani::slice::any_slice_of_array(&bytes);
+        unsafe { load_u16_le(buf, kani::any()) };
+    }
+
+    #[kani::proof_for_contract(load_u32_le)]
+    fn check_load_int_le_u32() {
+        let bytes: [u8; 16] = kani::any();
+        let buf = kani::slice::any_slice_of_array(&bytes);
+        unsafe { load_u32_le(buf, kani::any()) };
+    }
+
+    #[kani::proof_for_contract(load_u64_le)]
+    fn check_load_int_le_u64() {
+        let bytes: [u8; 16] = kani::any();
+        let buf = kani::slice::any_slice_of_array(&bytes);
+        unsafe { load_u64_le(buf, kani::any()) };
+    }
+
+    #[kani::proof_for_contract(u8to64_le)]
+    #[kani::unwind(8)]
+    fn check_u8to64_le() {
+        let bytes: [u8; 16] = kani::any();
+        let buf = kani::slice::any_slice_of_array(&bytes);
+        unsafe { u8to64_le(buf, kani::any(), kani::any()) };
+    }
+
+    /// `write` only reads within `msg`, whatever its length and the number of buffered bytes.
+    fn check_write<S: Sip>() {
+        let mut hasher = any_hasher::<S>();
+        let length = hasher.length;
+        let bytes: [u8; MAX_LEN] = kani::any();
+        let msg = kani::slice::any_slice_of_array(&bytes);
+        hasher.write(msg);
+        assert!(hasher.is_safe());
+        assert_eq!(hasher.length, length + msg.len());
+    }
+
+    /// Writing a message in two parts yields the same state as writing it at once.
+    fn check_write_split<S: Sip>() {
+        let mut whole = any_hasher::<S>();
+        let mut split = whole.clone();
+        let bytes: [u8; MAX_LEN] = kani::any();
+        let msg = kani::slice::any_slice_of_array(&bytes);
+        let (head, rest) = msg.split_at(kani::any_where(|mid: &usize| *mid <= msg.len()));
+        whole.write(msg);
+        split.write(head);
+        split.write(rest);
+        assert_eq!(split.length, whole.length);
+        assert_eq!(split.ntail, whole.ntail);
+        assert_eq!(split.tail, whole.tail);
+        assert_eq!(split.finish(), whole.finish());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(4)]
+    #[kani::solver(kissat)]
+    fn check_write_sip13() {
+        check_write::<Sip13Rounds>();
+    }
+
+    #[kani::proof]
+    #[kani::unwind(4)]
+    #[kani::solver(kissat)]
+    fn check_write_sip24() {
+        check_write::<Sip24Rounds>();
+    }
+
+    #[kani::proof]
+    #[kani::unwind(4)]
+    #[kani::solver(kissat)]
+    fn check_write_split_sip13() {
+        check_write_split::<Sip13Rounds>();
+    }
+
+    #[kani::proof]
+    #[kani::unwind(4)]
+    #[kani::solver(kissat)]
+    fn check_write_split_sip24() {
+        check_write_split::<Sip24Rounds>();
+    }
+
+    #[kani::proof]
+    #[kani::unwind(4)]
+    #[kani::solver(kissat)]
+    fn check_sip_hasher13_write_split() {
+        let mut whole = SipHasher13::new_with_keys(kani::any(), kani::any());
+        let mut split = whole.clone();
+        let bytes: [u8; MAX_LEN] = kani::any();
+        let msg = kani::slice::any_slice_of_array(&bytes);
+        let (head, rest) = msg.split_at(kani::any_where(|mid: &usize| *mid <= msg.len()));
+        whole.write(msg);
+        split.write(head);
+        split.write(rest);
+        assert_eq!(split.finish(), whole.finish());
+    }
+}
@@ -60,7 +60,8 @@ use safety::{ensures, requires};
 use crate::kani;
 use crate::marker::{ConstParamTy, DiscriminantKind, Tuple};
 use crate::ptr;
-#[cfg(kani)]
+// Used only for contract verification.
+#[allow(unused_imports)]
 use crate::ub_checks;
 
 mod bounds;
@@ -2777,14 +2778,9 @@ pub const fn ptr_metadata<P: ptr::Pointee<Metadata = M> + ?Sized, M>(ptr: *const
 #[rustc_nounwind]
 #[rustc_intrinsic]
 // Copy is "untyped".
-// TODO: we can no longer do this given https://github.com/model-checking/kani/issues/3325 (this
-// function used to have a dummy body, but no longer has)
-// #[cfg_attr(kani, kani::modifies(crate::ptr::slice_from_raw_parts(dst, count)))]
-// #[requires(!count.overflowing_mul(size_of::<T>()).1
-//   && ub_checks::can_dereference(core::ptr::slice_from_raw_parts(src as *const crate::mem::MaybeUninit<T>, count))
-//   && ub_checks::can_write(core::ptr::slice_from_raw_parts_mut(dst, count))
-//   && ub_checks::maybe_is_nonoverlapping(src as *const (), dst as *const (), size_of::<T>(), count))]
-// #[ensures(|_| { check_copy_untyped(src, dst, count)})]
+// TODO: we can no longer put the contract here given
+// https://github.com/model-checking/kani/issues/3325 (this function used to have a dummy body, but
+// no longer has), so it is stated and verified on `verify::copy_nonoverlapping_wrapper` instead.
 pub const unsafe fn copy_nonoverlapping<T>(src: *const T, dst: *mut T, count: usize);
 
 /// This is an accidentally-stable alias to [`ptr::copy`]; use that instead.
@@ -2796,13 +2792,9 @@ pub const unsafe fn copy_nonoverlapping<T>(src: *const T, dst: *mut T, count: us
 #[rustc_const_stable(feature = "const_intrinsic_copy", since = "1.83.0")]
 #[rustc_nounwind]
 #[rustc_intrinsic]
-// TODO: we can no longer do this given https://github.com/model-checking/kani/issues/3325 (this
-// function used to have a dummy body, but no longer has)
-// #[requires(!count.overflowing_mul(size_of::<T>()).1
-//   && ub_checks::can_dereference(core::ptr::slice_from_raw_parts(src as *const crate::mem::MaybeUninit<T>, count))
-//   && ub_checks::can_write(core::ptr::slice_from_raw_parts_mut(dst, count)))]
-// #[ensures(|_| { check_copy_untyped(src, dst, count) })]
-// #[cfg_attr(kani, kani::modifies(crate::ptr::slice_from_raw_parts(dst, count)))]
+// TODO: we can no longer put the contract here given
+// https://github.com/model-checking/kani/issues/3325 (this function used to have a dummy body, but
+// no longer has), so it is stated and verified on `verify::copy_wrapper` instead.
 pub const unsafe fn copy<T>(src: *const T, dst: *mut T, count: usize);
 
 /// This is an accidentally-stable alias to [`ptr::write_bytes`]; use that instead.
@@ -3163,36 +3155,6 @@ pub const unsafe fn copysignf64(x: f64, y: f64) -> f64;
 #[rustc_intrinsic]
 pub const unsafe fn copysignf128(x: f128, y: f128) -> f128;
 
-/// Return whether the initialization state is preserved.
-///
-/// For untyped copy, done via `copy` and `copy_nonoverlapping`, the copies of non-initialized
-/// bytes (such as padding bytes) should result in a non-initialized copy, while copies of
-/// initialized bytes result in initialized bytes.
-///
-/// It is UB to read the uninitialized bytes, so we cannot compare their values only their
-/// initialization state.
-///
-/// This is used for contracts only.
-///
-/// FIXME: Change this once we add support to quantifiers.
-#[allow(dead_code)]
-#[allow(unused_variables)]
-fn check_copy_untyped<T>(src: *const T, dst: *mut T, count: usize) -> bool {
-    #[cfg(kani)]
-    if count > 0 {
-        let byte = kani::any_where(|sz: &usize| *sz < size_of::<T>());
-        let elem = kani::any_where(|val: &usize| *val < count);
-        let src_data = src as *const u8;
-        let dst_data = unsafe { dst.add(elem) } as *const u8;
-        ub_checks::can_dereference(unsafe { src_data.add(byte) })
-            == ub_checks::can_dereference(unsafe { dst_data.add(byte) })
-    } else {
-        true
-    }
-    #[cfg(not(kani))]
-    false
-}
-
 /// Inform Miri that a given pointer definitely has a certain alignment.
 #[cfg(miri)]
 #[rustc_allow_const_fn_unstable(const_eval_select)]
@@ -3223,6 +3185,7 @@ mod verify {
     use core::mem::MaybeUninit;
 
     use kani::{AllocationStatus, Arbitrary, ArbitraryPointer, PointerGenerator};
+    use safety::forall;
 
     use super::*;
     use crate::kani;
@@ -3244,33 +3207,85 @@ mod verify {
         });
     }
 
-    // #[kani::proof_for_contract(copy)]
-    // fn check_copy() {
-    //     run_with_arbitrary_ptrs::<char>(|src, dst| unsafe { copy(src, dst, kani::any()) });
-    // }
-
-    // #[kani::proof_for_contract(copy_nonoverlapping)]
-    // fn check_copy_nonoverlapping() {
-    //     // Note: cannot use `ArbitraryPointer` here.
-    //     // The `ArbitraryPtr` will arbitrarily initialize memory by indirectly invoking
-    //     // `copy_nonoverlapping`.
-    //     // Kani contract checking would fail due to existing restriction on calls to
-    //     // the function under verification.
-    //     let gen_any_ptr = |buf: &mut [MaybeUninit<char>; 100]| -> *mut char {
-    //         let base = buf.as_mut_ptr() as *mut u8;
-    //         base.wrapping_add(kani::any_where(|offset: &usize| *offset < 400)) as *mut char
-    //     };
-    //     let mut buffer1 = [MaybeUninit::<char>::uninit(); 100];
-    //     for i in 0..100 {
-    //         if kani::any() {
-    //             buffer1[i] = MaybeUninit::new(kani::any());
-    //         }
-    //     }
-    //     let mut buffer2 = [MaybeUninit::<char>::uninit(); 100];
-    //     let src = gen_any_ptr(&mut buffer1);
-    //     let dst = if kani::any() { gen_any_ptr(&mut buffer2) } else { gen_any_ptr(&mut buffer1) };
-    //     unsafe { copy_nonoverlapping(src, dst, kani::any()) }
-    // }
+    /// Number of bytes a `Snapshot` holds, which covers the allocations of `PointerGenerator`.
+    const SNAPSHOT_LEN: usize = 100;
+
+    /// A copy of the bytes of a memory range, including the initialization state of each byte.
+    struct Snapshot {
+        bytes: [crate::mem::MaybeUninit<u8>; SNAPSHOT_LEN],
+        len: usize,
+    }
+
+    impl Snapshot {
+        /// Copies the `count` values of type `T` at `src`, or returns `None` if they do not fit.
+        ///
+        /// # Safety
+        /// `src` must be valid for reads of `count` values of type `T`.
+        unsafe fn new<T>(src: *const T, count: usize) -> Option<Snapshot> {
+            let len = count.checked_mul(size_of::<T>()).filter(|len| *len <= SNAPSHOT_LEN)?;
+            let mut bytes = [crate::mem::MaybeUninit::uninit(); SNAPSHOT_LEN];
+            // SAFETY: guaranteed by the caller, and `bytes` holds at least `len` bytes.
+            unsafe { copy_nonoverlapping(src.cast(), bytes.as_mut_ptr(), len) };
+            Some(Snapshot { bytes, len })
+        }
+    }
+
+    /// Return whether `dst` holds a copy of the source, taken before the copy in `src`.
+    ///
+    /// For untyped copy, done via `copy` and `copy_nonoverlapping`, the copies of non-initialized
+    /// bytes (such as padding bytes) should result in a non-initialized copy, while copies of
+    /// initialized bytes result in the same initialized bytes. The source is captured before the
+    /// copy because `copy` may overwrite it when the ranges overlap.
+    ///
+    /// It is UB to read the uninitialized bytes, so we can only compare their initialization
+    /// state.
+    fn check_copy_untyped<T>(src: &Option<Snapshot>, dst: *mut T) -> bool {
+        let Some(src) = src else {
+            return false;
+        };
+        let src_data = src.bytes.as_ptr() as *const u8;
+        let dst_data = dst as *const u8;
+        // SAFETY: the contracts using this function require the destination to be valid for
+        // `src.len` bytes.
+        forall!(|i in (0, src.len)| unsafe {
+            let init = ub_checks::can_dereference(src_data.add(i));
+            init == ub_checks::can_dereference(dst_data.add(i))
+                && (!init || *src_data.add(i) == *dst_data.add(i))
+        })
+    }
+
+    // We need these wrappers because `copy` and `copy_nonoverlapping` are intrinsics, for which
+    // Kani does not currently support contracts (https://github.com/model-checking/kani/issues/3325)
+    #[cfg_attr(kani, kani::modifies(crate::ptr::slice_from_raw_parts(dst, count)))]
+    #[requires(!count.overflowing_mul(size_of::<T>()).1
+      && ub_checks::can_dereference(core::ptr::slice_from_raw_parts(src as *const crate::mem::MaybeUninit<T>, count))
+      && ub_checks::can_write(core::ptr::slice_from_raw_parts_mut(dst, count))
+      && ub_checks::maybe_is_nonoverlapping(src as *const (), dst as *const (), size_of::<T>(), count))]
+    #[ensures(|_| check_copy_untyped(&old(unsafe { Snapshot::new(src, count) }), dst))]
+    unsafe fn copy_nonoverlapping_wrapper<T>(src: *const T, dst: *mut T, count: usize) {
+        unsafe { copy_nonoverlapping(src, dst, count) }
+    }
+
+    #[cfg_attr(kani, kani::modifies(crate::ptr::slice_from_raw_parts(dst, count)))]
+    #[requires(!count.overflowing_mul(size_of::<T>()).1
+      && ub_checks::can_dereference(core::ptr::slice_from_raw_parts(src as *const crate::mem::MaybeUninit<T>, count))
+      && ub_checks::can_write(core::ptr::slice_from_raw_parts_mut(dst, count)))]
+    #[ensures(|_| check_copy_untyped(&old(unsafe { Snapshot::new(src, count) }), dst))]
+    unsafe fn copy_wrapper<T>(src: *const T, dst: *mut T, count: usize) {
+        unsafe { copy(src, dst, count) }
+    }
+
+    #[kani::proof_for_contract(copy_wrapper)]
+    fn check_copy() {
+        run_with_arbitrary_ptrs::<char>(|src, dst| unsafe { copy_wrapper(src, dst, kani::any()) });
+    }
+
+    #[kani::proof_for_contract(copy_nonoverlapping_wrapper)]
+    fn check_copy_nonoverlapping() {
+        run_with_arbitrary_ptrs::<char>(|src, dst| unsafe {
+            copy_nonoverlapping_wrapper(src, dst, kani::any())
+        });
+    }
 
     //We need this wrapper because transmute_unchecked is an intrinsic, for which Kani does
     //not currently support contracts (https://github.com/model-checking/kani/issues/3345)
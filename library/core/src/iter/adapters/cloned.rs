@@ -1,5 +1,7 @@
 use core::num::NonZero;
 
+use safety::{ensures, requires};
+
 use crate::iter::adapters::zip::try_get_unchecked;
 use crate::iter::adapters::{SourceIter, TrustedRandomAccess, TrustedRandomAccessNoCoerce};
 use crate::iter::{FusedIterator, InPlaceIterable, TrustedLen, UncheckedIterator};
@@ -61,6 +63,8 @@ where
         self.it.map(T::clone).fold(init, f)
     }
 
+    #[requires(idx < self.size())]
+    #[ensures(|_| self.size_hint() == old(self.size_hint()))]
     unsafe fn __iterator_get_unchecked(&mut self, idx: usize) -> T
     where
         Self: TrustedRandomAccessNoCoerce,
@@ -147,6 +151,7 @@ where
     I: UncheckedIterator<Item = &'a T>,
     T: Clone,
 {
+    #[requires(self.size_hint().0 != 0)]
     unsafe fn next_unchecked(&mut self) -> T {
         // SAFETY: `Cloned` is 1:1 with the inner iterator, so if the caller promised
         // that there's an element left, the inner iterator has one too.
@@ -1,3 +1,5 @@
+use safety::{ensures, requires};
+
 use crate::iter::adapters::zip::try_get_unchecked;
 use crate::iter::adapters::{SourceIter, TrustedRandomAccess, TrustedRandomAccessNoCoerce};
 use crate::iter::{FusedIterator, InPlaceIterable, TrustedLen};
@@ -92,6 +94,8 @@ where
         self.it.advance_by(n)
     }
 
+    #[requires(idx < self.size())]
+    #[ensures(|_| self.size_hint() == old(self.size_hint()))]
     unsafe fn __iterator_get_unchecked(&mut self, idx: usize) -> T
     where
         Self: TrustedRandomAccessNoCoerce,
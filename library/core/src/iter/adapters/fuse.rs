@@ -1,3 +1,5 @@
+use safety::{ensures, requires};
+
 use crate::intrinsics;
 use crate::iter::adapters::SourceIter;
 use crate::iter::adapters::zip::try_get_unchecked;
@@ -109,6 +111,8 @@ where
     }
 
     #[inline]
+    #[requires(idx < self.size())]
+    #[ensures(|_| self.size_hint() == old(self.size_hint()))]
     unsafe fn __iterator_get_unchecked(&mut self, idx: usize) -> Self::Item
     where
         Self: TrustedRandomAccessNoCoerce,
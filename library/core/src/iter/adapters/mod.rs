@@ -227,3 +227,107 @@ where
     const EXPAND_BY: Option<NonZero<usize>> = I::EXPAND_BY;
     const MERGE_BY: Option<NonZero<usize>> = I::MERGE_BY;
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::iter::{TrustedLen, UncheckedIterator};
+    use crate::kani;
+    use crate::slice::Iter;
+
+    pub(super) const MAX_LEN: usize = 4;
+
+    /// Returns an iterator over an arbitrary subslice of `array`.
+    pub(super) fn any_iter(array: &[u8; MAX_LEN]) -> Iter<'_, u8> {
+        let end = kani::any_where(|end: &usize| *end <= MAX_LEN);
+        let start = kani::any_where(|start: &usize| *start <= end);
+        array[start..end].iter()
+    }
+
+    /// Consumes `it` from both ends and checks that `size_hint` stays exact, and that `it`
+    /// yields as many items as its initial `size_hint` promised, as `TrustedLen` requires.
+    fn check_trusted_len<I: DoubleEndedIterator + TrustedLen>(mut it: I) {
+        let (mut len, upper) = it.size_hint();
+        assert_eq!(upper, Some(len));
+        while let Some(_) = if kani::any() { it.next() } else { it.next_back() } {
+            assert!(len > 0);
+            len -= 1;
+            assert_eq!(it.size_hint(), (len, Some(len)));
+        }
+        assert_eq!(len, 0);
+    }
+
+    /// Consumes `it` from an arbitrary side, then reads an arbitrary item through
+    /// `__iterator_get_unchecked`.
+    fn check_any_get_unchecked<I: DoubleEndedIterator + TrustedRandomAccessNoCoerce>(mut it: I) {
+        if kani::any() {
+            let _ = it.next();
+        }
+        if kani::any() {
+            let _ = it.next_back();
+        }
+        let _ = unsafe { it.__iterator_get_unchecked(kani::any()) };
+    }
+
+    /// Generates a harness for the contract of `__iterator_get_unchecked` of `$adapter`, which
+    /// is built by calling `$method` on an arbitrary slice iterator.
+    macro_rules! check_get_unchecked {
+        ($harness:ident, $adapter:ty, $method:ident($($args:expr),*)) => {
+            #[kani::proof_for_contract(<$adapter as Iterator>::__iterator_get_unchecked)]
+            fn $harness() {
+                let array: [u8; MAX_LEN] = kani::any();
+                check_any_get_unchecked(any_iter(&array).$method($($args),*));
+            }
+        };
+    }
+
+    check_get_unchecked!(check_enumerate_get_unchecked, Enumerate<Iter<'static, u8>>, enumerate());
+    check_get_unchecked!(
+        check_map_get_unchecked,
+        Map<Iter<'static, u8>, fn(&u8) -> u8>,
+        map(u8::clone as fn(&u8) -> u8)
+    );
+    check_get_unchecked!(check_cloned_get_unchecked, Cloned<Iter<'static, u8>>, cloned());
+    check_get_unchecked!(check_copied_get_unchecked, Copied<Iter<'static, u8>>, copied());
+    check_get_unchecked!(
+        check_skip_get_unchecked,
+        Skip<Iter<'static, u8>>,
+        skip(kani::any_where(|n: &usize| *n <= MAX_LEN))
+    );
+    check_get_unchecked!(check_fuse_get_unchecked, Fuse<Iter<'static, u8>>, fuse());
+
+    #[kani::proof_for_contract(<Map<Iter<'static, u8>, fn(&u8) -> u8> as UncheckedIterator>::next_unchecked)]
+    fn check_map_next_unchecked() {
+        let array: [u8; MAX_LEN] = kani::any();
+        let mut it = any_iter(&array).map(u8::clone as fn(&u8) -> u8);
+        let _ = unsafe { it.next_unchecked() };
+    }
+
+    #[kani::proof_for_contract(<Cloned<Iter<'static, u8>> as UncheckedIterator>::next_unchecked)]
+    fn check_cloned_next_unchecked() {
+        let array: [u8; MAX_LEN] = kani::any();
+        let mut it = any_iter(&array).cloned();
+        let _ = unsafe { it.next_unchecked() };
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_trusted_len_adapters() {
+        let array: [u8; MAX_LEN] = kani::any();
+        let other: [u8; MAX_LEN] = kani::any();
+        let n = kani::any_where(|n: &usize| *n <= MAX_LEN);
+        match kani::any::<u8>() {
+            0 => check_trusted_len(any_iter(&array).zip(any_iter(&other))),
+            1 => check_trusted_len(any_iter(&array).enumerate()),
+            2 => check_trusted_len(any_iter(&array).map(|x| x ^ 1)),
+            3 => check_trusted_len(any_iter(&array).cloned()),
+            4 => check_trusted_len(any_iter(&array).copied()),
+            5 => check_trusted_len(any_iter(&array).skip(n)),
+            6 => check_trusted_len(any_iter(&array).take(n)),
+            7 => check_trusted_len(any_iter(&array).rev()),
+            8 => check_trusted_len(any_iter(&array).fuse()),
+            _ => check_trusted_len(any_iter(&array).step_by(n.max(1))),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use safety::{ensures, requires};
+
 use crate::cmp;
 use crate::fmt::{self, Debug};
 use crate::iter::{
@@ -105,6 +107,8 @@ where
     }
 
     #[inline]
+    #[requires(idx < self.size())]
+    #[ensures(|_| self.size_hint() == old(self.size_hint()))]
     unsafe fn __iterator_get_unchecked(&mut self, idx: usize) -> Self::Item
     where
         Self: TrustedRandomAccessNoCoerce,
@@ -266,6 +270,8 @@ where
     }
 
     #[inline]
+    #[requires(idx < self.size())]
+    #[ensures(|_| self.size_hint() == old(self.size_hint()))]
     unsafe fn get_unchecked(&mut self, idx: usize) -> <Self as Iterator>::Item {
         let idx = self.index + idx;
         // SAFETY: the caller must uphold the contract for
@@ -693,3 +699,65 @@ impl<A: TrustedLen, B: TrustedLen> SpecFold for Zip<A, B> {
         accum
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::{Zip, zip};
+    use crate::cell::Cell;
+    use crate::iter::adapters::verify::{MAX_LEN, any_iter};
+    use crate::kani;
+    use crate::slice::Iter;
+
+    #[kani::proof_for_contract(<Zip<Iter<'static, u8>, Iter<'static, u8>> as Iterator>::__iterator_get_unchecked)]
+    fn check_get_unchecked() {
+        let a: [u8; MAX_LEN] = kani::any();
+        let b: [u8; MAX_LEN] = kani::any();
+        let mut zip = zip(any_iter(&a), any_iter(&b));
+        if kani::any() {
+            let _ = zip.next();
+        }
+        if kani::any() {
+            let _ = zip.next_back();
+        }
+        let _ = unsafe { zip.__iterator_get_unchecked(kani::any()) };
+    }
+
+    // Drives the `TrustedRandomAccess` specialization of `Zip` through arbitrary calls to `next`,
+    // `nth` and `next_back`. The left side is a `Map`, which may have side effects, so trimming it
+    // in `next_back` runs code between calls to `__iterator_get_unchecked`. The stub checks that
+    // every call to `__iterator_get_unchecked` on the slice iterators is in range.
+    #[kani::proof]
+    #[kani::stub_verified(<Iter<'static, u8> as Iterator>::__iterator_get_unchecked)]
+    #[kani::unwind(6)]
+    fn check_zip_with_side_effects() {
+        let a: [u8; MAX_LEN] = kani::any();
+        let b: [u8; MAX_LEN] = kani::any();
+        let (a, b) = (any_iter(&a), any_iter(&b));
+        let (a_start, b_start) = (a.as_slice().as_ptr(), b.as_slice().as_ptr());
+        let (a_len, len) = (a.len(), a.len().min(b.len()));
+
+        let calls = Cell::new(0);
+        let a = a.map(|x| {
+            calls.set(calls.get() + 1);
+            x
+        });
+        let mut zip = zip(a, b);
+        let mut seen = [false; MAX_LEN];
+        for _ in 0..=MAX_LEN {
+            let item = match kani::any::<u8>() {
+                0 => zip.next(),
+                1 => zip.nth(kani::any_where(|n: &usize| *n <= MAX_LEN)),
+                _ => zip.next_back(),
+            };
+            let Some((x, y)) = item else { continue };
+            // Both items come from the same offset, which is yielded at most once.
+            let i = unsafe { (x as *const u8).offset_from(a_start) } as usize;
+            assert_eq!(unsafe { (y as *const u8).offset_from(b_start) } as usize, i);
+            assert!(i < len && !seen[i]);
+            seen[i] = true;
+        }
+        // The closure ran at most once per item of `a`.
+        assert!(calls.get() <= a_len);
+    }
+}
@@ -1,5 +1,11 @@
+use safety::requires;
+
 use crate::ops::{Deref, DerefMut, DerefPure};
 use crate::ptr;
+// Used only for contract verification.
+#[allow(unused_imports)]
+use crate::ub_checks;
+use crate::ub_checks::Invariant;
 
 /// A wrapper to inhibit the compiler from automatically calling `T`’s
 /// destructor. This wrapper is 0-cost.
@@ -158,6 +164,15 @@ pub struct ManuallyDrop<T: ?Sized> {
     value: T,
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T: ?Sized + Invariant> Invariant for ManuallyDrop<T> {
+    /// `ManuallyDrop<T>` has the same safety invariant as `T`.
+    #[inline]
+    fn is_safe(&self) -> bool {
+        self.value.is_safe()
+    }
+}
+
 impl<T> ManuallyDrop<T> {
     /// Wrap a value to be manually dropped.
     ///
@@ -217,6 +232,7 @@ impl<T> ManuallyDrop<T> {
     #[must_use = "if you don't need the value, you can use `ManuallyDrop::drop` instead"]
     #[stable(feature = "manually_drop_take", since = "1.42.0")]
     #[inline]
+    #[requires(ub_checks::can_dereference(&raw const slot.value))]
     pub unsafe fn take(slot: &mut ManuallyDrop<T>) -> T {
         // SAFETY: we are reading from a reference, which is guaranteed
         // to be valid for reads.
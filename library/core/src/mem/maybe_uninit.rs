@@ -1,5 +1,12 @@
+use safety::requires;
+
 use crate::any::type_name;
+#[cfg(kani)]
+use crate::kani;
 use crate::mem::ManuallyDrop;
+// Used only for contract verification.
+#[allow(unused_imports)]
+use crate::ub_checks;
 use crate::{fmt, intrinsics, ptr, slice};
 
 /// A wrapper type to construct uninitialized instances of `T`.
@@ -611,6 +618,7 @@ impl<T> MaybeUninit<T> {
     #[inline(always)]
     #[rustc_diagnostic_item = "assume_init"]
     #[track_caller]
+    #[requires(ub_checks::can_dereference(self.as_ptr()))]
     pub const unsafe fn assume_init(self) -> T {
         // SAFETY: the caller must guarantee that `self` is initialized.
         // This also means that `self` must be a `value` variant.
@@ -681,6 +689,7 @@ impl<T> MaybeUninit<T> {
     #[rustc_const_stable(feature = "const_maybe_uninit_assume_init_read", since = "1.75.0")]
     #[inline(always)]
     #[track_caller]
+    #[requires(ub_checks::can_dereference(self.as_ptr()))]
     pub const unsafe fn assume_init_read(&self) -> T {
         // SAFETY: the caller must guarantee that `self` is initialized.
         // Reading from `self.as_ptr()` is safe since `self` should be initialized.
@@ -712,6 +721,7 @@ impl<T> MaybeUninit<T> {
     ///
     /// [`assume_init`]: MaybeUninit::assume_init
     #[stable(feature = "maybe_uninit_extra", since = "1.60.0")]
+    #[requires(ub_checks::can_dereference(self.as_ptr()))]
     pub unsafe fn assume_init_drop(&mut self) {
         // SAFETY: the caller must guarantee that `self` is initialized and
         // satisfies all invariants of `T`.
@@ -778,6 +788,7 @@ impl<T> MaybeUninit<T> {
     #[stable(feature = "maybe_uninit_ref", since = "1.55.0")]
     #[rustc_const_stable(feature = "const_maybe_uninit_assume_init_ref", since = "1.59.0")]
     #[inline(always)]
+    #[requires(ub_checks::can_dereference(self.as_ptr()))]
     pub const unsafe fn assume_init_ref(&self) -> &T {
         // SAFETY: the caller must guarantee that `self` is initialized.
         // This also means that `self` must be a `value` variant.
@@ -895,6 +906,7 @@ impl<T> MaybeUninit<T> {
     #[stable(feature = "maybe_uninit_ref", since = "1.55.0")]
     #[rustc_const_stable(feature = "const_maybe_uninit_assume_init", since = "1.84.0")]
     #[inline(always)]
+    #[requires(ub_checks::can_dereference(self.as_ptr()))]
     pub const unsafe fn assume_init_mut(&mut self) -> &mut T {
         // SAFETY: the caller must guarantee that `self` is initialized.
         // This also means that `self` must be a `value` variant.
@@ -932,6 +944,7 @@ impl<T> MaybeUninit<T> {
     #[unstable(feature = "maybe_uninit_array_assume_init", issue = "96097")]
     #[inline(always)]
     #[track_caller]
+    #[requires(ub_checks::can_dereference((&raw const array).cast::<[T; N]>()))]
     pub const unsafe fn array_assume_init<const N: usize>(array: [Self; N]) -> [T; N] {
         // SAFETY:
         // * The caller guarantees that all elements of the array are initialized
@@ -1009,6 +1022,7 @@ impl<T> MaybeUninit<T> {
         note = "replaced by inherent assume_init_ref method; will eventually be removed",
         since = "1.83.0"
     )]
+    #[requires(ub_checks::can_dereference(slice as *const [Self] as *const [T]))]
     pub const unsafe fn slice_assume_init_ref(slice: &[Self]) -> &[T] {
         // SAFETY: Same for both methods.
         unsafe { slice.assume_init_ref() }
@@ -1020,6 +1034,7 @@ impl<T> MaybeUninit<T> {
         note = "replaced by inherent assume_init_mut method; will eventually be removed",
         since = "1.83.0"
     )]
+    #[requires(ub_checks::can_dereference(&raw const *slice as *const [T]))]
     pub const unsafe fn slice_assume_init_mut(slice: &mut [Self]) -> &mut [T] {
         // SAFETY: Same for both methods.
         unsafe { slice.assume_init_mut() }
@@ -1498,6 +1513,7 @@ impl<T> [MaybeUninit<T>] {
     /// non-null. Dropping such a `Vec<T>` however will cause undefined
     /// behaviour.
     #[unstable(feature = "maybe_uninit_slice", issue = "63569")]
+    #[requires(ub_checks::can_dereference(self as *const Self as *const [T]))]
     #[inline(always)]
     pub unsafe fn assume_init_drop(&mut self) {
         if !self.is_empty() {
@@ -1517,6 +1533,7 @@ impl<T> [MaybeUninit<T>] {
     /// the slice really is in an initialized state.
     #[unstable(feature = "maybe_uninit_slice", issue = "63569")]
     #[inline(always)]
+    #[requires(ub_checks::can_dereference(self as *const Self as *const [T]))]
     pub const unsafe fn assume_init_ref(&self) -> &[T] {
         // SAFETY: casting `slice` to a `*const [T]` is safe since the caller guarantees that
         // `slice` is initialized, and `MaybeUninit` is guaranteed to have the same layout as `T`.
@@ -1535,6 +1552,7 @@ impl<T> [MaybeUninit<T>] {
     /// be used to initialize a `MaybeUninit` slice.
     #[unstable(feature = "maybe_uninit_slice", issue = "63569")]
     #[inline(always)]
+    #[requires(ub_checks::can_dereference(&raw const *self as *const [T]))]
     pub const unsafe fn assume_init_mut(&mut self) -> &mut [T] {
         // SAFETY: similar to safety notes for `slice_get_ref`, but we have a
         // mutable reference which is also guaranteed to be valid for writes.
@@ -1621,3 +1639,122 @@ impl<T: Copy> SpecFill<T> for [MaybeUninit<T>] {
         self.fill(MaybeUninit::new(value));
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+    use crate::ub_checks::{Invariant, any_safe};
+
+    const MAX_LEN: usize = 4;
+
+    /// Generates harnesses for the `assume_init*` family on an initialized `MaybeUninit<$ty>`,
+    /// checking that the extracted value is the one that was written and is safe.
+    macro_rules! check_assume_init {
+        ($mod_name:ident, $ty:ty) => {
+            mod $mod_name {
+                use super::*;
+
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::assume_init)]
+                pub fn check_assume_init() {
+                    let value: $ty = any_safe();
+                    let init = MaybeUninit::new(value);
+                    let result = unsafe { init.assume_init() };
+                    assert!(result.is_safe());
+                    assert_eq!(result, value);
+                }
+
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::assume_init_read)]
+                pub fn check_assume_init_read() {
+                    let value: $ty = any_safe();
+                    let init = MaybeUninit::new(value);
+                    let result = unsafe { init.assume_init_read() };
+                    assert!(result.is_safe());
+                    assert_eq!(result, value);
+                }
+
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::assume_init_ref)]
+                pub fn check_assume_init_ref() {
+                    let value: $ty = any_safe();
+                    let init = MaybeUninit::new(value);
+                    let result = unsafe { init.assume_init_ref() };
+                    assert!(result.is_safe());
+                    assert_eq!(*result, value);
+                }
+
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::assume_init_mut)]
+                pub fn check_assume_init_mut() {
+                    let mut init = MaybeUninit::new(any_safe::<$ty>());
+                    let value: $ty = any_safe();
+                    *unsafe { init.assume_init_mut() } = value;
+                    assert_eq!(unsafe { init.assume_init() }, value);
+                }
+
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::assume_init_drop)]
+                pub fn check_assume_init_drop() {
+                    let mut init = MaybeUninit::new(any_safe::<$ty>());
+                    unsafe { init.assume_init_drop() };
+                }
+
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::array_assume_init)]
+                pub fn check_array_assume_init() {
+                    let values: [$ty; MAX_LEN] = any_safe();
+                    let array = values.map(MaybeUninit::new);
+                    let result = unsafe { MaybeUninit::array_assume_init(array) };
+                    assert!(result.is_safe());
+                    assert_eq!(result, values);
+                }
+
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::slice_assume_init_ref)]
+                pub fn check_slice_assume_init_ref() {
+                    let values: [$ty; MAX_LEN] = any_safe();
+                    let array = values.map(MaybeUninit::new);
+                    let len: usize = kani::any_where(|len| *len <= MAX_LEN);
+                    let result = unsafe { MaybeUninit::slice_assume_init_ref(&array[..len]) };
+                    assert_eq!(result, &values[..len]);
+                }
+
+                #[kani::proof_for_contract(MaybeUninit::<$ty>::slice_assume_init_mut)]
+                pub fn check_slice_assume_init_mut() {
+                    let values: [$ty; MAX_LEN] = any_safe();
+                    let mut array = values.map(MaybeUninit::new);
+                    let len: usize = kani::any_where(|len| *len <= MAX_LEN);
+                    let result = unsafe { MaybeUninit::slice_assume_init_mut(&mut array[..len]) };
+                    assert_eq!(result, &values[..len]);
+                }
+
+                #[kani::proof]
+                pub fn check_slice_methods() {
+                    let values: [$ty; MAX_LEN] = any_safe();
+                    let mut array = values.map(MaybeUninit::new);
+                    let len: usize = kani::any_where(|len| *len <= MAX_LEN);
+                    let slice = &mut array[..len];
+                    assert_eq!(unsafe { slice.assume_init_ref() }, &values[..len]);
+                    assert_eq!(unsafe { slice.assume_init_mut() }, &values[..len]);
+                    unsafe { slice.assume_init_drop() };
+                }
+
+                #[kani::proof]
+                #[allow(deprecated)]
+                pub fn check_copy_from_slice() {
+                    let values: [$ty; MAX_LEN] = any_safe();
+                    let mut array = [MaybeUninit::<$ty>::uninit(); MAX_LEN];
+                    let len: usize = kani::any_where(|len| *len <= MAX_LEN);
+                    let result = MaybeUninit::copy_from_slice(&mut array[..len], &values[..len]);
+                    assert_eq!(result, &values[..len]);
+                    assert_eq!(
+                        unsafe { MaybeUninit::slice_assume_init_ref(&array[..len]) },
+                        &values[..len]
+                    );
+                }
+            }
+        };
+    }
+
+    check_assume_init!(verify_u8, u8);
+    check_assume_init!(verify_bool, bool);
+    check_assume_init!(verify_char, char);
+    check_assume_init!(verify_tuple, (u16, char));
+    check_assume_init!(verify_option_nonzero, Option<crate::num::NonZero<u32>>);
+}
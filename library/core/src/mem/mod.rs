@@ -5,10 +5,15 @@
 
 #![stable(feature = "rust1", since = "1.0.0")]
 
+use safety::requires;
+
 use crate::alloc::Layout;
 #[cfg(kani)]
 use crate::kani;
 use crate::marker::DiscriminantKind;
+// Used only for contract verification.
+#[allow(unused_imports)]
+use crate::ub_checks;
 use crate::{clone, cmp, fmt, hash, intrinsics, ptr};
 
 mod manually_drop;
@@ -665,6 +670,7 @@ pub const fn needs_drop<T: ?Sized>() -> bool {
 #[rustc_diagnostic_item = "mem_zeroed"]
 #[track_caller]
 #[rustc_const_stable(feature = "const_mem_zeroed", since = "1.75.0")]
+#[requires(ub_checks::can_dereference(MaybeUninit::<T>::zeroed().as_ptr()))]
 pub const unsafe fn zeroed<T>() -> T {
     // SAFETY: the caller must guarantee that an all-zero value is valid for `T`.
     unsafe {
@@ -1026,6 +1032,10 @@ pub const fn copy<T: Copy>(x: &T) -> T {
 #[track_caller]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[rustc_const_stable(feature = "const_transmute_copy", since = "1.74.0")]
+#[requires(
+    size_of::<Src>() < size_of::<Dst>()
+        || ub_checks::can_read_unaligned(src as *const Src as *const Dst)
+)]
 pub const unsafe fn transmute_copy<Src, Dst>(src: &Src) -> Dst {
     assert!(
         size_of::<Src>() >= size_of::<Dst>(),
@@ -1412,6 +1422,7 @@ pub macro offset_of($Container:ty, $($fields:expr)+ $(,)?) {
 mod verify {
     use super::*;
     use crate::kani;
+    use crate::ub_checks::Invariant;
 
     /// Use this type to ensure that mem swap does not drop the value.
     #[derive(kani::Arbitrary)]
@@ -1440,4 +1451,122 @@ mod verify {
         forget(x);
         forget(y);
     }
+
+    #[kani::proof_for_contract(zeroed)]
+    pub fn check_zeroed_u64() {
+        let value: u64 = unsafe { zeroed() };
+        assert_eq!(value, 0);
+    }
+
+    #[kani::proof_for_contract(zeroed)]
+    pub fn check_zeroed_option_nonzero() {
+        let value: Option<crate::num::NonZero<u32>> = unsafe { zeroed() };
+        assert!(value.is_none() && value.is_safe());
+    }
+
+    #[kani::proof_for_contract(zeroed)]
+    pub fn check_zeroed_tuple() {
+        let value: (bool, char, [u8; 3]) = unsafe { zeroed() };
+        assert!(value.is_safe());
+        assert_eq!(value, (false, '\0', [0; 3]));
+    }
+
+    #[kani::proof_for_contract(transmute_copy)]
+    pub fn check_transmute_copy_same_size() {
+        let src: u32 = kani::any();
+        let dst: [u8; 4] = unsafe { transmute_copy(&src) };
+        assert_eq!(dst, src.to_ne_bytes());
+    }
+
+    #[kani::proof_for_contract(transmute_copy)]
+    pub fn check_transmute_copy_prefix() {
+        let src: [u16; 3] = kani::any();
+        let dst: u16 = unsafe { transmute_copy(&src) };
+        assert_eq!(dst, src[0]);
+    }
+
+    #[kani::proof_for_contract(transmute_copy)]
+    pub fn check_transmute_copy_higher_align() {
+        let src: [u8; 8] = kani::any();
+        let dst: u64 = unsafe { transmute_copy(&src) };
+        assert_eq!(dst, u64::from_ne_bytes(src));
+    }
+
+    #[kani::proof_for_contract(transmute_copy)]
+    pub fn check_transmute_copy_char() {
+        let src: char = kani::any();
+        let dst: u32 = unsafe { transmute_copy(&src) };
+        assert_eq!(dst, src as u32);
+    }
+
+    #[kani::proof_for_contract(transmute_copy)]
+    #[kani::should_panic]
+    pub fn check_transmute_copy_larger_dst() {
+        let src: u16 = kani::any();
+        let _dst: u32 = unsafe { transmute_copy(&src) };
+    }
+
+    #[kani::proof_for_contract(ManuallyDrop::<char>::take)]
+    pub fn check_manually_drop_take() {
+        let value: char = kani::any();
+        let mut slot = ManuallyDrop::new(value);
+        let taken = unsafe { ManuallyDrop::take(&mut slot) };
+        assert!(taken.is_safe() && slot.is_safe());
+        assert_eq!(taken, value);
+    }
+
+    #[kani::proof_for_contract(ManuallyDrop::<CannotDrop<u8>>::take)]
+    pub fn check_manually_drop_take_no_drop() {
+        let mut slot = ManuallyDrop::new(kani::any::<CannotDrop<u8>>());
+        let taken = unsafe { ManuallyDrop::take(&mut slot) };
+        forget(taken);
+    }
+
+    /// Transmutes every value of `$src` into `$dst` and back, and checks that the
+    /// round trip preserves the value and that the result upholds the safety invariant.
+    macro_rules! check_transmute_pair {
+        ($name:ident, $src:ty, $dst:ty) => {
+            #[kani::proof]
+            pub fn $name() {
+                let src: $src = kani::any();
+                let dst: $dst = unsafe { transmute(src) };
+                assert!(dst.is_safe());
+                let back: $src = unsafe { transmute(dst) };
+                assert_eq!(back, src);
+            }
+        };
+        ($name:ident, $src:ty, $dst:ty, $valid:expr) => {
+            #[kani::proof]
+            pub fn $name() {
+                let src: $src = kani::any_where($valid);
+                let dst: $dst = unsafe { transmute(src) };
+                assert!(dst.is_safe());
+                let back: $src = unsafe { transmute(dst) };
+                assert_eq!(back, src);
+            }
+        };
+    }
+
+    check_transmute_pair!(check_transmute_u8_i8, u8, i8);
+    check_transmute_pair!(check_transmute_u16_i16, u16, i16);
+    check_transmute_pair!(check_transmute_u32_i32, u32, i32);
+    check_transmute_pair!(check_transmute_u64_i64, u64, i64);
+    check_transmute_pair!(check_transmute_u128_i128, u128, i128);
+    check_transmute_pair!(check_transmute_usize_isize, usize, isize);
+    check_transmute_pair!(check_transmute_u16_bytes, u16, [u8; 2]);
+    check_transmute_pair!(check_transmute_u32_bytes, u32, [u8; 4]);
+    check_transmute_pair!(check_transmute_u64_bytes, u64, [u8; 8]);
+    check_transmute_pair!(check_transmute_u128_bytes, u128, [u8; 16]);
+    check_transmute_pair!(check_transmute_u32_f32, u32, f32);
+    check_transmute_pair!(check_transmute_u64_f64, u64, f64);
+    check_transmute_pair!(check_transmute_char_u32, char, u32);
+    check_transmute_pair!(check_transmute_bool_u8, bool, u8);
+    check_transmute_pair!(check_transmute_u32_char, u32, char, |v: &u32| char::from_u32(*v)
+        .is_some());
+    check_transmute_pair!(check_transmute_u8_bool, u8, bool, |v: &u8| *v <= 1);
+    check_transmute_pair!(
+        check_transmute_u32_option_nonzero,
+        u32,
+        Option<crate::num::NonZero<u32>>
+    );
 }
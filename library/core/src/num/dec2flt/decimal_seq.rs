@@ -10,6 +10,7 @@
 //! available online: <https://nigeltao.github.io/blog/2020/parse-number-f64-simple.html>.
 
 use crate::num::dec2flt::common::{ByteSlice, is_8digits};
+use crate::ub_checks::Invariant;
 
 /// A decimal floating-point number, represented as a sequence of decimal digits.
 #[derive(Clone, Debug, PartialEq)]
@@ -30,6 +31,15 @@ impl Default for DecimalSeq {
     }
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl Invariant for DecimalSeq {
+    /// At most `MAX_DIGITS` digits are stored, and each of them is in the range [0, 9].
+    fn is_safe(&self) -> bool {
+        self.num_digits <= Self::MAX_DIGITS
+            && self.digits[..self.num_digits].iter().all(|&d| d < 10)
+    }
+}
+
 impl DecimalSeq {
     /// The maximum number of digits required to unambiguously round up to a 64-bit float.
     ///
@@ -377,3 +387,100 @@ fn number_of_digits_decimal_left_shift(d: &DecimalSeq, mut shift: usize) -> usiz
 
     num_new_digits
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+
+    const MAX_LEN: usize = 12;
+
+    /// `parse_long_mantissa` never shifts by more than 60 bits at once.
+    const MAX_SHIFT: usize = 60;
+
+    /// Parses at most `MAX_LEN` arbitrary bytes.
+    fn any_decimal_seq() -> DecimalSeq {
+        let bytes: [u8; MAX_LEN] = kani::any();
+        let len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        parse_decimal_seq(&bytes[..len])
+    }
+
+    /// Generates a sequence of `MAX_DIGITS - MAX_LEN` to `MAX_DIGITS` digits, which
+    /// `any_decimal_seq` cannot reach, so that shifting it fills the buffer. Only the first
+    /// `MAX_LEN` digits are arbitrary, and the others are all nines.
+    ///
+    /// Harnesses using this function must unwind more than `MAX_DIGITS + MAX_SHIFT` times.
+    fn any_long_decimal_seq() -> DecimalSeq {
+        let mut d = DecimalSeq::default();
+        d.num_digits = kani::any_where(|num_digits: &usize| {
+            DecimalSeq::MAX_DIGITS - MAX_LEN <= *num_digits && *num_digits <= DecimalSeq::MAX_DIGITS
+        });
+        d.decimal_point = kani::any_where(|decimal_point: &i32| {
+            decimal_point.unsigned_abs() <= DecimalSeq::DECIMAL_POINT_RANGE as u32
+        });
+        d.truncated = kani::any();
+        let (prefix, fill) = d.digits.split_at_mut(MAX_LEN);
+        for digit in prefix {
+            *digit = kani::any_where(|digit: &u8| *digit < 10);
+        }
+        fill.fill(9);
+        d
+    }
+
+    // `parse_decimal_seq` zeroes up to `MAX_DIGITS_WITHOUT_OVERFLOW` digits.
+    #[kani::proof]
+    #[kani::unwind(20)]
+    fn check_parse_decimal_seq() {
+        assert!(any_decimal_seq().is_safe());
+    }
+
+    // Shifting right by `shift` bits can produce up to `shift` more digits.
+    #[kani::proof]
+    #[kani::unwind(62)]
+    fn check_right_shift() {
+        let mut d = any_decimal_seq();
+        d.right_shift(kani::any_where(|shift: &usize| 0 < *shift && *shift <= MAX_SHIFT));
+        assert!(d.is_safe());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(62)]
+    fn check_left_shift() {
+        let mut d = any_decimal_seq();
+        d.left_shift(kani::any_where(|shift: &usize| 0 < *shift && *shift <= MAX_SHIFT));
+        assert!(d.is_safe());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(830)]
+    fn check_right_shift_long() {
+        let mut d = any_long_decimal_seq();
+        d.right_shift(kani::any_where(|shift: &usize| 0 < *shift && *shift <= MAX_SHIFT));
+        assert!(d.is_safe());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(830)]
+    fn check_left_shift_long() {
+        let mut d = any_long_decimal_seq();
+        d.left_shift(kani::any_where(|shift: &usize| 0 < *shift && *shift <= MAX_SHIFT));
+        assert!(d.is_safe());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(20)]
+    fn check_round() {
+        let d = any_decimal_seq();
+        let n = d.round();
+        assert!(d.is_safe());
+        if d.num_digits == 0 || d.decimal_point < 0 {
+            assert_eq!(n, 0);
+        } else if d.decimal_point >= DecimalSeq::MAX_DIGITS_WITHOUT_OVERFLOW as i32 {
+            assert_eq!(n, u64::MAX);
+        } else {
+            // The integer part has `decimal_point` digits, and rounding adds at most one to it.
+            assert!(n <= 10_u64.pow(d.decimal_point as u32));
+        }
+    }
+}
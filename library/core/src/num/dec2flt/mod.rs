@@ -306,3 +306,49 @@ pub fn dec2flt<F: RawFloat>(s: &str) -> Result<F, ParseFloatError> {
     }
     Ok(float)
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+    use crate::mem::MaybeUninit;
+    use crate::num::FpCategory;
+    use crate::num::flt2dec::strategy::grisu::format_shortest;
+    use crate::num::flt2dec::{DecodableFloat, MAX_SIG_DIGITS, Sign, to_shortest_str};
+
+    /// Large enough for the decimal form of any `f32`, the longest being the 48 bytes of
+    /// `-0.000000000000000000000000000000000000000000001`.
+    const OUT_LEN: usize = 64;
+
+    /// Checks that formatting a float `x` as `Display` does, and parsing the result, gives back
+    /// `x`.
+    fn check_round_trip<F: DecodableFloat + kani::Arbitrary>() {
+        let x = kani::any::<F>();
+        kani::assume(x.classify() != FpCategory::Nan);
+
+        let mut buf = [MaybeUninit::uninit(); MAX_SIG_DIGITS];
+        let mut parts = [MaybeUninit::uninit(); 4];
+        let formatted = to_shortest_str(format_shortest, x, Sign::Minus, 0, &mut buf, &mut parts);
+        let mut out = [0; OUT_LEN];
+        let len = formatted.write(&mut out).unwrap();
+        let s = crate::str::from_utf8(&out[..len]).unwrap();
+
+        assert_eq!(dec2flt::<F>(s).unwrap().to_bits(), x.to_bits());
+    }
+
+    #[cfg(target_has_reliable_f16)]
+    #[kani::proof]
+    #[kani::unwind(65)]
+    #[kani::solver(kissat)]
+    fn check_round_trip_f16() {
+        check_round_trip::<f16>();
+    }
+
+    #[kani::proof]
+    #[kani::unwind(65)]
+    #[kani::solver(kissat)]
+    fn check_round_trip_f32() {
+        check_round_trip::<f32>();
+    }
+}
@@ -239,3 +239,53 @@ pub(crate) fn parse_inf_nan<F: RawFloat>(s: &[u8], negative: bool) -> Option<F>
 
     if negative { Some(-float) } else { Some(float) }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+
+    /// Generates at most `N` arbitrary bytes, in `buf`.
+    fn any_bytes<const N: usize>(buf: &mut [u8; N]) -> &[u8] {
+        *buf = kani::any();
+        let len = kani::any_where(|len: &usize| *len <= N);
+        &buf[..len]
+    }
+
+    #[kani::proof]
+    #[kani::unwind(11)]
+    fn check_parse_number() {
+        let mut buf = [0; 10];
+        let s = any_bytes(&mut buf);
+        kani::assume(!s.is_empty());
+        if let Some(decimal) = parse_number(s) {
+            // Up to 19 digits fit in the mantissa.
+            assert!(!decimal.many_digits && !decimal.negative);
+        }
+    }
+
+    /// Covers the numbers with more than 19 digits, which are parsed a second time to truncate
+    /// their mantissa.
+    #[kani::proof]
+    #[kani::unwind(23)]
+    fn check_parse_number_many_digits() {
+        let mut buf = [0; 22];
+        let s = any_bytes(&mut buf);
+        kani::assume(s.iter().all(|&c| c.is_ascii_digit() || c == b'.'));
+        kani::assume(!s.is_empty());
+        if let Some(decimal) = parse_number(s) {
+            assert!(!decimal.many_digits || decimal.mantissa >= MIN_19DIGIT_INT);
+        }
+    }
+
+    #[kani::proof]
+    fn check_parse_inf_nan() {
+        let mut buf = [0; 8];
+        let s = any_bytes(&mut buf);
+        let negative = kani::any();
+        if let Some(value) = parse_inf_nan::<f32>(s, negative) {
+            assert!(value.is_nan() || value.is_sign_negative() == negative);
+        }
+    }
+}
@@ -122,9 +122,14 @@ functions.
     issue = "none"
 )]
 
+use safety::{ensures, requires};
+
 pub use self::decoder::{DecodableFloat, Decoded, FullDecoded, decode};
 use super::fmt::{Formatted, Part};
 use crate::mem::MaybeUninit;
+// Used only for contract verification.
+#[allow(unused_imports)]
+use crate::ub_checks;
 
 pub mod decoder;
 pub mod estimator;
@@ -142,6 +147,23 @@ pub mod strategy {
 /// The exact formula is `ceil(# bits in mantissa * log_10 2 + 1)`.
 pub const MAX_SIG_DIGITS: usize = 17;
 
+/// Whether `digits`, as returned by a digit-generation function given a buffer of `buf_len`
+/// bytes, are at most `buf_len` initialized decimal digits, the first of which is not zero.
+#[allow(dead_code)] // Only used by contracts.
+pub(crate) fn is_valid_digits(digits: &[u8], buf_len: usize) -> bool {
+    digits.len() <= buf_len
+        && ub_checks::can_dereference(digits as *const [u8])
+        && digits.first().is_none_or(|&d| d != b'0')
+        && digits.iter().all(u8::is_ascii_digit)
+}
+
+/// Whether `parts`, as returned in a `Formatted`, are at most `max_len` initialized parts, and at
+/// least one.
+#[allow(dead_code)] // Only used by contracts.
+fn is_valid_parts(parts: &[Part<'_>], max_len: usize) -> bool {
+    (1..=max_len).contains(&parts.len()) && ub_checks::can_dereference(parts as *const [Part<'_>])
+}
+
 /// When `d` contains decimal digits, increase the last digit and propagate carry.
 /// Returns a next digit when it causes the length to change.
 #[doc(hidden)]
@@ -345,6 +367,8 @@ fn determine_sign(sign: Sign, decoded: &FullDecoded, negative: bool) -> &'static
 /// The byte buffer should be at least `MAX_SIG_DIGITS` bytes long.
 /// There should be at least 4 parts available, due to the worst case like
 /// `[+][0.][0000][2][0000]` with `frac_digits = 10`.
+#[requires(buf.len() >= MAX_SIG_DIGITS && parts.len() >= 4)]
+#[ensures(|result| is_valid_parts(result.parts, 4))]
 pub fn to_shortest_str<'a, T, F>(
     mut format_shortest: F,
     v: T,
@@ -587,6 +611,14 @@ where
 /// (The tipping point for `f64` is about 800, and 1000 bytes should be enough.)
 /// There should be at least 4 parts available, due to the worst case like
 /// `[+][0.][0000][2][0000]` with `frac_digits = 10`.
+#[requires(
+    parts.len() >= 4
+        && match decode(v).1 {
+            FullDecoded::Finite(ref decoded) => buf.len() >= estimate_max_buf_len(decoded.exp),
+            _ => true,
+        }
+)]
+#[ensures(|result| is_valid_parts(result.parts, 4))]
 pub fn to_exact_fixed_str<'a, T, F>(
     mut format_exact: F,
     v: T,
@@ -670,3 +702,50 @@ where
         }
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+
+    /// A buffer large enough for `to_exact_fixed_str` with any `f16`.
+    const EXACT_BUF_LEN: usize = 40;
+
+    fn any_sign() -> Sign {
+        if kani::any() { Sign::Minus } else { Sign::MinusPlus }
+    }
+
+    #[kani::proof_for_contract(to_shortest_str)]
+    #[kani::unwind(41)]
+    #[kani::solver(kissat)]
+    fn check_to_shortest_str() {
+        let mut buf = [MaybeUninit::uninit(); MAX_SIG_DIGITS];
+        let mut parts = [MaybeUninit::uninit(); 4];
+        let _ = to_shortest_str(
+            strategy::grisu::format_shortest,
+            kani::any::<f32>(),
+            any_sign(),
+            kani::any(),
+            &mut buf,
+            &mut parts,
+        );
+    }
+
+    #[cfg(target_has_reliable_f16)]
+    #[kani::proof_for_contract(to_exact_fixed_str)]
+    #[kani::unwind(41)]
+    #[kani::solver(kissat)]
+    fn check_to_exact_fixed_str() {
+        let mut buf = [MaybeUninit::uninit(); EXACT_BUF_LEN];
+        let mut parts = [MaybeUninit::uninit(); 4];
+        let _ = to_exact_fixed_str(
+            strategy::grisu::format_exact,
+            kani::any::<f16>(),
+            any_sign(),
+            kani::any(),
+            &mut buf,
+            &mut parts,
+        );
+    }
+}
@@ -5,8 +5,13 @@
 //! [^1]: Florian Loitsch. 2010. Printing floating-point numbers quickly and
 //!   accurately with integers. SIGPLAN Not. 45, 6 (June 2010), 233-243.
 
+use safety::{ensures, requires};
+
 use crate::mem::MaybeUninit;
 use crate::num::diy_float::Fp;
+// Used only for contract verification.
+#[allow(unused_imports)]
+use crate::num::flt2dec::is_valid_digits;
 use crate::num::flt2dec::{Decoded, MAX_SIG_DIGITS, round_up};
 
 // see the comments in `format_shortest_opt` for the rationale.
@@ -162,6 +167,17 @@ pub fn max_pow10_no_more_than(x: u32) -> (u8, u32) {
 /// The shortest mode implementation for Grisu.
 ///
 /// It returns `None` when it would return an inexact representation otherwise.
+#[requires(
+    d.mant > 0
+        && d.minus > 0
+        && d.plus > 0
+        && d.mant.checked_add(d.plus).is_some_and(|high| high < (1 << 61))
+        && d.mant.checked_sub(d.minus).is_some()
+        && buf.len() >= MAX_SIG_DIGITS
+)]
+#[ensures(|result| result.is_none_or(|(digits, _)| {
+    !digits.is_empty() && is_valid_digits(digits, MAX_SIG_DIGITS)
+}))]
 pub fn format_shortest_opt<'a>(
     d: &Decoded,
     buf: &'a mut [MaybeUninit<u8>],
@@ -468,6 +484,10 @@ pub fn format_shortest<'a>(
 /// The exact and fixed mode implementation for Grisu.
 ///
 /// It returns `None` when it would return an inexact representation otherwise.
+#[requires(d.mant > 0 && d.mant < (1 << 61) && !buf.is_empty())]
+#[ensures(|result| result.is_none_or(|(digits, exp)| {
+    is_valid_digits(digits, old(buf.len())) && (digits.is_empty() || exp > limit)
+}))]
 pub fn format_exact_opt<'a>(
     d: &Decoded,
     buf: &'a mut [MaybeUninit<u8>],
@@ -774,3 +794,92 @@ pub fn format_exact<'a>(
         None => fallback(d, buf, limit),
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+    use crate::num::flt2dec::strategy::dragon;
+    use crate::num::flt2dec::{DecodableFloat, FullDecoded, decode};
+
+    /// Decodes an arbitrary finite, nonzero float.
+    fn any_decoded<T: DecodableFloat + kani::Arbitrary>() -> Decoded {
+        let FullDecoded::Finite(decoded) = decode(kani::any::<T>()).1 else {
+            kani::assume(false);
+            unreachable!()
+        };
+        decoded
+    }
+
+    /// Checks that Grisu, when it succeeds, finds the same shortest representation as Dragon.
+    fn check_shortest_against_dragon<T: DecodableFloat + kani::Arbitrary>() {
+        let decoded = any_decoded::<T>();
+        let mut buf = [MaybeUninit::uninit(); MAX_SIG_DIGITS];
+        let mut dragon_buf = [MaybeUninit::uninit(); MAX_SIG_DIGITS];
+        if let Some(grisu) = format_shortest_opt(&decoded, &mut buf) {
+            assert_eq!(grisu, dragon::format_shortest(&decoded, &mut dragon_buf));
+        }
+    }
+
+    /// Checks that Grisu, when it succeeds, finds the same exact representation as Dragon, with an
+    /// arbitrary number of digits up to `MAX_SIG_DIGITS` and an arbitrary limit.
+    fn check_exact_against_dragon<T: DecodableFloat + kani::Arbitrary>() {
+        let decoded = any_decoded::<T>();
+        let len = kani::any_where(|len: &usize| 0 < *len && *len <= MAX_SIG_DIGITS);
+        let limit = kani::any();
+        let mut buf = [MaybeUninit::uninit(); MAX_SIG_DIGITS];
+        let mut dragon_buf = [MaybeUninit::uninit(); MAX_SIG_DIGITS];
+        if let Some(grisu) = format_exact_opt(&decoded, &mut buf[..len], limit) {
+            assert_eq!(grisu, dragon::format_exact(&decoded, &mut dragon_buf[..len], limit));
+        }
+    }
+
+    #[kani::proof_for_contract(format_shortest_opt)]
+    #[kani::unwind(18)]
+    fn check_format_shortest_opt() {
+        let decoded = any_decoded::<f32>();
+        let mut buf = [MaybeUninit::uninit(); MAX_SIG_DIGITS];
+        let _ = format_shortest_opt(&decoded, &mut buf);
+    }
+
+    #[kani::proof_for_contract(format_exact_opt)]
+    #[kani::unwind(18)]
+    fn check_format_exact_opt() {
+        let decoded = any_decoded::<f32>();
+        let len = kani::any_where(|len: &usize| 0 < *len && *len <= MAX_SIG_DIGITS);
+        let mut buf = [MaybeUninit::uninit(); MAX_SIG_DIGITS];
+        let _ = format_exact_opt(&decoded, &mut buf[..len], kani::any());
+    }
+
+    // Dragon works on `Big32x40` bignums, whose operations loop over their 40 digits.
+    #[cfg(target_has_reliable_f16)]
+    #[kani::proof]
+    #[kani::unwind(41)]
+    #[kani::solver(kissat)]
+    fn check_shortest_against_dragon_f16() {
+        check_shortest_against_dragon::<f16>();
+    }
+
+    #[kani::proof]
+    #[kani::unwind(41)]
+    #[kani::solver(kissat)]
+    fn check_shortest_against_dragon_f32() {
+        check_shortest_against_dragon::<f32>();
+    }
+
+    #[cfg(target_has_reliable_f16)]
+    #[kani::proof]
+    #[kani::unwind(41)]
+    #[kani::solver(kissat)]
+    fn check_exact_against_dragon_f16() {
+        check_exact_against_dragon::<f16>();
+    }
+
+    #[kani::proof]
+    #[kani::unwind(41)]
+    #[kani::solver(kissat)]
+    fn check_exact_against_dragon_f32() {
+        check_exact_against_dragon::<f32>();
+    }
+}
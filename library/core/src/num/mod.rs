@@ -2345,4 +2345,19 @@ mod verify {
         usize,
         checked_f128_to_int_unchecked_usize
     );
+
+    #[kani::proof]
+    pub fn check_f32_from_bits_to_bits() {
+        let bits: u32 = kani::any();
+        let value = f32::from_bits(bits);
+        assert_eq!(value.to_bits(), bits);
+    }
+
+    #[kani::proof]
+    pub fn check_f32_to_bits_from_bits() {
+        let value: f32 = kani::any();
+        let roundtrip = f32::from_bits(value.to_bits());
+        assert!(roundtrip == value || (roundtrip.is_nan() && value.is_nan()));
+        assert_eq!(roundtrip.to_bits(), value.to_bits());
+    }
 }
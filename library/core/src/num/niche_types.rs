@@ -7,7 +7,10 @@
 use crate::cmp::Ordering;
 use crate::fmt;
 use crate::hash::{Hash, Hasher};
+#[cfg(kani)]
+use crate::kani;
 use crate::marker::StructuralPartialEq;
+use crate::ub_checks::Invariant;
 
 macro_rules! define_valid_range_type {
     ($(
@@ -100,6 +103,24 @@ macro_rules! define_valid_range_type {
                 <$int as fmt::Debug>::fmt(&self.as_inner(), f)
             }
         }
+
+        #[unstable(feature = "ub_checks", issue = "none")]
+        impl Invariant for $name {
+            #[inline]
+            fn is_safe(&self) -> bool {
+                let val = self.as_inner();
+                (val as $uint) >= ($low as $uint) && (val as $uint) <= ($high as $uint)
+            }
+        }
+
+        #[cfg(kani)]
+        impl kani::Arbitrary for $name {
+            fn any() -> Self {
+                let val = kani::any_where(|val: &$int| $name::new(*val).is_some());
+                // SAFETY: just checked the inclusive range
+                unsafe { $name::new_unchecked(val) }
+            }
+        }
     )+};
 }
 
@@ -178,3 +199,49 @@ impl NotAllOnesHelper for u64 {
 impl NotAllOnesHelper for i64 {
     type Type = I64NotAllOnes;
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+
+    macro_rules! check_invariant {
+        ($($harness:ident: $name:ident),+ $(,)?) => {$(
+            #[kani::proof]
+            pub fn $harness() {
+                if let Some(value) = $name::new(kani::any()) {
+                    assert!(value.is_safe());
+                }
+                assert!(kani::any::<$name>().is_safe());
+            }
+        )+};
+    }
+
+    check_invariant! {
+        check_nanoseconds: Nanoseconds,
+        check_non_zero_u8_inner: NonZeroU8Inner,
+        check_non_zero_u16_inner: NonZeroU16Inner,
+        check_non_zero_u32_inner: NonZeroU32Inner,
+        check_non_zero_u64_inner: NonZeroU64Inner,
+        check_non_zero_u128_inner: NonZeroU128Inner,
+        check_non_zero_i8_inner: NonZeroI8Inner,
+        check_non_zero_i16_inner: NonZeroI16Inner,
+        check_non_zero_i32_inner: NonZeroI32Inner,
+        check_non_zero_i64_inner: NonZeroI64Inner,
+        check_non_zero_i128_inner: NonZeroI128Inner,
+        check_non_zero_char_inner: NonZeroCharInner,
+        check_usize_no_high_bit: UsizeNoHighBit,
+        check_non_zero_usize_inner: NonZeroUsizeInner,
+        check_non_zero_isize_inner: NonZeroIsizeInner,
+        check_u32_not_all_ones: U32NotAllOnes,
+        check_i32_not_all_ones: I32NotAllOnes,
+        check_u64_not_all_ones: U64NotAllOnes,
+        check_i64_not_all_ones: I64NotAllOnes,
+    }
+
+    #[kani::proof]
+    pub fn check_nanoseconds_zero() {
+        assert!(Nanoseconds::ZERO.is_safe());
+        assert!(Nanoseconds::default().is_safe());
+    }
+}
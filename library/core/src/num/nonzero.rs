@@ -12,6 +12,7 @@ use crate::marker::{Freeze, StructuralPartialEq};
 use crate::ops::{BitOr, BitOrAssign, Div, DivAssign, Neg, Rem, RemAssign};
 use crate::panic::{RefUnwindSafe, UnwindSafe};
 use crate::str::FromStr;
+use crate::ub_checks::Invariant;
 use crate::{fmt, intrinsics, ptr, ub_checks};
 
 /// A marker trait for primitive types which can be zero.
@@ -194,6 +195,19 @@ impl<T> UseCloned for NonZero<T> where T: ZeroablePrimitive {}
 #[stable(feature = "nonzero", since = "1.28.0")]
 impl<T> Copy for NonZero<T> where T: ZeroablePrimitive {}
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T> Invariant for NonZero<T>
+where
+    T: ZeroablePrimitive,
+    T::NonZeroInner: Invariant,
+{
+    /// The inner niche type ensures that the value is not zero.
+    #[inline]
+    fn is_safe(&self) -> bool {
+        self.0.is_safe()
+    }
+}
+
 #[stable(feature = "nonzero", since = "1.28.0")]
 impl<T> PartialEq for NonZero<T>
 where
@@ -2395,6 +2409,35 @@ mod verify {
     nonzero_check!(u128, core::num::NonZeroU128, nonzero_check_new_unchecked_for_u128);
     nonzero_check!(usize, core::num::NonZeroUsize, nonzero_check_new_unchecked_for_usize);
 
+    macro_rules! nonzero_check_invariant {
+        ($t:ty, $nonzero_check_invariant_for:ident) => {
+            #[kani::proof]
+            pub fn $nonzero_check_invariant_for() {
+                let x: $t = kani::any();
+                if let Some(nonzero) = NonZero::<$t>::new(x) {
+                    assert!(nonzero.is_safe());
+                    assert!(unsafe { NonZero::<$t>::new_unchecked(x) }.is_safe());
+                }
+                assert!(NonZero::<$t>::MIN.is_safe());
+                assert!(NonZero::<$t>::MAX.is_safe());
+                assert!(kani::any::<NonZero<$t>>().is_safe());
+            }
+        };
+    }
+
+    nonzero_check_invariant!(i8, nonzero_check_invariant_for_i8);
+    nonzero_check_invariant!(i16, nonzero_check_invariant_for_i16);
+    nonzero_check_invariant!(i32, nonzero_check_invariant_for_i32);
+    nonzero_check_invariant!(i64, nonzero_check_invariant_for_i64);
+    nonzero_check_invariant!(i128, nonzero_check_invariant_for_i128);
+    nonzero_check_invariant!(isize, nonzero_check_invariant_for_isize);
+    nonzero_check_invariant!(u8, nonzero_check_invariant_for_u8);
+    nonzero_check_invariant!(u16, nonzero_check_invariant_for_u16);
+    nonzero_check_invariant!(u32, nonzero_check_invariant_for_u32);
+    nonzero_check_invariant!(u64, nonzero_check_invariant_for_u64);
+    nonzero_check_invariant!(u128, nonzero_check_invariant_for_u128);
+    nonzero_check_invariant!(usize, nonzero_check_invariant_for_usize);
+
     macro_rules! nonzero_check_cmp {
         ($nonzero_type:ty, $nonzero_check_cmp_for:ident) => {
             #[kani::proof]
@@ -1,3 +1,6 @@
+// Used only for contract verification.
+#[allow(unused_imports)]
+use safety::forall;
 use safety::{ensures, requires};
 
 use crate::cmp::Ordering;
@@ -10,9 +13,10 @@ use crate::ops::{CoerceUnsized, DispatchFromDyn};
 use crate::pin::PinCoerceUnsized;
 use crate::ptr::Unique;
 use crate::slice::{self, SliceIndex};
-#[cfg(kani)]
+// Used only for contract verification.
+#[allow(unused_imports)]
 use crate::ub_checks;
-use crate::ub_checks::assert_unsafe_precondition;
+use crate::ub_checks::{Invariant, assert_unsafe_precondition};
 use crate::{fmt, hash, intrinsics, mem, ptr};
 
 /// `*mut T` but non-zero and [covariant].
@@ -89,6 +93,17 @@ impl<T: ?Sized> !Send for NonNull<T> {}
 #[stable(feature = "nonnull", since = "1.25.0")]
 impl<T: ?Sized> !Sync for NonNull<T> {}
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T: ?Sized> Invariant for NonNull<T> {
+    /// A `NonNull` pointer is never null, but it may dangle.
+    #[inline]
+    fn is_safe(&self) -> bool {
+        // Look at the address, since `is_null` on a pointer taken from a `NonNull` is assumed to
+        // be `false` and would not catch a null `NonNull` created by faulty unsafe code.
+        self.as_ptr().addr() != 0
+    }
+}
+
 impl<T: Sized> NonNull<T> {
     /// Creates a pointer with the given address and no [provenance][crate::ptr#provenance].
     ///
@@ -1268,7 +1283,8 @@ impl<T: ?Sized> NonNull<T> {
         ub_checks::can_write(core::ptr::slice_from_raw_parts_mut(self.as_ptr(), count))
     )]
     #[ensures(|_|
-        ub_checks::can_dereference(crate::ptr::slice_from_raw_parts(self.as_ptr() as *const u8, count * size_of::<T>())))]
+        ub_checks::can_dereference(crate::ptr::slice_from_raw_parts(self.as_ptr() as *const u8, count * size_of::<T>()))
+        && forall!(|i in (0, count * size_of::<T>())| unsafe { *(self.as_ptr() as *const u8).add(i) == val }))]
     pub const unsafe fn write_bytes(self, val: u8, count: usize)
     where
         T: Sized,
@@ -1940,6 +1956,25 @@ mod verify {
         }
     }
 
+    #[kani::proof]
+    pub fn non_null_check_invariant() {
+        let mut x: i32 = kani::any();
+        let ptr = if kani::any() { &mut x as *mut i32 } else { null_mut() };
+        if let Some(non_null) = NonNull::new(ptr) {
+            assert!(non_null.is_safe());
+        }
+        assert!(NonNull::<i32>::dangling().is_safe());
+        assert!(NonNull::<i32>::without_provenance(kani::any()).is_safe());
+        assert!(NonNull::from(&x).is_safe());
+        assert!(NonNull::from(&mut x).is_safe());
+        assert!(NonNull::from_ref(&x).cast::<u8>().is_safe());
+        assert!(NonNull::from_mut(&mut x).with_addr(kani::any()).is_safe());
+        let arr: [i32; 3] = kani::any();
+        let slice = NonNull::slice_from_raw_parts(NonNull::from(&arr).cast::<i32>(), arr.len());
+        assert!(slice.is_safe());
+        assert!(kani::any::<NonNull<i32>>().is_safe());
+    }
+
     // pub const unsafe fn new_unchecked(ptr: *mut T) -> Self
     #[kani::proof_for_contract(NonNull::new_unchecked)]
     pub fn non_null_check_new_unchecked() {
@@ -7,6 +7,7 @@ use crate::marker::{PhantomData, Unsize};
 use crate::ops::{CoerceUnsized, DispatchFromDyn};
 use crate::pin::PinCoerceUnsized;
 use crate::ptr::NonNull;
+use crate::ub_checks::Invariant;
 
 /// A wrapper around a raw non-null `*mut T` that indicates that the possessor
 /// of this wrapper owns the referent. Useful for building abstractions like
@@ -48,6 +49,15 @@ pub struct Unique<T: ?Sized> {
     _marker: PhantomData<T>,
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T: ?Sized> Invariant for Unique<T> {
+    /// A `Unique` pointer is never null, but it may dangle.
+    #[inline]
+    fn is_safe(&self) -> bool {
+        self.pointer.is_safe()
+    }
+}
+
 /// `Unique` pointers are `Send` if `T` is `Send` because the data they
 /// reference is unaliased. Note that this aliasing invariant is
 /// unenforced by the type system; the abstraction using the
@@ -245,6 +255,19 @@ mod verify {
         let _ = Unique::new(xptr as *mut i32);
     }
 
+    #[kani::proof]
+    pub fn check_invariant() {
+        let mut x: i32 = kani::any();
+        let ptr = if kani::any() { &mut x as *mut i32 } else { crate::ptr::null_mut() };
+        if let Some(unique) = Unique::new(ptr) {
+            assert!(unique.is_safe());
+        }
+        assert!(Unique::<i32>::dangling().is_safe());
+        assert!(Unique::from(&mut x).is_safe());
+        assert!(Unique::from(NonNull::from(&mut x)).is_safe());
+        assert!(Unique::from(&mut x).cast::<u8>().is_safe());
+    }
+
     // pub const fn as_ptr(self) -> *mut T
     #[kani::proof_for_contract(Unique::as_ptr)]
     pub fn check_as_ptr() {
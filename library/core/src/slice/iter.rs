@@ -3621,6 +3621,13 @@ mod verify {
                 check_unsafe_contracts!(check_post_inc_start, $ty, post_inc_start(kani::any()));
                 check_unsafe_contracts!(check_pre_dec_end, $ty, pre_dec_end(kani::any()));
 
+                #[kani::proof_for_contract(<Iter<'static, $ty> as Iterator>::__iterator_get_unchecked)]
+                fn check_iterator_get_unchecked() {
+                    let array: [$ty; MAX_LEN] = kani::any();
+                    let mut iter = any_iter::<$ty>(&array);
+                    let _ = unsafe { iter.__iterator_get_unchecked(kani::any()) };
+                }
+
                 // Public functions that call safe abstraction `make_slice`.
                 check_safe_abstraction!(check_as_slice, $ty, |iter: &mut Iter<'_, $ty>| {
                     iter.as_slice();
@@ -1,5 +1,12 @@
 //! This module and the contained sub-modules contains the code for efficient and robust sort
 //! implementations, as well as the domain adjacent implementation of `select_nth_unstable`.
+//!
+//! The Kani harnesses of these modules mostly use comparison functions that return arbitrary
+//! results, and thus need not implement a total order. Loop contracts do not keep track of the
+//! contents of the slice being sorted, so the harnesses of the functions with loop invariants only
+//! check that all accesses are in bounds. Panicking comparison functions cannot be modeled, as
+//! Kani does not support unwinding. The `sort` mode of `scripts/runtime_contracts` checks both the
+//! results and the panicking case instead, with the loop invariants checked at runtime.
 
 pub mod stable;
 pub mod unstable;
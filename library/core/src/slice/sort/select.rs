@@ -79,6 +79,7 @@ fn partition_at_index_loop<'a, T, F>(
     // with the fallback minimal in relative terms.
     let mut limit = 16;
 
+    #[safety::loop_invariant(index < v.len() && limit <= 16)]
     loop {
         if v.len() <= INSERTION_SORT_THRESHOLD {
             if v.len() >= 2 {
@@ -174,6 +175,7 @@ fn median_of_medians<T, F: FnMut(&T, &T) -> bool>(mut v: &mut [T], is_less: &mut
     debug_assert!(!T::IS_ZST);
 
     // We now know that `k < v.len() <= isize::MAX`
+    #[safety::loop_invariant(k < v.len())]
     loop {
         if v.len() <= INSERTION_SORT_THRESHOLD {
             if v.len() >= 2 {
@@ -309,3 +311,24 @@ fn median_idx<T, F: FnMut(&T, &T) -> bool>(
     }
     b
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+    use crate::slice::sort::shared::{MAX_LEN, any_len};
+
+    // The selection and insertion sort loops have loop contracts, which do not keep track of the
+    // contents of `v`: only check the bounds of the result.
+    #[kani::proof]
+    #[kani::unwind(7)]
+    fn check_partition_at_index() {
+        let mut arr: [u8; MAX_LEN] = kani::any();
+        let len = any_len();
+        let index = kani::any_where::<usize, _>(|index| *index < len);
+        let (left, _, right) = partition_at_index(&mut arr[..len], index, |_, _| kani::any());
+        assert_eq!(left.len(), index);
+        assert_eq!(right.len(), len - index - 1);
+    }
+}
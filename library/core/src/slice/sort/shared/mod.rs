@@ -33,10 +33,12 @@ pub(crate) fn find_existing_run<T, F: FnMut(&T, &T) -> bool>(
         let mut run_len = 2;
         let strictly_descending = is_less(v.get_unchecked(1), v.get_unchecked(0));
         if strictly_descending {
+            #[safety::loop_invariant(run_len >= 2 && run_len <= len)]
             while run_len < len && is_less(v.get_unchecked(run_len), v.get_unchecked(run_len - 1)) {
                 run_len += 1;
             }
         } else {
+            #[safety::loop_invariant(run_len >= 2 && run_len <= len)]
             while run_len < len && !is_less(v.get_unchecked(run_len), v.get_unchecked(run_len - 1))
             {
                 run_len += 1;
@@ -45,3 +47,40 @@ pub(crate) fn find_existing_run<T, F: FnMut(&T, &T) -> bool>(
         (run_len, strictly_descending)
     }
 }
+
+/// Checks that `a` and `b` hold the same elements, each with the same multiplicity.
+///
+/// This is quadratic in the length of the slices, and only meant for the small, bounded slices
+/// of the sort harnesses.
+#[cfg(kani)]
+pub(crate) fn is_permutation<T: PartialEq>(a: &[T], b: &[T]) -> bool {
+    let count = |s: &[T], x: &T| s.iter().filter(|y| *y == x).count();
+    a.len() == b.len() && a.iter().all(|x| count(a, x) == count(b, x))
+}
+
+/// The length of the arrays the sort harnesses take their slices from.
+#[cfg(kani)]
+pub(crate) const MAX_LEN: usize = 6;
+
+/// Generates the length of a slice for the sort harnesses, at most [`MAX_LEN`].
+#[cfg(kani)]
+pub(crate) fn any_len() -> usize {
+    crate::kani::any_where(|len: &usize| *len <= MAX_LEN)
+}
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+
+    #[kani::proof]
+    #[kani::unwind(7)]
+    fn check_find_existing_run() {
+        let arr: [u8; MAX_LEN] = kani::any();
+        let v = kani::slice::any_slice_of_array(&arr);
+        let (run_len, _) = find_existing_run(v, &mut |_, _| kani::any());
+        assert!(run_len <= v.len());
+        assert!(run_len >= 2 || run_len == v.len());
+    }
+}
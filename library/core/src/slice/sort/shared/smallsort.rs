@@ -1,7 +1,12 @@
 //! This module contains a variety of sort implementations that are optimized for small lengths.
 
+use safety::requires;
+
 use crate::mem::{self, ManuallyDrop, MaybeUninit};
 use crate::slice::sort::shared::FreezeMarker;
+// Used only for contract verification.
+#[allow(unused_imports)]
+use crate::ub_checks;
 use crate::{hint, intrinsics, ptr, slice};
 
 // It's important to differentiate between SMALL_SORT_THRESHOLD performance for
@@ -383,6 +388,12 @@ where
 /// types. `is_less` could be a huge function and we want to give the compiler an option to
 /// not inline this function. For the same reasons that this function is very perf critical
 /// it should be in the same module as the functions that use it.
+#[requires(ub_checks::can_dereference(v_base.wrapping_add(a_pos))
+    && ub_checks::can_write(v_base.wrapping_add(a_pos))
+    && ub_checks::can_dereference(v_base.wrapping_add(b_pos))
+    && ub_checks::can_write(v_base.wrapping_add(b_pos))
+    && ub_checks::same_allocation(v_base, v_base.wrapping_add(a_pos))
+    && ub_checks::same_allocation(v_base, v_base.wrapping_add(b_pos)))]
 unsafe fn swap_if_less<T, F>(v_base: *mut T, a_pos: usize, b_pos: usize, is_less: &mut F)
 where
     F: FnMut(&T, &T) -> bool,
@@ -539,6 +550,10 @@ where
 ///
 /// # Safety
 /// begin < tail and p must be valid and initialized for all begin <= p <= tail.
+#[requires(begin < tail
+    && ub_checks::same_allocation(begin, tail)
+    && ub_checks::can_dereference(closed_range(begin, tail).cast_const())
+    && ub_checks::can_write(closed_range(begin, tail)))]
 unsafe fn insert_tail<T, F: FnMut(&T, &T) -> bool>(begin: *mut T, tail: *mut T, is_less: &mut F) {
     // SAFETY: see individual comments.
     unsafe {
@@ -556,6 +571,7 @@ unsafe fn insert_tail<T, F: FnMut(&T, &T) -> bool>(begin: *mut T, tail: *mut T,
         let tmp = ManuallyDrop::new(tail.read());
         let mut gap_guard = CopyOnDrop { src: &*tmp, dst: tail, len: 1 };
 
+        #[safety::loop_invariant(begin <= sift && sift < gap_guard.dst && gap_guard.dst <= tail)]
         loop {
             // SAFETY: we move sift into the gap (which is valid), and point the
             // gap guard destination at sift, ensuring that if a panic occurs the
@@ -576,6 +592,13 @@ unsafe fn insert_tail<T, F: FnMut(&T, &T) -> bool>(begin: *mut T, tail: *mut T,
     }
 }
 
+/// Returns the elements from `begin` to `tail`, both included.
+#[allow(dead_code)] // Only used by contracts.
+fn closed_range<T>(begin: *mut T, tail: *mut T) -> *mut [T] {
+    let len = (tail.addr() - begin.addr()).checked_div(size_of::<T>()).unwrap_or(0) + 1;
+    ptr::slice_from_raw_parts_mut(begin, len)
+}
+
 /// Sort `v` assuming `v[..offset]` is already sorted.
 pub fn insertion_sort_shift_left<T, F: FnMut(&T, &T) -> bool>(
     v: &mut [T],
@@ -596,6 +619,7 @@ pub fn insertion_sort_shift_left<T, F: FnMut(&T, &T) -> bool>(
         let v_base = v.as_mut_ptr();
         let v_end = v_base.add(len);
         let mut tail = v_base.add(offset);
+        #[safety::loop_invariant(v_base < tail && tail <= v_end)]
         while tail != v_end {
             // SAFETY: v_base and tail are both valid pointers to elements, and
             // v_base < tail since we checked offset != 0.
@@ -609,6 +633,8 @@ pub fn insertion_sort_shift_left<T, F: FnMut(&T, &T) -> bool>(
 
 /// SAFETY: The caller MUST guarantee that `v_base` is valid for 4 reads and
 /// `dst` is valid for 4 writes. The result will be stored in `dst[0..4]`.
+#[requires(ub_checks::can_dereference(ptr::slice_from_raw_parts(v_base, 4))
+    && ub_checks::can_write(ptr::slice_from_raw_parts_mut(dst, 4)))]
 pub unsafe fn sort4_stable<T, F: FnMut(&T, &T) -> bool>(
     v_base: *const T,
     dst: *mut T,
@@ -865,3 +891,126 @@ pub(crate) const fn has_efficient_in_place_swap<T>() -> bool {
     // Heuristic that holds true on all tested 64-bit capable architectures.
     size_of::<T>() <= 8 // size_of::<u64>()
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::cell::Cell;
+    use crate::kani;
+    use crate::slice::sort::shared::{MAX_LEN, any_len, is_permutation};
+
+    /// A `Freeze` type that is not `Copy`.
+    struct NonCopy(u8);
+
+    #[kani::proof_for_contract(swap_if_less)]
+    #[kani::unwind(7)]
+    fn check_swap_if_less() {
+        let mut arr: [u8; MAX_LEN] = kani::any();
+        let original = arr;
+        unsafe {
+            swap_if_less(arr.as_mut_ptr(), kani::any(), kani::any(), &mut |_, _| kani::any())
+        };
+        assert!(is_permutation(&arr, &original));
+    }
+
+    // The loops of `insert_tail` and `insertion_sort_shift_left`, which all the small-sorts below
+    // use, have loop contracts. These do not keep track of the contents of `v`, so the harnesses
+    // of the functions running them only check that all accesses are in bounds.
+    #[kani::proof_for_contract(insert_tail)]
+    #[kani::unwind(7)]
+    fn check_insert_tail() {
+        let mut arr: [u8; MAX_LEN] = kani::any();
+        let begin = arr.as_mut_ptr();
+        unsafe { insert_tail(begin, begin.wrapping_add(kani::any()), &mut |_, _| kani::any()) };
+    }
+
+    #[kani::proof]
+    #[kani::unwind(7)]
+    fn check_insertion_sort_shift_left() {
+        let mut arr: [u8; MAX_LEN] = kani::any();
+        let len = any_len();
+        let offset = kani::any_where::<usize, _>(|offset| *offset != 0 && *offset <= len);
+        insertion_sort_shift_left(&mut arr[..len], offset, &mut |_, _| kani::any());
+    }
+
+    #[kani::proof_for_contract(sort4_stable)]
+    #[kani::unwind(5)]
+    fn check_sort4_stable() {
+        let src: [u8; 4] = kani::any();
+        let mut dst = MaybeUninit::<[u8; 4]>::uninit();
+        unsafe { sort4_stable(src.as_ptr(), dst.as_mut_ptr().cast(), &mut |_, _| kani::any()) };
+        assert!(is_permutation(unsafe { dst.assume_init_ref() }, &src));
+    }
+
+    #[kani::proof]
+    fn check_has_efficient_in_place_swap() {
+        assert!(has_efficient_in_place_swap::<u64>());
+        assert!(!has_efficient_in_place_swap::<[u64; 2]>());
+    }
+
+    // `Cell<u8>` is not `Freeze`, so this uses the default insertion sort.
+    #[kani::proof]
+    #[kani::unwind(7)]
+    fn check_stable_small_sort_fallback() {
+        let mut arr: [Cell<u8>; MAX_LEN] = kani::any::<[u8; MAX_LEN]>().map(Cell::new);
+        let mut scratch = [const { MaybeUninit::<Cell<u8>>::uninit() }; MAX_LEN];
+        let len = any_len();
+        <Cell<u8> as StableSmallSortTypeImpl>::small_sort(
+            &mut arr[..len],
+            &mut scratch,
+            &mut |_, _| kani::any(),
+        );
+    }
+
+    // `bidirectional_merge` panics if it detects that the comparison function is not a total
+    // order, which it may also do for a total order as the presorted halves it merges are
+    // arbitrary after the loop contracts. The only failure allowed is that panic.
+    #[kani::proof]
+    #[kani::should_panic]
+    #[kani::unwind(7)]
+    fn check_stable_small_sort_general_inconsistent() {
+        let mut arr: [u8; MAX_LEN] = kani::any();
+        let mut scratch = [MaybeUninit::<u8>::uninit(); SMALL_SORT_GENERAL_SCRATCH_LEN];
+        let len = any_len();
+        <u8 as StableSmallSortTypeImpl>::small_sort(&mut arr[..len], &mut scratch, &mut |_, _| {
+            kani::any()
+        });
+    }
+
+    // `Cell<u8>` is not `Freeze`, so this uses `small_sort_fallback`.
+    #[kani::proof]
+    #[kani::unwind(7)]
+    fn check_unstable_small_sort_fallback() {
+        let mut arr: [Cell<u8>; MAX_LEN] = kani::any::<[u8; MAX_LEN]>().map(Cell::new);
+        let len = any_len();
+        <Cell<u8> as UnstableSmallSortTypeImpl>::small_sort(&mut arr[..len], &mut |_, _| {
+            kani::any()
+        });
+    }
+
+    // `u8` is `Copy` and has an efficient in-place swap, so this uses `small_sort_network`.
+    #[kani::proof]
+    #[kani::unwind(7)]
+    fn check_unstable_small_sort_network() {
+        let mut arr: [u8; MAX_LEN] = kani::any();
+        let len = any_len();
+        <u8 as UnstableSmallSortFreezeTypeImpl>::small_sort(&mut arr[..len], &mut |_, _| {
+            kani::any()
+        });
+    }
+
+    // `NonCopy` is `Freeze` but not `Copy`, so this uses `small_sort_general`, which panics in
+    // `bidirectional_merge` like the stable one.
+    #[kani::proof]
+    #[kani::should_panic]
+    #[kani::unwind(7)]
+    fn check_unstable_small_sort_general_inconsistent() {
+        let original: [u8; MAX_LEN] = kani::any();
+        let mut arr = original.map(NonCopy);
+        let len = any_len();
+        <NonCopy as UnstableSmallSortFreezeTypeImpl>::small_sort(&mut arr[..len], &mut |_, _| {
+            kani::any()
+        });
+    }
+}
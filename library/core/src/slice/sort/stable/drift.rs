@@ -54,6 +54,7 @@ pub fn sort<T, F: FnMut(&T, &T) -> bool>(
 
     let mut scan_idx = 0;
     let mut prev_run = DriftsortRun::new_sorted(0); // Initial dummy run.
+    #[safety::loop_invariant(stack_len <= 66 && scan_idx <= len && prev_run.len() <= scan_idx)]
     loop {
         // Compute the next run and the desired depth of the merge node between
         // prev_run and next_run. On the last iteration we create a dummy run
@@ -84,6 +85,7 @@ pub fn sort<T, F: FnMut(&T, &T) -> bool>(
         //  3. The sum of all valid runs[i].len() plus prev_run.len() equals
         //     scan_idx.
         unsafe {
+            #[safety::loop_invariant(stack_len <= 66 && prev_run.len() <= scan_idx)]
             while stack_len > 1 && *desired_depths.add(stack_len - 1) >= desired_depth {
                 // Desired depth greater than the upcoming desired depth, pop
                 // left neighbor run from stack and merge into prev_run.
@@ -296,3 +298,25 @@ impl DriftsortRun {
         self.0 >> 1
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+    use crate::slice::sort::shared::smallsort::SMALL_SORT_GENERAL_SCRATCH_LEN;
+    use crate::slice::sort::shared::{MAX_LEN, any_len};
+
+    // The loops of the sort have loop contracts, which do not keep track of the contents of `v`,
+    // so the small-sort may detect an order violation even for a total order. With an arbitrary
+    // comparison function, the only failure allowed is that panic.
+    #[kani::proof]
+    #[kani::should_panic]
+    #[kani::unwind(7)]
+    fn check_sort_inconsistent() {
+        let mut arr: [u8; MAX_LEN] = kani::any();
+        let len = any_len();
+        let mut scratch = [MaybeUninit::<u8>::uninit(); SMALL_SORT_GENERAL_SCRATCH_LEN];
+        sort(&mut arr[..len], &mut scratch, kani::any(), &mut |_, _| kani::any());
+    }
+}
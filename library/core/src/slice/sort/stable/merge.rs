@@ -86,6 +86,11 @@ impl<T> MergeState<T> {
             let left = &mut self.start;
             let out = &mut self.dst;
 
+            // The gap between `out` and `right` is as long as the rest of the left run, which
+            // is what `MergeState::drop` copies into it.
+            #[safety::loop_invariant(*left <= self.end && right <= right_end
+                && (*out).cast_const() <= right
+                && right.addr() - (*out).addr() == self.end.addr() - left.addr())]
             while *left != self.end && right as *const T != right_end {
                 let consume_left = !is_less(&*right, &**left);
 
@@ -114,6 +119,12 @@ impl<T> MergeState<T> {
     ) {
         // SAFETY: See function safety comment.
         unsafe {
+            // The gap between `self.dst` and `out` is as long as the rest of the right run, which
+            // is what `MergeState::drop` copies into it.
+            #[safety::loop_invariant(left_end < self.dst.cast_const()
+                && right_end < self.end.cast_const()
+                && self.dst <= out
+                && out.addr() - self.dst.addr() == self.end.addr() - self.start.addr())]
             loop {
                 let left = self.dst.sub(1);
                 let right = self.end.sub(1);
@@ -148,3 +159,23 @@ impl<T> Drop for MergeState<T> {
         }
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+    use crate::slice::sort::shared::{MAX_LEN, any_len};
+
+    // The loops of `merge_up` and `merge_down` have loop contracts, which do not keep track of the
+    // contents of `v`: only check that all accesses are in bounds.
+    #[kani::proof]
+    #[kani::unwind(7)]
+    fn check_merge() {
+        let mut arr: [u8; MAX_LEN] = kani::any();
+        let len = any_len();
+        let scratch_len = any_len();
+        let mut scratch = [MaybeUninit::<u8>::uninit(); MAX_LEN];
+        merge(&mut arr[..len], &mut scratch[..scratch_len], kani::any(), &mut |_, _| kani::any());
+    }
+}
@@ -1,5 +1,7 @@
 //! This module contains a branchless heapsort as fallback for unstable quicksort.
 
+use safety::requires;
+
 use crate::{cmp, intrinsics, ptr};
 
 /// Sorts `v` using heapsort, which guarantees *O*(*n* \* log(*n*)) worst-case.
@@ -13,6 +15,7 @@ where
 {
     let len = v.len();
 
+    #[safety::loop_invariant(v.len() == len)]
     for i in (0..len + len / 2).rev() {
         let sift_idx = if i >= len {
             i - len
@@ -34,6 +37,7 @@ where
 //
 // SAFETY: The caller has to guarantee that `node <= v.len()`.
 #[inline(always)]
+#[requires(node <= v.len())]
 unsafe fn sift_down<T, F>(v: &mut [T], mut node: usize, is_less: &mut F)
 where
     F: FnMut(&T, &T) -> bool,
@@ -47,6 +51,7 @@ where
 
     let v_base = v.as_mut_ptr();
 
+    #[safety::loop_invariant(node <= len)]
     loop {
         // Children of `node`.
         let mut child = 2 * node + 1;
@@ -75,3 +80,29 @@ where
         node = child;
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+    use crate::slice::sort::shared::{MAX_LEN, any_len};
+
+    // The loops of `heapsort` and `sift_down` have loop contracts, which do not keep track of the
+    // contents of `v`: only check that all accesses are in bounds.
+    #[kani::proof_for_contract(sift_down)]
+    #[kani::unwind(7)]
+    fn check_sift_down() {
+        let mut arr: [u8; MAX_LEN] = kani::any();
+        let len = any_len();
+        unsafe { sift_down(&mut arr[..len], kani::any(), &mut |_, _| kani::any()) };
+    }
+
+    #[kani::proof]
+    #[kani::unwind(10)]
+    fn check_heapsort() {
+        let mut arr: [u8; MAX_LEN] = kani::any();
+        let len = any_len();
+        heapsort(&mut arr[..len], &mut |_, _| kani::any());
+    }
+}
@@ -190,13 +190,20 @@ where
         let mut left = v_base;
         let mut right = v_base.add(len);
 
+        #[safety::loop_invariant(v_base <= left && left <= right
+            && v_base < right && right <= v_base.add(len)
+            && gap_opt.as_ref().is_none_or(|gap| right <= gap.pos && gap.pos < v_base.add(len)))]
         loop {
             // Find the first element greater than the pivot.
+            #[safety::loop_invariant(v_base <= left && left <= right
+                && v_base < right && right <= v_base.add(len))]
             while left < right && is_less(&*left, pivot) {
                 left = left.add(1);
             }
 
             // Find the last element equal to the pivot.
+            #[safety::loop_invariant(v_base <= left && left <= right
+                && v_base < right && right <= v_base.add(len))]
             loop {
                 right = right.sub(1);
                 if left >= right || is_less(&*right, pivot) {
@@ -392,3 +399,37 @@ impl<T> Drop for GapGuardRaw<T> {
         }
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+    use crate::slice::sort::shared::{MAX_LEN, any_len, is_permutation};
+
+    // `u8` is small, so this uses the branchless Lomuto partition.
+    #[kani::proof]
+    #[kani::unwind(7)]
+    fn check_partition() {
+        let mut arr: [u8; MAX_LEN] = kani::any();
+        let original = arr;
+        let len = any_len();
+        let pivot = kani::any_where::<usize, _>(|pivot| *pivot < len);
+        let num_lt = partition(&mut arr[..len], pivot, &mut |_, _| kani::any());
+        assert!(num_lt < len);
+        assert!(is_permutation(&arr[..len], &original[..len]));
+    }
+
+    // The loops of the Hoare partition have loop contracts, which do not keep track of the
+    // contents of `v`: only check that all accesses are in bounds.
+    #[kani::proof]
+    #[kani::unwind(7)]
+    fn check_partition_hoare_branchy_cyclic() {
+        let mut arr: [[u8; 100]; MAX_LEN] = kani::any();
+        let pivot: [u8; 100] = kani::any();
+        let len = any_len();
+        let num_lt =
+            partition_hoare_branchy_cyclic(&mut arr[..len], &pivot, &mut |_, _| kani::any());
+        assert!(num_lt <= len);
+    }
+}
@@ -211,7 +211,7 @@ impl<'a> Iterator for Utf8Chunks<'a> {
         // TODO: remove `LEN` and use `self.source.len()` directly once
         // fix the issue that Kani loop contracts doesn't support `self`.
         // Tracked in https://github.com/model-checking/kani/issues/3700
-        #[cfg(kani)]
+        #[allow(non_snake_case, unused_variables)]
         let LEN = self.source.len();
         #[safety::loop_invariant(i <= LEN && valid_up_to == i)]
         while i < self.source.len() {
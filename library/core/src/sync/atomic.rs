@@ -242,10 +242,15 @@
 // are just normal values that get loaded/stored, but not dereferenced.
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
+use safety::requires;
+
 use self::Ordering::*;
 use crate::cell::UnsafeCell;
 use crate::hint::spin_loop;
 use crate::intrinsics::AtomicOrdering as AO;
+// Used only for contract verification.
+#[allow(unused_imports)]
+use crate::ub_checks;
 use crate::{fmt, intrinsics};
 
 trait Sealed {}
@@ -571,6 +576,7 @@ impl AtomicBool {
     #[inline]
     #[stable(feature = "atomic_from_ptr", since = "1.75.0")]
     #[rustc_const_stable(feature = "const_atomic_from_ptr", since = "1.84.0")]
+    #[requires(is_valid_atomic_ptr(ptr, align_of::<AtomicBool>()))]
     pub const unsafe fn from_ptr<'a>(ptr: *mut bool) -> &'a AtomicBool {
         // SAFETY: guaranteed by the caller
         unsafe { &*ptr.cast() }
@@ -1527,6 +1533,7 @@ impl<T> AtomicPtr<T> {
     #[inline]
     #[stable(feature = "atomic_from_ptr", since = "1.75.0")]
     #[rustc_const_stable(feature = "const_atomic_from_ptr", since = "1.84.0")]
+    #[requires(is_valid_atomic_ptr(ptr, align_of::<AtomicPtr<T>>()))]
     pub const unsafe fn from_ptr<'a>(ptr: *mut *mut T) -> &'a AtomicPtr<T> {
         // SAFETY: guaranteed by the caller
         unsafe { &*ptr.cast() }
@@ -2704,6 +2711,7 @@ macro_rules! atomic_int {
             #[inline]
             #[stable(feature = "atomic_from_ptr", since = "1.75.0")]
             #[rustc_const_stable(feature = "const_atomic_from_ptr", since = "1.84.0")]
+            #[requires(is_valid_atomic_ptr(ptr, align_of::<$atomic_type>()))]
             pub const unsafe fn from_ptr<'a>(ptr: *mut $int_type) -> &'a $atomic_type {
                 // SAFETY: guaranteed by the caller
                 unsafe { &*ptr.cast() }
@@ -3923,8 +3931,17 @@ fn strongest_failure_ordering(order: Ordering) -> Ordering {
     }
 }
 
+/// Checks that `ptr` can be accessed atomically with alignment `align`: it is aligned, and valid
+/// for both reads and writes. Mixing atomic accesses of different sizes, or atomic and non-atomic
+/// accesses, cannot be checked here.
+#[allow(dead_code)] // Only used by contracts.
+fn is_valid_atomic_ptr<T>(ptr: *mut T, align: usize) -> bool {
+    ptr.is_aligned_to(align) && ub_checks::can_dereference(ptr) && ub_checks::can_write(ptr)
+}
+
 #[inline]
 #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
+#[requires(is_valid_atomic_ptr(dst, size_of::<T>()) && !matches!(order, Acquire | AcqRel))]
 unsafe fn atomic_store<T: Copy>(dst: *mut T, val: T, order: Ordering) {
     // SAFETY: the caller must uphold the safety contract for `atomic_store`.
     unsafe {
@@ -3940,6 +3957,7 @@ unsafe fn atomic_store<T: Copy>(dst: *mut T, val: T, order: Ordering) {
 
 #[inline]
 #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
+#[requires(is_valid_atomic_ptr(dst.cast_mut(), size_of::<T>()) && !matches!(order, Release | AcqRel))]
 unsafe fn atomic_load<T: Copy>(dst: *const T, order: Ordering) -> T {
     // SAFETY: the caller must uphold the safety contract for `atomic_load`.
     unsafe {
@@ -3956,6 +3974,7 @@ unsafe fn atomic_load<T: Copy>(dst: *const T, order: Ordering) -> T {
 #[inline]
 #[cfg(target_has_atomic)]
 #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
+#[requires(is_valid_atomic_ptr(dst, size_of::<T>()))]
 unsafe fn atomic_swap<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
     // SAFETY: the caller must uphold the safety contract for `atomic_swap`.
     unsafe {
@@ -3973,6 +3992,7 @@ unsafe fn atomic_swap<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
 #[inline]
 #[cfg(target_has_atomic)]
 #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
+#[requires(is_valid_atomic_ptr(dst, size_of::<T>()))]
 unsafe fn atomic_add<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
     // SAFETY: the caller must uphold the safety contract for `atomic_add`.
     unsafe {
@@ -3990,6 +4010,7 @@ unsafe fn atomic_add<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
 #[inline]
 #[cfg(target_has_atomic)]
 #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
+#[requires(is_valid_atomic_ptr(dst, size_of::<T>()))]
 unsafe fn atomic_sub<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
     // SAFETY: the caller must uphold the safety contract for `atomic_sub`.
     unsafe {
@@ -4009,6 +4030,7 @@ unsafe fn atomic_sub<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
 #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
 #[unstable(feature = "core_intrinsics", issue = "none")]
 #[doc(hidden)]
+#[requires(is_valid_atomic_ptr(dst, size_of::<T>()) && !matches!(failure, Release | AcqRel))]
 pub unsafe fn atomic_compare_exchange<T: Copy>(
     dst: *mut T,
     old: T,
@@ -4074,6 +4096,7 @@ pub unsafe fn atomic_compare_exchange<T: Copy>(
 #[inline]
 #[cfg(target_has_atomic)]
 #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
+#[requires(is_valid_atomic_ptr(dst, size_of::<T>()) && !matches!(failure, Release | AcqRel))]
 unsafe fn atomic_compare_exchange_weak<T: Copy>(
     dst: *mut T,
     old: T,
@@ -4139,6 +4162,7 @@ unsafe fn atomic_compare_exchange_weak<T: Copy>(
 #[inline]
 #[cfg(target_has_atomic)]
 #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
+#[requires(is_valid_atomic_ptr(dst, size_of::<T>()))]
 unsafe fn atomic_and<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
     // SAFETY: the caller must uphold the safety contract for `atomic_and`
     unsafe {
@@ -4155,6 +4179,7 @@ unsafe fn atomic_and<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
 #[inline]
 #[cfg(target_has_atomic)]
 #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
+#[requires(is_valid_atomic_ptr(dst, size_of::<T>()))]
 unsafe fn atomic_nand<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
     // SAFETY: the caller must uphold the safety contract for `atomic_nand`
     unsafe {
@@ -4171,6 +4196,7 @@ unsafe fn atomic_nand<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
 #[inline]
 #[cfg(target_has_atomic)]
 #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
+#[requires(is_valid_atomic_ptr(dst, size_of::<T>()))]
 unsafe fn atomic_or<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
     // SAFETY: the caller must uphold the safety contract for `atomic_or`
     unsafe {
@@ -4187,6 +4213,7 @@ unsafe fn atomic_or<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
 #[inline]
 #[cfg(target_has_atomic)]
 #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
+#[requires(is_valid_atomic_ptr(dst, size_of::<T>()))]
 unsafe fn atomic_xor<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
     // SAFETY: the caller must uphold the safety contract for `atomic_xor`
     unsafe {
@@ -4204,6 +4231,7 @@ unsafe fn atomic_xor<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
 #[inline]
 #[cfg(target_has_atomic)]
 #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
+#[requires(is_valid_atomic_ptr(dst, size_of::<T>()))]
 unsafe fn atomic_max<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
     // SAFETY: the caller must uphold the safety contract for `atomic_max`
     unsafe {
@@ -4221,6 +4249,7 @@ unsafe fn atomic_max<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
 #[inline]
 #[cfg(target_has_atomic)]
 #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
+#[requires(is_valid_atomic_ptr(dst, size_of::<T>()))]
 unsafe fn atomic_min<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
     // SAFETY: the caller must uphold the safety contract for `atomic_min`
     unsafe {
@@ -4238,6 +4267,7 @@ unsafe fn atomic_min<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
 #[inline]
 #[cfg(target_has_atomic)]
 #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
+#[requires(is_valid_atomic_ptr(dst, size_of::<T>()))]
 unsafe fn atomic_umax<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
     // SAFETY: the caller must uphold the safety contract for `atomic_umax`
     unsafe {
@@ -4255,6 +4285,7 @@ unsafe fn atomic_umax<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
 #[inline]
 #[cfg(target_has_atomic)]
 #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
+#[requires(is_valid_atomic_ptr(dst, size_of::<T>()))]
 unsafe fn atomic_umin<T: Copy>(dst: *mut T, val: T, order: Ordering) -> T {
     // SAFETY: the caller must uphold the safety contract for `atomic_umin`
     unsafe {
@@ -4477,3 +4508,396 @@ impl<T> fmt::Pointer for AtomicPtr<T> {
 pub fn spin_loop_hint() {
     spin_loop()
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::{kani, ptr};
+
+    fn any_ordering() -> Ordering {
+        match kani::any_where(|order: &u8| *order < 5) {
+            0 => Relaxed,
+            1 => Release,
+            2 => Acquire,
+            3 => AcqRel,
+            _ => SeqCst,
+        }
+    }
+
+    /// Returns an arbitrary ordering that is valid for loads, and for the failure case of
+    /// compare-exchange operations.
+    fn any_load_ordering() -> Ordering {
+        let order = any_ordering();
+        kani::assume(!matches!(order, Release | AcqRel));
+        order
+    }
+
+    fn any_store_ordering() -> Ordering {
+        let order = any_ordering();
+        kani::assume(!matches!(order, Acquire | AcqRel));
+        order
+    }
+
+    /// Returns an arbitrary address, which may or may not point into `buf`.
+    fn any_ptr<T>(buf: &mut [u8; 4]) -> *mut T {
+        if kani::any() {
+            ptr::without_provenance_mut(kani::any())
+        } else {
+            buf.as_mut_ptr().wrapping_add(kani::any_where(|offset: &usize| *offset <= 4)).cast()
+        }
+    }
+
+    macro_rules! check_atomic_op {
+        ($($harness:ident: $op:ident::<$ty:ty>($($arg:expr),*);)*) => {$(
+            #[kani::proof_for_contract($op::<$ty>)]
+            fn $harness() {
+                let mut val: $ty = kani::any();
+                let _ = unsafe { $op(&raw mut val, $($arg),*) };
+            }
+        )*};
+    }
+
+    check_atomic_op! {
+        check_atomic_store: atomic_store::<u32>(kani::any(), any_ordering());
+        check_atomic_load: atomic_load::<u32>(any_ordering());
+        check_atomic_swap: atomic_swap::<u32>(kani::any(), any_ordering());
+        check_atomic_add: atomic_add::<u32>(kani::any(), any_ordering());
+        check_atomic_sub: atomic_sub::<u32>(kani::any(), any_ordering());
+        check_atomic_compare_exchange: atomic_compare_exchange::<u32>(
+            kani::any(),
+            kani::any(),
+            any_ordering(),
+            any_ordering()
+        );
+        check_atomic_compare_exchange_weak: atomic_compare_exchange_weak::<u32>(
+            kani::any(),
+            kani::any(),
+            any_ordering(),
+            any_ordering()
+        );
+        check_atomic_and: atomic_and::<u32>(kani::any(), any_ordering());
+        check_atomic_nand: atomic_nand::<u32>(kani::any(), any_ordering());
+        check_atomic_or: atomic_or::<u32>(kani::any(), any_ordering());
+        check_atomic_xor: atomic_xor::<u32>(kani::any(), any_ordering());
+        check_atomic_max: atomic_max::<i32>(kani::any(), any_ordering());
+        check_atomic_min: atomic_min::<i32>(kani::any(), any_ordering());
+        check_atomic_umax: atomic_umax::<u32>(kani::any(), any_ordering());
+        check_atomic_umin: atomic_umin::<u32>(kani::any(), any_ordering());
+    }
+
+    macro_rules! check_atomic_int {
+        ($($(#[$cfg:meta])* $mod:ident: $atomic:ident($int:ty), $cfg_align:meta;)*) => {$(
+            $(#[$cfg])*
+            mod $mod {
+                use super::*;
+
+                #[kani::proof_for_contract($atomic::from_ptr)]
+                fn check_from_ptr() {
+                    let val: $int = kani::any();
+                    let storage = $atomic::new(val);
+                    let inner = storage.as_ptr();
+                    let atomic = unsafe { $atomic::from_ptr(inner) };
+                    assert_eq!(atomic.as_ptr(), inner);
+                    assert_eq!(atomic.load(any_load_ordering()), val);
+                }
+
+                #[kani::proof]
+                fn check_read_modify_write() {
+                    let val: $int = kani::any();
+                    let arg: $int = kani::any();
+                    let atomic = $atomic::new(val);
+                    let order = any_ordering();
+                    let (prev, expected) = match kani::any_where(|op: &u8| *op < 10) {
+                        0 => (atomic.swap(arg, order), arg),
+                        1 => (atomic.fetch_add(arg, order), val.wrapping_add(arg)),
+                        2 => (atomic.fetch_sub(arg, order), val.wrapping_sub(arg)),
+                        3 => (atomic.fetch_and(arg, order), val & arg),
+                        4 => (atomic.fetch_nand(arg, order), !(val & arg)),
+                        5 => (atomic.fetch_or(arg, order), val | arg),
+                        6 => (atomic.fetch_xor(arg, order), val ^ arg),
+                        7 => (atomic.fetch_max(arg, order), val.max(arg)),
+                        8 => (atomic.fetch_min(arg, order), val.min(arg)),
+                        _ => {
+                            atomic.store(arg, any_store_ordering());
+                            (val, arg)
+                        }
+                    };
+                    assert_eq!(prev, val);
+                    assert_eq!(atomic.load(any_load_ordering()), expected);
+                }
+
+                #[kani::proof]
+                #[kani::unwind(3)]
+                fn check_fetch_update() {
+                    let val: $int = kani::any();
+                    let next: Option<$int> = kani::any();
+                    let atomic = $atomic::new(val);
+                    let result = atomic.fetch_update(any_ordering(), any_load_ordering(), |_| next);
+                    match next {
+                        Some(next) => {
+                            assert_eq!(result, Ok(val));
+                            assert_eq!(atomic.load(any_load_ordering()), next);
+                        }
+                        None => assert_eq!(result, Err(val)),
+                    }
+                }
+
+                #[kani::proof]
+                #[kani::unwind(3)]
+                fn check_compare_exchange_weak_loop() {
+                    let val: $int = kani::any();
+                    let atomic = $atomic::new(val);
+                    let mut current = atomic.load(any_load_ordering());
+                    loop {
+                        let new = current.wrapping_add(1);
+                        match atomic.compare_exchange_weak(
+                            current,
+                            new,
+                            any_ordering(),
+                            any_load_ordering(),
+                        ) {
+                            Ok(_) => break,
+                            Err(actual) => current = actual,
+                        }
+                    }
+                    assert_eq!(atomic.load(any_load_ordering()), val.wrapping_add(1));
+                }
+
+                #[$cfg_align]
+                #[kani::proof]
+                fn check_mut_slice() {
+                    let mut vals: [$int; 2] = kani::any();
+                    let new: $int = kani::any();
+                    let atomics = $atomic::from_mut_slice(&mut vals);
+                    atomics[0].store(new, any_store_ordering());
+                    let ints = $atomic::get_mut_slice(atomics);
+                    assert_eq!(ints.len(), 2);
+                    assert_eq!(ints[0], new);
+                }
+            }
+        )*};
+    }
+
+    check_atomic_int! {
+        #[cfg(target_has_atomic = "8")]
+        atomic_i8: AtomicI8(i8), cfg(target_has_atomic_equal_alignment = "8");
+        #[cfg(target_has_atomic = "8")]
+        atomic_u8: AtomicU8(u8), cfg(target_has_atomic_equal_alignment = "8");
+        #[cfg(target_has_atomic = "16")]
+        atomic_i16: AtomicI16(i16), cfg(target_has_atomic_equal_alignment = "16");
+        #[cfg(target_has_atomic = "16")]
+        atomic_u16: AtomicU16(u16), cfg(target_has_atomic_equal_alignment = "16");
+        #[cfg(target_has_atomic = "32")]
+        atomic_i32: AtomicI32(i32), cfg(target_has_atomic_equal_alignment = "32");
+        #[cfg(target_has_atomic = "32")]
+        atomic_u32: AtomicU32(u32), cfg(target_has_atomic_equal_alignment = "32");
+        #[cfg(target_has_atomic = "64")]
+        atomic_i64: AtomicI64(i64), cfg(target_has_atomic_equal_alignment = "64");
+        #[cfg(target_has_atomic = "64")]
+        atomic_u64: AtomicU64(u64), cfg(target_has_atomic_equal_alignment = "64");
+        #[cfg(target_has_atomic = "128")]
+        atomic_i128: AtomicI128(i128), cfg(target_has_atomic_equal_alignment = "128");
+        #[cfg(target_has_atomic = "128")]
+        atomic_u128: AtomicU128(u128), cfg(target_has_atomic_equal_alignment = "128");
+        #[cfg(target_has_atomic = "ptr")]
+        atomic_isize: AtomicIsize(isize), cfg(target_has_atomic_equal_alignment = "ptr");
+        #[cfg(target_has_atomic = "ptr")]
+        atomic_usize: AtomicUsize(usize), cfg(target_has_atomic_equal_alignment = "ptr");
+    }
+
+    #[cfg(target_has_atomic = "8")]
+    mod atomic_bool {
+        use super::*;
+
+        #[kani::proof_for_contract(AtomicBool::from_ptr)]
+        fn check_from_ptr() {
+            let val: bool = kani::any();
+            let storage = AtomicBool::new(val);
+            let inner = storage.as_ptr();
+            let atomic = unsafe { AtomicBool::from_ptr(inner) };
+            assert_eq!(atomic.as_ptr(), inner);
+            assert_eq!(atomic.load(any_load_ordering()), val);
+        }
+
+        #[kani::proof]
+        fn check_read_modify_write() {
+            let val: bool = kani::any();
+            let arg: bool = kani::any();
+            let atomic = AtomicBool::new(val);
+            let order = any_ordering();
+            let (prev, expected) = match kani::any_where(|op: &u8| *op < 6) {
+                0 => (atomic.swap(arg, order), arg),
+                1 => (atomic.fetch_and(arg, order), val & arg),
+                2 => (atomic.fetch_nand(arg, order), !(val & arg)),
+                3 => (atomic.fetch_or(arg, order), val | arg),
+                4 => (atomic.fetch_xor(arg, order), val ^ arg),
+                _ => (atomic.fetch_not(order), !val),
+            };
+            assert_eq!(prev, val);
+            assert_eq!(atomic.load(any_load_ordering()), expected);
+        }
+
+        #[kani::proof]
+        #[kani::unwind(3)]
+        fn check_fetch_update() {
+            let val: bool = kani::any();
+            let next: Option<bool> = kani::any();
+            let atomic = AtomicBool::new(val);
+            let result = atomic.fetch_update(any_ordering(), any_load_ordering(), |_| next);
+            match next {
+                Some(next) => {
+                    assert_eq!(result, Ok(val));
+                    assert_eq!(atomic.load(any_load_ordering()), next);
+                }
+                None => assert_eq!(result, Err(val)),
+            }
+        }
+
+        #[cfg(target_has_atomic_equal_alignment = "8")]
+        #[kani::proof]
+        fn check_mut_slice() {
+            let mut vals: [bool; 2] = kani::any();
+            let new: bool = kani::any();
+            let atomics = AtomicBool::from_mut_slice(&mut vals);
+            atomics[1].store(new, any_store_ordering());
+            let bools = AtomicBool::get_mut_slice(atomics);
+            assert_eq!(bools.len(), 2);
+            assert_eq!(bools[1], new);
+        }
+    }
+
+    #[cfg(target_has_atomic = "ptr")]
+    mod atomic_ptr {
+        use super::*;
+
+        /// The value of an `AtomicPtr<T>` is an arbitrary address, whatever the size of `T`.
+        fn check_from_ptr<T>() {
+            let mut buf = [0; 4];
+            let val = any_ptr::<T>(&mut buf);
+            let storage = AtomicPtr::new(val);
+            let inner = storage.as_ptr();
+            let atomic = unsafe { AtomicPtr::from_ptr(inner) };
+            assert_eq!(atomic.as_ptr(), inner);
+            assert_eq!(atomic.load(any_load_ordering()), val);
+        }
+
+        #[kani::proof_for_contract(AtomicPtr::<()>::from_ptr)]
+        fn check_from_ptr_zst() {
+            check_from_ptr::<()>();
+        }
+
+        #[kani::proof_for_contract(AtomicPtr::<u8>::from_ptr)]
+        fn check_from_ptr_u8() {
+            check_from_ptr::<u8>();
+        }
+
+        #[kani::proof_for_contract(AtomicPtr::<u16>::from_ptr)]
+        fn check_from_ptr_u16() {
+            check_from_ptr::<u16>();
+        }
+
+        #[kani::proof_for_contract(AtomicPtr::<u32>::from_ptr)]
+        fn check_from_ptr_u32() {
+            check_from_ptr::<u32>();
+        }
+
+        #[kani::proof_for_contract(AtomicPtr::<[u8; 3]>::from_ptr)]
+        fn check_from_ptr_u8_array() {
+            check_from_ptr::<[u8; 3]>();
+        }
+
+        /// The pointer operations only do wrapping address arithmetic, so they are safe for any
+        /// address and any offset.
+        fn check_arithmetic<T>() {
+            let mut buf = [0; 4];
+            let val = any_ptr::<T>(&mut buf);
+            let arg: usize = kani::any();
+            let atomic = AtomicPtr::new(val);
+            let order = any_ordering();
+            let (prev, expected) = match kani::any_where(|op: &u8| *op < 7) {
+                0 => (atomic.fetch_ptr_add(arg, order), val.wrapping_add(arg)),
+                1 => (atomic.fetch_ptr_sub(arg, order), val.wrapping_sub(arg)),
+                2 => (atomic.fetch_byte_add(arg, order), val.wrapping_byte_add(arg)),
+                3 => (atomic.fetch_byte_sub(arg, order), val.wrapping_byte_sub(arg)),
+                4 => (atomic.fetch_or(arg, order), val.map_addr(|addr| addr | arg)),
+                5 => (atomic.fetch_and(arg, order), val.map_addr(|addr| addr & arg)),
+                _ => (atomic.fetch_xor(arg, order), val.map_addr(|addr| addr ^ arg)),
+            };
+            assert_eq!(prev, val);
+            assert_eq!(atomic.load(any_load_ordering()), expected);
+        }
+
+        #[kani::proof]
+        fn check_arithmetic_zst() {
+            check_arithmetic::<()>();
+        }
+
+        #[kani::proof]
+        fn check_arithmetic_u8() {
+            check_arithmetic::<u8>();
+        }
+
+        #[kani::proof]
+        fn check_arithmetic_u32() {
+            check_arithmetic::<u32>();
+        }
+
+        #[kani::proof]
+        fn check_arithmetic_u8_array() {
+            check_arithmetic::<[u8; 3]>();
+        }
+
+        #[kani::proof]
+        #[kani::unwind(3)]
+        fn check_fetch_update() {
+            let mut buf = [0; 4];
+            let val = any_ptr::<u8>(&mut buf);
+            let next = if kani::any() { Some(any_ptr::<u8>(&mut buf)) } else { None };
+            let atomic = AtomicPtr::new(val);
+            let result = atomic.fetch_update(any_ordering(), any_load_ordering(), |_| next);
+            match next {
+                Some(next) => {
+                    assert_eq!(result, Ok(val));
+                    assert_eq!(atomic.load(any_load_ordering()), next);
+                }
+                None => assert_eq!(result, Err(val)),
+            }
+        }
+
+        #[kani::proof]
+        #[kani::unwind(3)]
+        fn check_compare_exchange_weak_loop() {
+            let mut buf = [0; 4];
+            let val = any_ptr::<u8>(&mut buf);
+            let atomic = AtomicPtr::new(val);
+            let mut current = atomic.load(any_load_ordering());
+            loop {
+                let new = current.wrapping_add(1);
+                match atomic.compare_exchange_weak(
+                    current,
+                    new,
+                    any_ordering(),
+                    any_load_ordering(),
+                ) {
+                    Ok(_) => break,
+                    Err(actual) => current = actual,
+                }
+            }
+            assert_eq!(atomic.load(any_load_ordering()), val.wrapping_add(1));
+        }
+
+        #[cfg(target_has_atomic_equal_alignment = "ptr")]
+        #[kani::proof]
+        fn check_mut_slice() {
+            let mut buf = [0; 4];
+            let mut ptrs = [any_ptr::<u8>(&mut buf), any_ptr::<u8>(&mut buf)];
+            let new = any_ptr::<u8>(&mut buf);
+            let atomics = AtomicPtr::from_mut_slice(&mut ptrs);
+            atomics[0].store(new, any_store_ordering());
+            let ptrs = AtomicPtr::get_mut_slice(atomics);
+            assert_eq!(ptrs.len(), 2);
+            assert_eq!(ptrs[0], new);
+        }
+    }
+}
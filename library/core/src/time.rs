@@ -27,6 +27,8 @@ use crate::iter::Sum;
 use crate::kani;
 use crate::num::niche_types::Nanoseconds;
 use crate::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+// Used only for contract verification.
+#[allow(unused_imports)]
 use crate::ub_checks::Invariant;
 
 const NANOS_PER_SEC: u32 = 1_000_000_000;
@@ -43,13 +45,6 @@ const HOURS_PER_DAY: u64 = 24;
 #[unstable(feature = "duration_units", issue = "120301")]
 const DAYS_PER_WEEK: u64 = 7;
 
-#[unstable(feature = "ub_checks", issue = "none")]
-impl Invariant for Nanoseconds {
-    fn is_safe(&self) -> bool {
-        self.as_inner() < NANOS_PER_SEC
-    }
-}
-
 /// A `Duration` type to represent a span of time, typically used for system
 /// timeouts.
 ///
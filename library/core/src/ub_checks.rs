@@ -171,6 +171,65 @@ pub(crate) const fn maybe_is_nonoverlapping(
     )
 }
 
+/// Checks a `#[requires]` or `#[loop_invariant]` condition of the `safety` contracts.
+///
+/// Calls to this function are only emitted by the runtime mode of the `safety` contract macros.
+/// Like [`assert_unsafe_precondition`], the check only runs when library UB checks are enabled
+/// when the caller is monomorphized, and it is skipped during const-eval.
+#[doc(hidden)]
+#[inline]
+#[rustc_const_stable_indirect]
+#[rustc_allow_const_fn_unstable(const_eval_select)]
+pub const fn check_contract<C: Fn() -> bool + Copy>(cond: C, msg: &'static str) {
+    const_eval_select!(
+        @capture[C: Fn() -> bool + Copy] { cond: C, msg: &'static str } :
+        if const {
+            // Contracts are not checked during const-eval.
+        } else {
+            if check_library_ub() && !cond() {
+                crate::panicking::panic_nounwind(msg);
+            }
+        }
+    )
+}
+
+/// Checks a `#[ensures]` condition of the `safety` contracts against the value `ret` that is
+/// about to be returned, and passes `ret` through.
+///
+/// See [`check_contract`] for when the check runs.
+#[doc(hidden)]
+#[inline]
+#[rustc_const_stable_indirect]
+#[rustc_allow_const_fn_unstable(const_eval_select)]
+pub const fn check_contract_ensures<Ret, C: Fn(&Ret) -> bool + Copy>(
+    ret: Ret,
+    cond: C,
+    msg: &'static str,
+) -> Ret {
+    const_eval_select!(
+        @capture[Ret, C: Fn(&Ret) -> bool + Copy] { ret: Ret, cond: C, msg: &'static str } -> Ret :
+        if const {
+            ret
+        } else {
+            if check_library_ub() && !cond(&ret) {
+                crate::panicking::panic_nounwind(msg);
+            }
+            ret
+        }
+    )
+}
+
+/// Takes the snapshot of an `old(expr)` of a `#[ensures]` condition of the `safety` contracts.
+///
+/// The snapshot is a copy, so that taking it neither moves out of nor borrows the value that the
+/// function body may go on to consume or mutate.
+#[doc(hidden)]
+#[inline]
+#[rustc_const_stable_indirect]
+pub const fn contract_old<T: Copy>(value: &T) -> T {
+    *value
+}
+
 pub use predicates::*;
 
 /// Provide a few predicates to be used in safety contracts.
@@ -292,3 +351,167 @@ trivial_invariant!(f16);
 trivial_invariant!(f32);
 trivial_invariant!(f64);
 trivial_invariant!(f128);
+
+/// A `&str` must point to valid UTF-8.
+#[unstable(feature = "ub_checks", issue = "none")]
+impl Invariant for &str {
+    #[inline]
+    fn is_safe(&self) -> bool {
+        crate::str::from_utf8(self.as_bytes()).is_ok()
+    }
+}
+
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T: ?Sized> Invariant for crate::marker::PhantomData<T> {
+    #[inline(always)]
+    fn is_safe(&self) -> bool {
+        true
+    }
+}
+
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T: Invariant> Invariant for Option<T> {
+    #[inline]
+    fn is_safe(&self) -> bool {
+        match self {
+            Some(value) => value.is_safe(),
+            None => true,
+        }
+    }
+}
+
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T: Invariant, E: Invariant> Invariant for Result<T, E> {
+    #[inline]
+    fn is_safe(&self) -> bool {
+        match self {
+            Ok(value) => value.is_safe(),
+            Err(error) => error.is_safe(),
+        }
+    }
+}
+
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T: Invariant, const N: usize> Invariant for [T; N] {
+    #[inline]
+    fn is_safe(&self) -> bool {
+        self.iter().all(Invariant::is_safe)
+    }
+}
+
+/// A tuple is safe if all its elements are safe.
+macro_rules! tuple_invariant {
+    ( $( $name:ident )+ ) => {
+        #[unstable(feature = "ub_checks", issue = "none")]
+        impl<$($name: Invariant),+> Invariant for ($($name,)+) {
+            #[inline]
+            #[allow(non_snake_case)]
+            fn is_safe(&self) -> bool {
+                let ($($name,)+) = self;
+                true $(&& $name.is_safe())+
+            }
+        }
+    };
+}
+
+tuple_invariant!(A);
+tuple_invariant!(A B);
+tuple_invariant!(A B C);
+tuple_invariant!(A B C D);
+tuple_invariant!(A B C D E);
+tuple_invariant!(A B C D E F);
+tuple_invariant!(A B C D E F G);
+tuple_invariant!(A B C D E F G H);
+tuple_invariant!(A B C D E F G H I);
+tuple_invariant!(A B C D E F G H I J);
+tuple_invariant!(A B C D E F G H I J K);
+tuple_invariant!(A B C D E F G H I J K L);
+
+/// Generates a symbolic value of type `T` that satisfies the safety invariant of `T`.
+///
+/// This should be preferred over `kani::any()` followed by an assumption in harnesses, when `T`
+/// has a safety invariant that is stronger than its validity invariant.
+#[cfg(kani)]
+pub fn any_safe<T: crate::kani::Arbitrary + Invariant>() -> T {
+    crate::kani::any_where(|value: &T| value.is_safe())
+}
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+    use crate::num::NonZero;
+
+    #[kani::proof]
+    pub fn check_char_invariant() {
+        if let Some(c) = char::from_u32(kani::any()) {
+            assert!(c.is_safe());
+        }
+        if let Some(c) =
+            char::from_digit(kani::any(), kani::any_where(|radix| (2..=36).contains(radix)))
+        {
+            assert!(c.is_safe());
+        }
+        assert!(char::from(kani::any::<u8>()).is_safe());
+        assert!(kani::any::<char>().is_safe());
+    }
+
+    #[kani::proof]
+    pub fn check_bool_invariant() {
+        assert!(kani::any::<bool>().is_safe());
+        assert!((kani::any::<u8>() == 0).is_safe());
+    }
+
+    #[kani::proof]
+    pub fn check_str_invariant() {
+        let bytes: [u8; 4] = kani::any();
+        let mut buf = [0; 4];
+        let s: &str = kani::any::<char>().encode_utf8(&mut buf);
+        assert!(s.is_safe());
+        if let Ok(s) = crate::str::from_utf8(&bytes) {
+            assert!(s.is_safe());
+            let mid = kani::any_where(|mid: &usize| s.is_char_boundary(*mid));
+            assert!((&s[..mid]).is_safe());
+            assert!((&s[mid..]).is_safe());
+        }
+    }
+
+    #[kani::proof]
+    pub fn check_option_invariant() {
+        assert!(NonZero::new(kani::any::<u32>()).is_safe());
+        assert!(char::from_u32(kani::any()).is_safe());
+        assert!(kani::any::<Option<NonZero<u8>>>().is_safe());
+        assert!(None::<NonZero<u8>>.is_safe());
+    }
+
+    #[kani::proof]
+    pub fn check_result_invariant() {
+        let x: u8 = kani::any();
+        assert!(NonZero::new(x).ok_or(x).is_safe());
+        assert!(char::try_from(kani::any::<u32>()).map_err(|_| ()).is_safe());
+        assert!(kani::any::<Result<NonZero<u8>, char>>().is_safe());
+    }
+
+    #[kani::proof]
+    pub fn check_array_invariant() {
+        assert!(kani::any::<[NonZero<u16>; 3]>().is_safe());
+        assert!(kani::any::<[char; 4]>().is_safe());
+        assert!(<[Option<char>; 0]>::default().is_safe());
+    }
+
+    #[kani::proof]
+    pub fn check_tuple_invariant() {
+        assert!(kani::any::<(NonZero<u8>,)>().is_safe());
+        assert!(kani::any::<(char, bool, NonZero<i64>)>().is_safe());
+        assert!((kani::any::<u8>(), char::from_u32(kani::any())).is_safe());
+    }
+
+    #[kani::proof]
+    pub fn check_any_safe() {
+        let layout: crate::alloc::Layout = any_safe();
+        assert!(layout.is_safe());
+        let value: Option<NonZero<u32>> = any_safe();
+        assert!(value.is_safe());
+    }
+}
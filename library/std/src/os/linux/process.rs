@@ -64,6 +64,15 @@ impl PidFd {
         self.inner.kill()
     }
 
+    /// Sends a signal to the child process.
+    ///
+    /// Like [`kill`](PidFd::kill), this cannot signal an unrelated process
+    /// that reused the child's pid, and returns an error if the child has
+    /// already been reaped.
+    pub fn send_signal(&self, signal: i32) -> Result<()> {
+        self.inner.send_signal(signal)
+    }
+
     /// Waits for the child to exit completely, returning the status that it exited with.
     ///
     /// Unlike [`Child::wait`] it does not ensure that the stdin handle is closed.
@@ -205,6 +214,19 @@ pub trait CommandExt: Sealed {
     /// [`pidfd`]: fn@ChildExt::pidfd
     /// [`into_pidfd`]: ChildExt::into_pidfd
     fn create_pidfd(&mut self, val: bool) -> &mut process::Command;
+
+    /// Sets a signal to be sent to the child process when its parent dies.
+    /// This calls `prctl(PR_SET_PDEATHSIG)` in the child process, after any
+    /// change of user or group, which would otherwise reset it.
+    ///
+    /// If the parent has already died by the time the child sets this up,
+    /// the child sends the signal to itself before executing the command.
+    ///
+    /// Note that "parent" refers to the thread that spawned the child: the
+    /// signal is sent when that thread exits, even if the rest of the parent
+    /// process keeps running.
+    #[unstable(feature = "process_child_setup", issue = "none")]
+    fn parent_death_signal(&mut self, signal: i32) -> &mut process::Command;
 }
 
 impl CommandExt for process::Command {
@@ -212,4 +234,9 @@ impl CommandExt for process::Command {
         self.as_inner_mut().create_pidfd(val);
         self
     }
+
+    fn parent_death_signal(&mut self, signal: i32) -> &mut process::Command {
+        self.as_inner_mut().parent_death_signal(signal);
+        self
+    }
 }
@@ -210,6 +210,64 @@ pub trait CommandExt: Sealed {
     /// intentional difference from the underlying `chroot` system call.)
     #[unstable(feature = "process_chroot", issue = "141298")]
     fn chroot<P: AsRef<Path>>(&mut self, dir: P) -> &mut process::Command;
+
+    /// Sets whether the child process starts a new session. This calls
+    /// `setsid` in the child process, which detaches it from the controlling
+    /// terminal and makes it the leader of a new session and process group.
+    ///
+    /// This cannot be combined with [`process_group`]: `setsid` runs first,
+    /// and `setpgid` then fails with `EPERM` since a session leader cannot
+    /// change its process group, so spawning fails.
+    ///
+    /// [`process_group`]: CommandExt::process_group
+    #[unstable(feature = "process_child_setup", issue = "none")]
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command;
+
+    /// Sets a resource limit of the child process. This calls `setrlimit` in
+    /// the child process, before any change of user or group.
+    ///
+    /// `resource` is one of the `RLIMIT_*` constants, and `soft` and `hard`
+    /// are the soft and hard limits. Limits that do not fit in the platform's
+    /// `rlim_t` are treated as `RLIM_INFINITY`. If the same resource is set
+    /// more than once, the last call wins.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_child_setup)]
+    /// # #![feature(rustc_private)]
+    ///
+    /// use std::os::unix::process::CommandExt;
+    /// use std::process::Command;
+    ///
+    /// // Limit the job to 256 open files and forbid core dumps.
+    /// Command::new("make")
+    ///     .rlimit(libc::RLIMIT_NOFILE as i32, 256, 256)
+    ///     .rlimit(libc::RLIMIT_CORE as i32, 0, 0)
+    ///     .status()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "process_child_setup", issue = "none")]
+    fn rlimit(&mut self, resource: i32, soft: u64, hard: u64) -> &mut process::Command;
+
+    /// Sets the file mode creation mask of the child process. This calls
+    /// `umask` in the child process.
+    #[unstable(feature = "process_child_setup", issue = "none")]
+    fn umask(&mut self, mask: u32) -> &mut process::Command;
+
+    /// Sets whether file descriptors other than stdin, stdout and stderr are
+    /// closed in the child process.
+    ///
+    /// File descriptors created by the standard library are never inherited
+    /// by child processes, but ones created elsewhere, for example by C
+    /// libraries, may be. With this option, every descriptor from 3 upwards
+    /// is marked close-on-exec before any [`pre_exec`] closure runs, so that
+    /// closures can still set up descriptors the child should inherit.
+    ///
+    /// [`pre_exec`]: CommandExt::pre_exec
+    #[unstable(feature = "process_child_setup", issue = "none")]
+    fn close_other_fds(&mut self, close: bool) -> &mut process::Command;
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -260,6 +318,26 @@ impl CommandExt for process::Command {
         self.as_inner_mut().chroot(dir.as_ref());
         self
     }
+
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command {
+        self.as_inner_mut().setsid(setsid);
+        self
+    }
+
+    fn rlimit(&mut self, resource: i32, soft: u64, hard: u64) -> &mut process::Command {
+        self.as_inner_mut().rlimit(resource, soft, hard);
+        self
+    }
+
+    fn umask(&mut self, mask: u32) -> &mut process::Command {
+        self.as_inner_mut().umask(mask);
+        self
+    }
+
+    fn close_other_fds(&mut self, close: bool) -> &mut process::Command {
+        self.as_inner_mut().close_other_fds(close);
+        self
+    }
 }
 
 /// Unix-specific extensions to [`process::ExitStatus`] and
@@ -378,6 +456,97 @@ impl ExitStatusExt for process::ExitStatusError {
     }
 }
 
+/// Unix-specific extensions to [`process::Child`].
+///
+/// This trait is sealed: it cannot be implemented outside the standard library.
+/// This is so that future additional methods are not breaking changes.
+#[unstable(feature = "unix_send_signal", issue = "none")]
+pub trait ChildExt: Sealed {
+    /// Sends a signal to the child process.
+    ///
+    /// Unlike [`Child::kill`], which always sends `SIGKILL`, this allows
+    /// asking the child to shut down gracefully, for example with `SIGTERM`,
+    /// before [waiting](process::Child::wait_timeout) for it to exit.
+    ///
+    /// If the child has already been waited for, this does nothing and
+    /// returns `Ok`, just like [`Child::kill`].
+    ///
+    /// On Linux, the signal is delivered through the child's pidfd if it has
+    /// one, see [`linux::process::CommandExt::create_pidfd`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_send_signal, child_wait_timeout)]
+    /// # #![feature(rustc_private)]
+    ///
+    /// use std::os::unix::process::ChildExt;
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// let mut child = Command::new("sleep").arg("1000").spawn()?;
+    /// child.send_signal(libc::SIGTERM)?;
+    /// if child.wait_timeout(Duration::from_secs(5))?.is_none() {
+    ///     child.kill()?;
+    ///     child.wait()?;
+    /// }
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`Child::kill`]: process::Child::kill
+    /// [`linux::process::CommandExt::create_pidfd`]: crate::os::linux::process::CommandExt::create_pidfd
+    #[unstable(feature = "unix_send_signal", issue = "none")]
+    fn send_signal(&self, signal: i32) -> io::Result<()>;
+
+    /// Sends a signal to every process in the child's process group.
+    ///
+    /// This is typically used together with
+    /// [`CommandExt::process_group`] to signal the child and all of its
+    /// descendants that have not moved to another group.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`io::ErrorKind::InvalidInput`] if the child
+    /// is still in the process group of the current process, so that the
+    /// current process never signals itself, or if the child has already
+    /// been waited for, since its process group can then no longer be
+    /// identified reliably.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_send_signal)]
+    /// # #![feature(rustc_private)]
+    ///
+    /// use std::os::unix::process::{ChildExt, CommandExt};
+    /// use std::process::Command;
+    ///
+    /// let mut child = Command::new("sh")
+    ///     .args(["-c", "sleep 1000 & sleep 1000"])
+    ///     .process_group(0)
+    ///     .spawn()?;
+    /// // Terminates both `sleep`s and the shell.
+    /// child.kill_process_group(libc::SIGTERM)?;
+    /// child.wait()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "unix_send_signal", issue = "none")]
+    fn kill_process_group(&self, signal: i32) -> io::Result<()>;
+}
+
+#[unstable(feature = "unix_send_signal", issue = "none")]
+impl ChildExt for process::Child {
+    fn send_signal(&self, signal: i32) -> io::Result<()> {
+        self.as_inner().send_signal(signal)
+    }
+
+    fn kill_process_group(&self, signal: i32) -> io::Result<()> {
+        self.as_inner().kill_process_group(signal)
+    }
+}
+
 #[stable(feature = "process_extensions", since = "1.2.0")]
 impl FromRawFd for process::Stdio {
     #[inline]
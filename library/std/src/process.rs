@@ -160,6 +160,13 @@
 ))]
 mod tests;
 
+mod capture;
+mod pipeline;
+
+#[unstable(feature = "process_output_capture", issue = "none")]
+pub use self::capture::{CaptureLimit, CapturedOutput, OutputChunk, OutputOptions, OutputStream};
+#[unstable(feature = "process_pipeline", issue = "none")]
+pub use self::pipeline::{Pipeline, PipelineChildren, PipelineOutput, PipelineStatus};
 use crate::convert::Infallible;
 use crate::ffi::OsStr;
 use crate::io::prelude::*;
@@ -169,6 +176,7 @@ use crate::path::Path;
 use crate::sys::pipe::{AnonPipe, read2};
 use crate::sys::process as imp;
 use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
+use crate::time::{Duration, Instant};
 use crate::{fmt, fs, str};
 
 /// Representation of a running or exited child process.
@@ -1075,6 +1083,34 @@ impl Command {
         Ok(Output { status: ExitStatus(status), stdout, stderr })
     }
 
+    /// Executes the command as a child process, waiting for it to finish and
+    /// collecting its output according to `options`.
+    ///
+    /// Like [`output`](Command::output), stdout and stderr are captured by
+    /// default and stdin is not inherited. Unlike it, the amount of output
+    /// kept can be bounded, stderr can be merged into stdout, and the child
+    /// can be killed after a timeout; see [`OutputOptions`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_output_capture)]
+    ///
+    /// use std::process::{CaptureLimit, Command, OutputOptions};
+    ///
+    /// let output = Command::new("/bin/cat")
+    ///     .arg("file.txt")
+    ///     .output_with_options(OutputOptions::new().stdout_limit(CaptureLimit::Head(1024)))
+    ///     .expect("failed to execute process");
+    ///
+    /// assert!(output.stdout.len() <= 1024);
+    /// ```
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub fn output_with_options(&mut self, options: &OutputOptions) -> io::Result<CapturedOutput> {
+        let child = self.inner.spawn(imp::Stdio::MakePipe, false).map(Child::from_inner)?;
+        capture::wait_with_output(child, options)
+    }
+
     /// Executes a command as a child process, waiting for it to finish and
     /// collecting its status.
     ///
@@ -2297,6 +2333,92 @@ impl Child {
         Ok(self.handle.try_wait()?.map(ExitStatus))
     }
 
+    /// Waits for the child to exit, giving up once `timeout` has elapsed.
+    ///
+    /// If the child exits in time, then `Ok(Some(status))` is returned, and
+    /// the child's exit status is collected just like with [`wait`]. If it is
+    /// still running once the timeout elapses, then `Ok(None)` is returned and
+    /// the child keeps running; it can then, for example, be [`kill`]ed.
+    ///
+    /// Like [`try_wait`], and unlike [`wait`], this function will not attempt
+    /// to drop stdin.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Linux, this waits on a pidfd for the child, opening one if the child
+    /// was not spawned with one. If pidfds are not supported by the kernel, and
+    /// on other Unix platforms, the child is polled with increasing intervals
+    /// until it exits or the timeout elapses. No `SIGCHLD` handler is
+    /// installed.
+    ///
+    /// [`wait`]: Self::wait
+    /// [`try_wait`]: Self::try_wait
+    /// [`kill`]: Self::kill
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(child_wait_timeout)]
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// let mut child = Command::new("sleep").arg("60").spawn()?;
+    ///
+    /// match child.wait_timeout(Duration::from_secs(1))? {
+    ///     Some(status) => println!("exited with: {status}"),
+    ///     None => {
+    ///         println!("still running after a second, killing it");
+    ///         child.kill()?;
+    ///         child.wait()?;
+    ///     }
+    /// }
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[unstable(feature = "child_wait_timeout", issue = "none")]
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.wait_deadline(deadline),
+            // The deadline cannot be represented, so it will never be reached.
+            None => Ok(Some(ExitStatus(self.handle.wait()?))),
+        }
+    }
+
+    /// Waits for the child to exit, giving up once `deadline` has passed.
+    ///
+    /// This behaves like [`wait_timeout`], except that the time limit is an
+    /// absolute point in time. This makes it convenient to wait for several
+    /// children with a single overall time limit. If the deadline has already
+    /// passed, this behaves like [`try_wait`].
+    ///
+    /// [`wait_timeout`]: Self::wait_timeout
+    /// [`try_wait`]: Self::try_wait
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(child_wait_timeout)]
+    /// use std::process::Command;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let mut children = vec![
+    ///     Command::new("sleep").arg("1").spawn()?,
+    ///     Command::new("sleep").arg("60").spawn()?,
+    /// ];
+    /// let deadline = Instant::now() + Duration::from_secs(5);
+    ///
+    /// for child in &mut children {
+    ///     if child.wait_deadline(deadline)?.is_none() {
+    ///         child.kill()?;
+    ///         child.wait()?;
+    ///     }
+    /// }
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[unstable(feature = "child_wait_timeout", issue = "none")]
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        Ok(self.handle.wait_deadline(deadline)?.map(ExitStatus))
+    }
+
     /// Simultaneously waits for the child to exit and collect all remaining
     /// output on the stdout/stderr handles, returning an `Output`
     /// instance.
@@ -2353,6 +2475,39 @@ impl Child {
         let status = self.wait()?;
         Ok(Output { status, stdout, stderr })
     }
+
+    /// Simultaneously waits for the child to exit and collects its output
+    /// according to `options`.
+    ///
+    /// This behaves like [`wait_with_output`](Child::wait_with_output), except
+    /// that the amount of output kept can be bounded, stderr can be merged
+    /// into stdout, and the child is killed if it has not closed its output
+    /// and exited before the timeout elapses; see [`OutputOptions`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_output_capture)]
+    ///
+    /// use std::process::{Command, OutputOptions, Stdio};
+    /// use std::time::Duration;
+    ///
+    /// let child = Command::new("/bin/cat")
+    ///     .arg("file.txt")
+    ///     .stdout(Stdio::piped())
+    ///     .spawn()
+    ///     .expect("failed to execute child");
+    ///
+    /// let output = child
+    ///     .wait_with_output_options(OutputOptions::new().timeout(Duration::from_secs(5)))
+    ///     .expect("failed to wait on child");
+    ///
+    /// assert!(!output.timed_out);
+    /// ```
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub fn wait_with_output_options(self, options: &OutputOptions) -> io::Result<CapturedOutput> {
+        capture::wait_with_output(self, options)
+    }
 }
 
 /// Terminates the current process with the specified exit code.
@@ -0,0 +1,307 @@
+//! Bounded collection of a child's output, see [`OutputOptions`].
+
+use super::{Child, ExitStatus};
+use crate::collections::VecDeque;
+use crate::sys::pipe::read2_until;
+use crate::time::{Duration, Instant};
+use crate::{fmt, io, str};
+
+/// How much of an output stream to keep when collecting it.
+///
+/// Bytes beyond the limit are still read from the child, so that it never
+/// blocks on a full pipe, but they are discarded.
+#[unstable(feature = "process_output_capture", issue = "none")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CaptureLimit {
+    /// Keep everything.
+    #[default]
+    Unlimited,
+    /// Keep at most this many bytes from the start of the stream.
+    Head(usize),
+    /// Keep at most this many bytes from the end of the stream.
+    Tail(usize),
+}
+
+/// One of the output streams of a child process.
+#[unstable(feature = "process_output_capture", issue = "none")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OutputStream {
+    /// The child's standard output.
+    Stdout,
+    /// The child's standard error.
+    Stderr,
+}
+
+/// A chunk of output read from a child, as recorded when
+/// [`OutputOptions::timestamps`] is set.
+#[unstable(feature = "process_output_capture", issue = "none")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutputChunk {
+    /// The stream the chunk was read from, even if stderr was merged into
+    /// stdout.
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub stream: OutputStream,
+    /// The number of bytes read from `stream` before this chunk.
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub offset: usize,
+    /// The number of bytes in the chunk, including any later discarded
+    /// because of a [`CaptureLimit`].
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub len: usize,
+    /// When the chunk was read.
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub time: Instant,
+}
+
+/// Options controlling how [`Command::output_with_options`] and
+/// [`Child::wait_with_output_options`] collect a child's output.
+///
+/// [`Command::output_with_options`]: super::Command::output_with_options
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(process_output_capture)]
+///
+/// use std::process::{CaptureLimit, Command, OutputOptions};
+/// use std::time::Duration;
+///
+/// let output = Command::new("make")
+///     .output_with_options(
+///         OutputOptions::new()
+///             .stdout_limit(CaptureLimit::Tail(64 * 1024))
+///             .merge_stderr(true)
+///             .timeout(Duration::from_secs(600)),
+///     )
+///     .expect("failed to execute process");
+///
+/// if output.timed_out || !output.status.success() {
+///     eprintln!("build failed:\n{}", String::from_utf8_lossy(&output.stdout));
+/// }
+/// ```
+#[unstable(feature = "process_output_capture", issue = "none")]
+#[derive(Clone, Debug, Default)]
+pub struct OutputOptions {
+    stdout_limit: CaptureLimit,
+    stderr_limit: CaptureLimit,
+    merge_stderr: bool,
+    timestamps: bool,
+    timeout: Option<Duration>,
+}
+
+impl OutputOptions {
+    /// Creates options that collect both streams in full, separately, and
+    /// without a timeout, like [`Child::wait_with_output`].
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    #[must_use]
+    pub fn new() -> OutputOptions {
+        OutputOptions::default()
+    }
+
+    /// Sets how much of the child's stdout to keep.
+    ///
+    /// When stderr is [merged](OutputOptions::merge_stderr) into stdout, this
+    /// limit applies to the merged stream.
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub fn stdout_limit(&mut self, limit: CaptureLimit) -> &mut OutputOptions {
+        self.stdout_limit = limit;
+        self
+    }
+
+    /// Sets how much of the child's stderr to keep.
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub fn stderr_limit(&mut self, limit: CaptureLimit) -> &mut OutputOptions {
+        self.stderr_limit = limit;
+        self
+    }
+
+    /// Sets whether stderr is collected into stdout.
+    ///
+    /// The two streams are read from separate pipes, so chunks are interleaved
+    /// in the order they were read, which approximates but does not guarantee
+    /// the order in which the child wrote them. With
+    /// [timestamps](OutputOptions::timestamps), the chunks record which stream
+    /// each part of the merged output came from.
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub fn merge_stderr(&mut self, merge: bool) -> &mut OutputOptions {
+        self.merge_stderr = merge;
+        self
+    }
+
+    /// Sets whether to record when each chunk of output was read, in
+    /// [`CapturedOutput::chunks`].
+    ///
+    /// One [`OutputChunk`] is kept for every read, whatever the limits, so
+    /// this is best left off for children that may write unbounded output.
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub fn timestamps(&mut self, timestamps: bool) -> &mut OutputOptions {
+        self.timestamps = timestamps;
+        self
+    }
+
+    /// Sets how long to wait for the child to close its output and exit.
+    ///
+    /// If the timeout elapses the child is killed unless it has already exited,
+    /// the output collected so far is returned, and
+    /// [`CapturedOutput::timed_out`] is set.
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub fn timeout(&mut self, timeout: Duration) -> &mut OutputOptions {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+/// The output of a finished process, collected according to [`OutputOptions`].
+#[unstable(feature = "process_output_capture", issue = "none")]
+#[derive(PartialEq, Eq, Clone)]
+pub struct CapturedOutput {
+    /// The status (exit code) of the process.
+    ///
+    /// If the process timed out, this is the status it was killed with, or the
+    /// status it exited with if it had exited before the timeout elapsed but
+    /// its output was still open.
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub status: ExitStatus,
+    /// The data that the process wrote to stdout, and to stderr if it was
+    /// merged.
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub stdout: Vec<u8>,
+    /// The data that the process wrote to stderr, unless it was merged.
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub stderr: Vec<u8>,
+    /// Whether bytes were discarded from `stdout` because of its limit.
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub stdout_truncated: bool,
+    /// Whether bytes were discarded from `stderr` because of its limit.
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub stderr_truncated: bool,
+    /// Whether the timeout elapsed before the process exited and closed its
+    /// output. The process was then killed, unless it had already exited.
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub timed_out: bool,
+    /// Every chunk read from the child, in the order they were read, if
+    /// [timestamps](OutputOptions::timestamps) were requested.
+    #[unstable(feature = "process_output_capture", issue = "none")]
+    pub chunks: Vec<OutputChunk>,
+}
+
+#[unstable(feature = "process_output_capture", issue = "none")]
+impl fmt::Debug for CapturedOutput {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stdout_utf8 = str::from_utf8(&self.stdout);
+        let stdout_debug: &dyn fmt::Debug = match stdout_utf8 {
+            Ok(ref s) => s,
+            Err(_) => &self.stdout,
+        };
+
+        let stderr_utf8 = str::from_utf8(&self.stderr);
+        let stderr_debug: &dyn fmt::Debug = match stderr_utf8 {
+            Ok(ref s) => s,
+            Err(_) => &self.stderr,
+        };
+
+        fmt.debug_struct("CapturedOutput")
+            .field("status", &self.status)
+            .field("stdout", stdout_debug)
+            .field("stderr", stderr_debug)
+            .field("stdout_truncated", &self.stdout_truncated)
+            .field("stderr_truncated", &self.stderr_truncated)
+            .field("timed_out", &self.timed_out)
+            .field("chunks", &self.chunks)
+            .finish()
+    }
+}
+
+/// A stream being collected under a [`CaptureLimit`].
+struct Capture {
+    limit: CaptureLimit,
+    buf: VecDeque<u8>,
+    truncated: bool,
+}
+
+impl Capture {
+    fn new(limit: CaptureLimit) -> Capture {
+        Capture { limit, buf: VecDeque::new(), truncated: false }
+    }
+
+    fn push(&mut self, bytes: &[u8]) {
+        match self.limit {
+            CaptureLimit::Unlimited => self.buf.extend(bytes),
+            CaptureLimit::Head(max) => {
+                let keep = bytes.len().min(max - self.buf.len());
+                self.buf.extend(&bytes[..keep]);
+                self.truncated |= keep < bytes.len();
+            }
+            CaptureLimit::Tail(max) => {
+                let keep = &bytes[bytes.len().saturating_sub(max)..];
+                let evict = (self.buf.len() + keep.len()).saturating_sub(max);
+                self.buf.drain(..evict);
+                self.buf.extend(keep);
+                self.truncated |= evict > 0 || keep.len() < bytes.len();
+            }
+        }
+    }
+
+    fn into_vec(self) -> Vec<u8> {
+        self.buf.into()
+    }
+}
+
+/// Implementation of [`Child::wait_with_output_options`].
+pub(super) fn wait_with_output(
+    mut child: Child,
+    options: &OutputOptions,
+) -> io::Result<CapturedOutput> {
+    drop(child.stdin.take());
+
+    // A timeout too large to represent is as good as no timeout at all.
+    let deadline = options.timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+    let mut stdout = Capture::new(options.stdout_limit);
+    let mut stderr = Capture::new(options.stderr_limit);
+    let merge = options.merge_stderr;
+    let timestamps = options.timestamps;
+    let mut chunks = Vec::new();
+    let mut read = [0; 2];
+    let drained = read2_until(
+        child.stdout.take().map(|out| out.inner),
+        child.stderr.take().map(|err| err.inner),
+        deadline,
+        &mut |index, bytes| {
+            if timestamps {
+                let stream = if index == 0 { OutputStream::Stdout } else { OutputStream::Stderr };
+                let time = Instant::now();
+                chunks.push(OutputChunk { stream, offset: read[index], len: bytes.len(), time });
+                read[index] += bytes.len();
+            }
+            if index == 0 || merge { stdout.push(bytes) } else { stderr.push(bytes) }
+        },
+    )?;
+
+    let status = match deadline {
+        None => Some(child.wait()?),
+        Some(deadline) if drained => child.wait_deadline(deadline)?,
+        Some(_) => None,
+    };
+    // The child may have exited while its output stayed open, for instance
+    // because a process it spawned inherited it. It is then left alone.
+    let (status, timed_out) = match status {
+        Some(status) => (status, false),
+        None => match child.try_wait()? {
+            Some(status) => (status, true),
+            None => {
+                child.kill()?;
+                (child.wait()?, true)
+            }
+        },
+    };
+
+    Ok(CapturedOutput {
+        status,
+        stdout_truncated: stdout.truncated,
+        stderr_truncated: stderr.truncated,
+        stdout: stdout.into_vec(),
+        stderr: stderr.into_vec(),
+        timed_out,
+        chunks,
+    })
+}
@@ -0,0 +1,396 @@
+//! Chains of [`Command`]s connected stdout-to-stdin, as in `a | b | c`.
+
+use super::{Child, Command, ExitStatus, Stdio, imp};
+use crate::sys_common::FromInner;
+use crate::{fmt, io, mem, str};
+
+/// A builder for a sequence of processes connected by anonymous pipes.
+///
+/// Each stage's standard output is connected to the standard input of the
+/// next stage, like a shell pipeline `a | b | c`. The standard input of the
+/// first stage, the standard output of the last stage and the standard error
+/// of every stage are configured on the individual [`Command`]s as usual.
+///
+/// Any stdin configuration on a stage other than the first, and any stdout
+/// configuration on a stage other than the last, is replaced by the pipe
+/// connecting it to its neighbour while the stage is spawned. The stages
+/// themselves are left as configured, as returned by [`get_stages`].
+///
+/// [`get_stages`]: Pipeline::get_stages
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(process_pipeline)]
+///
+/// use std::process::{Command, Pipeline};
+///
+/// let output = Pipeline::new()
+///     .stage(Command::new("ls"))
+///     .stage({
+///         let mut grep = Command::new("grep");
+///         grep.arg("\\.rs$");
+///         grep
+///     })
+///     .stage({
+///         let mut wc = Command::new("wc");
+///         wc.arg("-l");
+///         wc
+///     })
+///     .output()
+///     .expect("failed to run pipeline");
+///
+/// assert!(output.status.success());
+/// println!("{} Rust files", String::from_utf8_lossy(&output.stdout).trim());
+/// ```
+#[unstable(feature = "process_pipeline", issue = "none")]
+pub struct Pipeline {
+    stages: Vec<Command>,
+}
+
+#[unstable(feature = "process_pipeline", issue = "none")]
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.stages).finish()
+    }
+}
+
+#[unstable(feature = "process_pipeline", issue = "none")]
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pipeline {
+    /// Creates an empty pipeline.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    #[must_use]
+    pub fn new() -> Pipeline {
+        Pipeline { stages: Vec::new() }
+    }
+
+    /// Appends a stage to the end of the pipeline.
+    ///
+    /// The standard input of `command` will be connected to the standard
+    /// output of the previous stage, if any.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub fn stage(&mut self, command: Command) -> &mut Pipeline {
+        self.stages.push(command);
+        self
+    }
+
+    /// Returns the stages of the pipeline, in order.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub fn get_stages(&self) -> &[Command] {
+        &self.stages
+    }
+
+    /// Spawns every stage of the pipeline, returning handles to all of them.
+    ///
+    /// By default, the first stage inherits stdin, the last stage inherits
+    /// stdout, and every stage inherits stderr from the parent.
+    ///
+    /// If any stage fails to spawn, the stages that were already spawned are
+    /// killed and waited for before the error is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`io::ErrorKind::InvalidInput`] if the
+    /// pipeline has no stages.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub fn spawn(&mut self) -> io::Result<PipelineChildren> {
+        self.spawn_stages(|_| (imp::Stdio::Inherit, true))
+    }
+
+    /// Executes every stage of the pipeline, waiting for all of them to
+    /// finish and collecting their statuses.
+    ///
+    /// By default, the first stage inherits stdin, the last stage inherits
+    /// stdout, and every stage inherits stderr from the parent.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub fn status(&mut self) -> io::Result<PipelineStatus> {
+        self.spawn()?.wait()
+    }
+
+    /// Executes every stage of the pipeline, waiting for all of them to
+    /// finish and collecting the output of the last stage.
+    ///
+    /// By default, the standard output and standard error of the last stage
+    /// are captured, the first stage's stdin is [`Stdio::null`], and the other
+    /// stages inherit stderr from the parent.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub fn output(&mut self) -> io::Result<PipelineOutput> {
+        let last = self.stages.len().saturating_sub(1);
+        self.spawn_stages(|index| {
+            if index == last { (imp::Stdio::MakePipe, false) } else { (imp::Stdio::Inherit, false) }
+        })?
+        .wait_with_output()
+    }
+
+    /// Spawns every stage, connecting neighbouring stages with anonymous pipes.
+    ///
+    /// `defaults` gives the default stdio and `needs_stdin` flag for the stage
+    /// at the given index, as passed to `imp::Command::spawn`.
+    fn spawn_stages(
+        &mut self,
+        defaults: impl Fn(usize) -> (imp::Stdio, bool),
+    ) -> io::Result<PipelineChildren> {
+        if self.stages.is_empty() {
+            return Err(io::const_error!(io::ErrorKind::InvalidInput, "pipeline has no stages"));
+        }
+
+        let last = self.stages.len() - 1;
+        let mut children = Vec::with_capacity(self.stages.len());
+        let mut upstream = None;
+        for (index, command) in self.stages.iter_mut().enumerate() {
+            // The pipe ends only stand in for the stage's own stdio configuration while it is
+            // spawned. Restoring that configuration afterwards also drops the parent's copies of
+            // the pipe ends, otherwise readers would never see end-of-file and writers would
+            // never see a broken pipe.
+            let saved_stdin = upstream
+                .take()
+                .map(|reader| mem::replace(command.inner.stdin_mut(), Some(Stdio::from(reader).0)));
+            let saved_stdout = if index != last {
+                match io::pipe() {
+                    Ok((reader, writer)) => {
+                        upstream = Some(reader);
+                        Some(mem::replace(command.inner.stdout_mut(), Some(Stdio::from(writer).0)))
+                    }
+                    Err(e) => {
+                        if let Some(stdin) = saved_stdin {
+                            *command.inner.stdin_mut() = stdin;
+                        }
+                        return Err(abort(children, e));
+                    }
+                }
+            } else {
+                None
+            };
+
+            let (default, needs_stdin) = defaults(index);
+            let spawned = command.inner.spawn(default, needs_stdin).map(Child::from_inner);
+
+            if let Some(stdin) = saved_stdin {
+                *command.inner.stdin_mut() = stdin;
+            }
+            if let Some(stdout) = saved_stdout {
+                *command.inner.stdout_mut() = stdout;
+            }
+
+            match spawned {
+                Ok(child) => children.push(child),
+                Err(e) => return Err(abort(children, e)),
+            }
+        }
+        Ok(PipelineChildren { children })
+    }
+}
+
+/// Kills and reaps the stages that were spawned before `error` occurred.
+fn abort(children: Vec<Child>, error: io::Error) -> io::Error {
+    for mut child in children {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+    error
+}
+
+/// Handles to the running stages of a [`Pipeline`].
+///
+/// This structure is created by [`Pipeline::spawn`]. As with [`Child`], there
+/// is no implementation of [`Drop`]; stages that are not waited for will keep
+/// running after the handle goes out of scope.
+#[unstable(feature = "process_pipeline", issue = "none")]
+pub struct PipelineChildren {
+    children: Vec<Child>,
+}
+
+#[unstable(feature = "process_pipeline", issue = "none")]
+impl fmt::Debug for PipelineChildren {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.children).finish()
+    }
+}
+
+impl PipelineChildren {
+    /// Returns the child processes, in stage order.
+    ///
+    /// The [`stdin`](Child::stdin) handle, if any, lives on the first child
+    /// and the [`stdout`](Child::stdout) handle, if any, on the last one.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub fn children(&self) -> &[Child] {
+        &self.children
+    }
+
+    /// Returns the child processes mutably, in stage order.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub fn children_mut(&mut self) -> &mut [Child] {
+        &mut self.children
+    }
+
+    /// Consumes the handle, returning the child processes in stage order.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub fn into_children(self) -> Vec<Child> {
+        self.children
+    }
+
+    /// Forces every stage to exit.
+    ///
+    /// Every stage is sent a kill request even if an earlier one fails; the
+    /// first error encountered is returned.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub fn kill(&mut self) -> io::Result<()> {
+        let mut result = Ok(());
+        for child in &mut self.children {
+            if let Err(e) = child.kill() {
+                result = result.and(Err(e));
+            }
+        }
+        result
+    }
+
+    /// Waits for every stage to exit, returning their statuses.
+    ///
+    /// The stdin handle of the first stage, if any, is closed before waiting.
+    /// Every stage is waited for even if waiting on an earlier one fails; the
+    /// first error encountered is returned.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub fn wait(&mut self) -> io::Result<PipelineStatus> {
+        drop(self.children[0].stdin.take());
+        let statuses = self.wait_all()?;
+        Ok(PipelineStatus { statuses })
+    }
+
+    /// Waits for every stage to exit, collecting the output of the last one.
+    ///
+    /// The stdin handle of the first stage, if any, is closed before waiting.
+    /// The captured stdout and stderr of the last stage are read to the end
+    /// before the other stages are waited for, so that a stage blocked on a
+    /// full pipe cannot deadlock the pipeline.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub fn wait_with_output(mut self) -> io::Result<PipelineOutput> {
+        drop(self.children[0].stdin.take());
+
+        let last = self.children.pop().unwrap().wait_with_output();
+        let rest = self.wait_all();
+        let output = last?;
+        let mut statuses = rest?;
+        statuses.push(output.status);
+        Ok(PipelineOutput {
+            status: PipelineStatus { statuses },
+            stdout: output.stdout,
+            stderr: output.stderr,
+        })
+    }
+
+    /// Waits for every stage, returning the first error encountered.
+    fn wait_all(&mut self) -> io::Result<Vec<ExitStatus>> {
+        let mut error = None;
+        let mut statuses = Vec::with_capacity(self.children.len() + 1);
+        for child in &mut self.children {
+            match child.wait() {
+                Ok(status) => statuses.push(status),
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            }
+        }
+        match error {
+            Some(e) => Err(e),
+            None => Ok(statuses),
+        }
+    }
+}
+
+/// The exit statuses of every stage of a finished [`Pipeline`].
+///
+/// This structure is returned by [`Pipeline::status`] and
+/// [`PipelineChildren::wait`], and is part of [`PipelineOutput`].
+#[derive(PartialEq, Eq, Clone)]
+#[unstable(feature = "process_pipeline", issue = "none")]
+pub struct PipelineStatus {
+    statuses: Vec<ExitStatus>,
+}
+
+#[unstable(feature = "process_pipeline", issue = "none")]
+impl fmt::Debug for PipelineStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.statuses).finish()
+    }
+}
+
+impl PipelineStatus {
+    /// Returns the exit status of every stage, in stage order.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub fn statuses(&self) -> &[ExitStatus] {
+        &self.statuses
+    }
+
+    /// Returns the exit status of the last stage.
+    ///
+    /// This is the status a shell reports for a pipeline by default.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub fn last(&self) -> ExitStatus {
+        *self.statuses.last().unwrap()
+    }
+
+    /// Returns the status of the rightmost stage that did not succeed, or
+    /// the status of the last stage if every stage succeeded.
+    ///
+    /// This is the status a shell reports for a pipeline with
+    /// `set -o pipefail`.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub fn pipefail(&self) -> ExitStatus {
+        self.statuses.iter().rev().find(|status| !status.success()).copied().unwrap_or(self.last())
+    }
+
+    /// Was termination of every stage successful?
+    ///
+    /// This is equivalent to `self.pipefail().success()`.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub fn success(&self) -> bool {
+        self.statuses.iter().all(ExitStatus::success)
+    }
+}
+
+/// The output of a finished [`Pipeline`].
+///
+/// This structure is returned by [`Pipeline::output`] and
+/// [`PipelineChildren::wait_with_output`].
+#[derive(PartialEq, Eq, Clone)]
+#[unstable(feature = "process_pipeline", issue = "none")]
+pub struct PipelineOutput {
+    /// The exit status of every stage.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub status: PipelineStatus,
+    /// The data that the last stage wrote to stdout.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub stdout: Vec<u8>,
+    /// The data that the last stage wrote to stderr.
+    #[unstable(feature = "process_pipeline", issue = "none")]
+    pub stderr: Vec<u8>,
+}
+
+#[unstable(feature = "process_pipeline", issue = "none")]
+impl fmt::Debug for PipelineOutput {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stdout_utf8 = str::from_utf8(&self.stdout);
+        let stdout_debug: &dyn fmt::Debug = match stdout_utf8 {
+            Ok(ref s) => s,
+            Err(_) => &self.stdout,
+        };
+
+        let stderr_utf8 = str::from_utf8(&self.stderr);
+        let stderr_debug: &dyn fmt::Debug = match stderr_utf8 {
+            Ok(ref s) => s,
+            Err(_) => &self.stderr,
+        };
+
+        fmt.debug_struct("PipelineOutput")
+            .field("status", &self.status)
+            .field("stdout", stdout_debug)
+            .field("stderr", stderr_debug)
+            .finish()
+    }
+}
@@ -1,8 +1,9 @@
-use super::{Command, Output, Stdio};
+use super::{CaptureLimit, Command, Output, OutputOptions, OutputStream, Pipeline, Stdio};
 use crate::io::prelude::*;
 use crate::io::{BorrowedBuf, ErrorKind};
 use crate::mem::MaybeUninit;
 use crate::str;
+use crate::time::{Duration, Instant};
 
 fn known_command() -> Command {
     if cfg!(windows) { Command::new("help") } else { Command::new("echo") }
@@ -243,6 +244,45 @@ fn test_finish_twice() {
     assert!(prog.wait().unwrap().code() == Some(1));
 }
 
+#[test]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_wait_timeout_exited() {
+    let mut prog = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(&["/C", "exit 1"]).spawn().unwrap()
+    } else {
+        shell_cmd().arg("-c").arg("false").spawn().unwrap()
+    };
+    let status = prog.wait_timeout(Duration::from_secs(60)).unwrap();
+    assert_eq!(status.and_then(|status| status.code()), Some(1));
+    // The status is cached once collected.
+    assert_eq!(prog.wait_timeout(Duration::ZERO).unwrap(), status);
+    assert_eq!(prog.wait().unwrap().code(), Some(1));
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_wait_timeout_running() {
+    let mut prog = shell_cmd().arg("-c").arg("sleep 1000").spawn().unwrap();
+    let timeout = Duration::from_millis(50);
+    let start = Instant::now();
+    assert_eq!(prog.wait_timeout(timeout).unwrap(), None);
+    assert!(start.elapsed() >= timeout);
+    assert_eq!(prog.wait_deadline(Instant::now()).unwrap(), None);
+    prog.kill().unwrap();
+    let status = prog.wait_timeout(Duration::from_secs(60)).unwrap().unwrap();
+    assert!(!status.success());
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_wait_timeout_unrepresentable() {
+    let mut prog = shell_cmd().arg("-c").arg("true").spawn().unwrap();
+    let status = prog.wait_timeout(Duration::MAX).unwrap().unwrap();
+    assert!(status.success());
+}
+
 #[test]
 #[cfg_attr(any(target_os = "vxworks"), ignore)]
 fn test_wait_with_output_once() {
@@ -603,3 +643,238 @@ fn terminate_exited_process() {
     assert!(p.kill().is_ok());
     assert!(p.kill().is_ok());
 }
+
+#[cfg(unix)]
+fn shell_stage(script: &str) -> Command {
+    let mut cmd = shell_cmd();
+    cmd.arg("-c").arg(script);
+    cmd
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_pipeline_output() {
+    let output = Pipeline::new()
+        .stage(shell_stage("printf 'a\\nb\\nc\\n'"))
+        .stage(shell_stage("grep -v b"))
+        .stage(shell_stage("wc -l"))
+        .output()
+        .unwrap();
+    assert_eq!(output.status.statuses().len(), 3);
+    assert!(output.status.success());
+    assert_eq!(str::from_utf8(&output.stdout).unwrap().trim(), "2");
+    assert_eq!(output.stderr, Vec::new());
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_pipeline_pipefail() {
+    let status = Pipeline::new()
+        .stage(shell_stage("exit 3"))
+        .stage(shell_stage("exit 4"))
+        .stage(shell_stage("cat"))
+        .status()
+        .unwrap();
+    let codes: Vec<_> = status.statuses().iter().map(|status| status.code()).collect();
+    assert_eq!(codes, [Some(3), Some(4), Some(0)]);
+    assert!(status.last().success());
+    assert_eq!(status.pipefail().code(), Some(4));
+    assert!(!status.success());
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_pipeline_closes_pipes() {
+    // `yes` only terminates once `head` exits and the read end of the pipe
+    // is closed, which requires the parent not to hold on to it.
+    let output =
+        Pipeline::new().stage(shell_stage("yes")).stage(shell_stage("head -n 1")).output().unwrap();
+    assert_eq!(output.stdout, b"y\n");
+    assert!(output.status.last().success());
+    assert!(!output.status.statuses()[0].success());
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_pipeline_spawn_stdin() {
+    let mut pipeline = Pipeline::new();
+    pipeline.stage(shell_stage("tr a-z A-Z")).stage({
+        let mut cat = shell_stage("cat");
+        cat.stdout(Stdio::piped());
+        cat
+    });
+    let mut children = pipeline.spawn().unwrap();
+    children.children_mut()[0].stdin.take().unwrap().write_all(b"hello").unwrap();
+    let mut stdout = String::new();
+    children.children_mut()[1].stdout.take().unwrap().read_to_string(&mut stdout).unwrap();
+    assert_eq!(stdout, "HELLO");
+    assert!(children.wait().unwrap().success());
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_pipeline_keeps_stage_stdio() {
+    // The pipes only replace the stdio configuration of the stages while they are spawned.
+    let mut pipeline = Pipeline::new();
+    pipeline.stage(shell_stage("echo a")).stage({
+        let mut cat = shell_stage("cat");
+        cat.stdin(Stdio::null()).stdout(Stdio::null());
+        cat
+    });
+    let debug = |pipeline: &Pipeline| -> Vec<String> {
+        pipeline.get_stages().iter().map(|stage| format!("{stage:#?}")).collect()
+    };
+    let before = debug(&pipeline);
+    assert!(pipeline.status().unwrap().success());
+    assert_eq!(debug(&pipeline), before);
+    let output = pipeline.output().unwrap();
+    assert_eq!(output.stdout, b"");
+    assert_eq!(debug(&pipeline), before);
+}
+
+#[test]
+fn test_pipeline_empty() {
+    let err = Pipeline::new().status().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_pipeline_spawn_failure() {
+    let mut pipeline = Pipeline::new();
+    pipeline.stage(shell_stage("sleep 1000")).stage(Command::new("nonexistent-pipeline-stage"));
+    assert!(pipeline.status().is_err());
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_output_with_options_limits() {
+    let script = "printf 0123456789; printf abcdefghij >&2";
+    let output = shell_stage(script)
+        .output_with_options(
+            OutputOptions::new()
+                .stdout_limit(CaptureLimit::Head(4))
+                .stderr_limit(CaptureLimit::Tail(4)),
+        )
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"0123");
+    assert_eq!(output.stderr, b"ghij");
+    assert!(output.stdout_truncated);
+    assert!(output.stderr_truncated);
+    assert!(!output.timed_out);
+
+    let output = shell_stage(script)
+        .output_with_options(OutputOptions::new().stdout_limit(CaptureLimit::Tail(10)))
+        .unwrap();
+    assert_eq!(output.stdout, b"0123456789");
+    assert!(!output.stdout_truncated);
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_output_with_options_tail_across_chunks() {
+    // Far more output than a pipe buffer holds, so it arrives in many chunks.
+    let output = shell_stage("i=0; while [ $i -lt 20000 ]; do echo $i; i=$((i+1)); done")
+        .output_with_options(OutputOptions::new().stdout_limit(CaptureLimit::Tail(12)))
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"19998\n19999\n");
+    assert!(output.stdout_truncated);
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_output_with_options_merge_stderr() {
+    let output = shell_stage("echo out; echo err >&2; echo out")
+        .output_with_options(OutputOptions::new().merge_stderr(true).timestamps(true))
+        .unwrap();
+    assert_eq!(output.stderr, Vec::new());
+    // The streams may be read in any order, but splitting the merged output
+    // along the chunks read gives each stream back.
+    let mut streams = [Vec::new(), Vec::new()];
+    let mut merged = &output.stdout[..];
+    for chunk in &output.chunks {
+        let stream = &mut streams[(chunk.stream == OutputStream::Stderr) as usize];
+        assert_eq!(chunk.offset, stream.len());
+        let (bytes, rest) = merged.split_at(chunk.len);
+        stream.extend_from_slice(bytes);
+        merged = rest;
+    }
+    assert!(merged.is_empty());
+    assert_eq!(streams, [b"out\nout\n".to_vec(), b"err\n".to_vec()]);
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_output_with_options_timestamps() {
+    let start = Instant::now();
+    let output = shell_stage("printf 0123456789; printf abc >&2")
+        .output_with_options(
+            OutputOptions::new().stdout_limit(CaptureLimit::Head(4)).timestamps(true),
+        )
+        .unwrap();
+    let end = Instant::now();
+    assert_eq!(output.stdout, b"0123");
+    // Chunks are recorded in the order they were read, before limits apply.
+    let total = |stream: OutputStream| -> usize {
+        output.chunks.iter().filter(|chunk| chunk.stream == stream).map(|chunk| chunk.len).sum()
+    };
+    assert_eq!(total(OutputStream::Stdout), 10);
+    assert_eq!(total(OutputStream::Stderr), 3);
+    assert!(output.chunks.is_sorted_by_key(|chunk| chunk.time));
+    assert!(output.chunks.iter().all(|chunk| start <= chunk.time && chunk.time <= end));
+
+    // Timestamps are off by default.
+    let output = shell_stage("echo out").output_with_options(&OutputOptions::new()).unwrap();
+    assert!(output.chunks.is_empty());
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_output_with_options_timeout() {
+    let start = Instant::now();
+    let output = shell_stage("echo started; exec sleep 1000")
+        .output_with_options(OutputOptions::new().timeout(Duration::from_millis(200)))
+        .unwrap();
+    assert!(start.elapsed() < Duration::from_secs(60));
+    assert!(output.timed_out);
+    assert!(!output.status.success());
+    assert_eq!(output.stdout, b"started\n");
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_output_with_options_timeout_after_exit() {
+    // The shell exits at once, but `sleep` keeps its stdout open.
+    let output = shell_stage("sleep 10 & exit 3")
+        .output_with_options(OutputOptions::new().timeout(Duration::from_millis(200)))
+        .unwrap();
+    assert!(output.timed_out);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_wait_with_output_options_no_pipes() {
+    let child = shell_stage("exit 7").spawn().unwrap();
+    let output = child
+        .wait_with_output_options(OutputOptions::new().timeout(Duration::from_secs(60)))
+        .unwrap();
+    assert_eq!(output.status.code(), Some(7));
+    assert!(!output.timed_out);
+    assert!(output.stdout.is_empty() && output.stderr.is_empty());
+}
@@ -8,6 +8,8 @@
 //!   - <http://www.1024cores.net/home/lock-free-algorithms/queues/bounded-mpmc-queue>
 //!   - <https://docs.google.com/document/d/1yIAYmbvL3JxOKOjuCyon7JhW4cSv1wy5hC0ApeGMV9s/pub>
 
+use safety::{ensures, requires};
+
 use super::context::Context;
 use super::error::*;
 use super::select::{Operation, Selected, Token};
@@ -120,7 +122,22 @@ impl<T> Channel<T> {
         }
     }
 
+    /// Returns `true` if `slot` is one of the slots of this channel and holds `stamp`.
+    #[allow(dead_code)] // Only used by contracts.
+    fn slot_has_stamp(&self, slot: *const u8, stamp: usize) -> bool {
+        self.buffer.iter().any(|s| {
+            ptr::eq(s as *const Slot<T> as *const u8, slot)
+                && s.stamp.load(Ordering::Relaxed) == stamp
+        })
+    }
+
     /// Attempts to reserve a slot for sending a message.
+    ///
+    /// On success, the reserved slot is empty and still holds the stamp of the tail it was
+    /// reserved at, i.e. one less than the stamp `write` will store.
+    #[ensures(|ready| !*ready
+        || token.array.slot.is_null()
+        || self.slot_has_stamp(token.array.slot, token.array.stamp.wrapping_sub(1)))]
     fn start_send(&self, token: &mut Token) -> bool {
         let backoff = Backoff::new();
         let mut tail = self.tail.load(Ordering::Relaxed);
@@ -193,6 +210,8 @@ impl<T> Channel<T> {
     }
 
     /// Writes a message into the channel.
+    #[requires(token.array.slot.is_null()
+        || self.slot_has_stamp(token.array.slot, token.array.stamp.wrapping_sub(1)))]
     pub(crate) unsafe fn write(&self, token: &mut Token, msg: T) -> Result<(), T> {
         // If there is no slot, the channel is disconnected.
         if token.array.slot.is_null() {
@@ -212,6 +231,15 @@ impl<T> Channel<T> {
     }
 
     /// Attempts to reserve a slot for receiving a message.
+    ///
+    /// On success, the reserved slot holds a message, i.e. its stamp is one more than the head it
+    /// was reserved at.
+    #[ensures(|ready| !*ready
+        || token.array.slot.is_null()
+        || self.slot_has_stamp(
+            token.array.slot,
+            token.array.stamp.wrapping_sub(self.one_lap).wrapping_add(1),
+        ))]
     fn start_recv(&self, token: &mut Token) -> bool {
         let backoff = Backoff::new();
         let mut head = self.head.load(Ordering::Relaxed);
@@ -285,6 +313,11 @@ impl<T> Channel<T> {
     }
 
     /// Reads a message from the channel.
+    #[requires(token.array.slot.is_null()
+        || self.slot_has_stamp(
+            token.array.slot,
+            token.array.stamp.wrapping_sub(self.one_lap).wrapping_add(1),
+        ))]
     pub(crate) unsafe fn read(&self, token: &mut Token) -> Result<T, ()> {
         if token.array.slot.is_null() {
             // The channel is disconnected.
@@ -492,6 +525,8 @@ impl<T> Channel<T> {
     /// This method must only be called when dropping the last receiver. The
     /// destruction of all other receivers must have been observed with acquire
     /// ordering or stronger.
+    #[requires(self.is_disconnected()
+        && tail & !self.mark_bit == self.tail.load(Ordering::Relaxed) & !self.mark_bit)]
     unsafe fn discard_all_messages(&self, tail: usize) {
         debug_assert!(self.is_disconnected());
 
@@ -567,3 +602,260 @@ impl<T> Channel<T> {
         head.wrapping_add(self.one_lap) == tail & !self.mark_bit
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::kani;
+
+    use super::*;
+    use crate::sync::mpmc::interleave::{Flavor, check_interleavings};
+
+    /// Returns an empty channel of capacity 1 or 2.
+    fn any_empty_channel() -> Channel<u8> {
+        let cap = kani::any_where(|cap: &usize| (1..=2).contains(cap));
+        Channel::with_capacity(cap)
+    }
+
+    /// Returns `any_empty_channel` after an arbitrary sequence of completed operations.
+    fn any_channel() -> Channel<u8> {
+        let chan = any_empty_channel();
+        for _ in 0..3 {
+            if kani::any() {
+                let _ = chan.try_send(kani::any());
+            } else {
+                let _ = chan.try_recv();
+            }
+        }
+        if kani::any() {
+            chan.disconnect_senders();
+        }
+        chan
+    }
+
+    /// The next step of a send, named after the access it makes.
+    pub(in crate::sync::mpmc) enum SendStep {
+        LoadTail,
+        LoadStamp { tail: usize },
+        CompareExchangeTail { tail: usize },
+        LoadHead { tail: usize },
+        Write,
+    }
+
+    /// The next step of a receive, named after the access it makes.
+    pub(in crate::sync::mpmc) enum RecvStep {
+        LoadHead,
+        LoadStamp { head: usize },
+        CompareExchangeHead { head: usize },
+        LoadTail { head: usize },
+        Read,
+    }
+
+    // The steps mirror the loops of `start_send` and `start_recv`, while `write` and `read`, which
+    // make a single atomic access, are steps of their own.
+    impl Flavor for Channel<u8> {
+        type Sender = (SendStep, Token);
+        type Receiver = (RecvStep, Token);
+
+        fn sender(&self, _id: usize) -> Self::Sender {
+            (SendStep::LoadTail, Token::default())
+        }
+
+        fn receiver(&self, _id: usize) -> Self::Receiver {
+            (RecvStep::LoadHead, Token::default())
+        }
+
+        fn send_step(&self, (step, token): &mut Self::Sender, msg: u8) -> Option<bool> {
+            match *step {
+                SendStep::LoadTail => {
+                    let tail = self.tail.load(Ordering::Relaxed);
+                    *step = SendStep::LoadStamp { tail };
+                }
+                SendStep::LoadStamp { tail } => {
+                    // The channel is disconnected.
+                    if tail & self.mark_bit != 0 {
+                        *step = SendStep::LoadTail;
+                        return Some(false);
+                    }
+                    let index = tail & (self.mark_bit - 1);
+                    let stamp = self.buffer[index].stamp.load(Ordering::Acquire);
+                    *step = if tail == stamp {
+                        SendStep::CompareExchangeTail { tail }
+                    } else if stamp.wrapping_add(self.one_lap) == tail + 1 {
+                        SendStep::LoadHead { tail }
+                    } else {
+                        SendStep::LoadTail
+                    };
+                }
+                SendStep::CompareExchangeTail { tail } => {
+                    let index = tail & (self.mark_bit - 1);
+                    let lap = tail & !(self.one_lap - 1);
+                    let new_tail = if index + 1 < self.cap {
+                        tail + 1
+                    } else {
+                        lap.wrapping_add(self.one_lap)
+                    };
+                    *step = match self.tail.compare_exchange_weak(
+                        tail,
+                        new_tail,
+                        Ordering::SeqCst,
+                        Ordering::Relaxed,
+                    ) {
+                        Ok(_) => {
+                            token.array.slot = &raw const self.buffer[index] as *const u8;
+                            token.array.stamp = tail + 1;
+                            SendStep::Write
+                        }
+                        Err(_) => SendStep::LoadTail,
+                    };
+                }
+                SendStep::LoadHead { tail } => {
+                    let head = self.head.load(Ordering::Relaxed);
+                    *step = SendStep::LoadTail;
+                    // The channel is full.
+                    if head.wrapping_add(self.one_lap) == tail {
+                        return Some(false);
+                    }
+                }
+                SendStep::Write => {
+                    let array = &token.array;
+                    assert!(self.slot_has_stamp(array.slot, array.stamp.wrapping_sub(1)));
+                    // SAFETY: the slot was reserved, and the channel is not disconnected.
+                    unsafe { self.write(token, msg).unwrap() };
+                    *step = SendStep::LoadTail;
+                    return Some(true);
+                }
+            }
+            None
+        }
+
+        fn recv_step(&self, (step, token): &mut Self::Receiver) -> Option<Option<u8>> {
+            match *step {
+                RecvStep::LoadHead => {
+                    let head = self.head.load(Ordering::Relaxed);
+                    *step = RecvStep::LoadStamp { head };
+                }
+                RecvStep::LoadStamp { head } => {
+                    let index = head & (self.mark_bit - 1);
+                    let stamp = self.buffer[index].stamp.load(Ordering::Acquire);
+                    *step = if head + 1 == stamp {
+                        RecvStep::CompareExchangeHead { head }
+                    } else if stamp == head {
+                        RecvStep::LoadTail { head }
+                    } else {
+                        RecvStep::LoadHead
+                    };
+                }
+                RecvStep::CompareExchangeHead { head } => {
+                    let index = head & (self.mark_bit - 1);
+                    let lap = head & !(self.one_lap - 1);
+                    let new = if index + 1 < self.cap {
+                        head + 1
+                    } else {
+                        lap.wrapping_add(self.one_lap)
+                    };
+                    *step = match self.head.compare_exchange_weak(
+                        head,
+                        new,
+                        Ordering::SeqCst,
+                        Ordering::Relaxed,
+                    ) {
+                        Ok(_) => {
+                            token.array.slot = &raw const self.buffer[index] as *const u8;
+                            token.array.stamp = head.wrapping_add(self.one_lap);
+                            RecvStep::Read
+                        }
+                        Err(_) => RecvStep::LoadHead,
+                    };
+                }
+                RecvStep::LoadTail { head } => {
+                    let tail = self.tail.load(Ordering::Relaxed);
+                    *step = RecvStep::LoadHead;
+                    // The channel is empty, and maybe disconnected.
+                    if tail & !self.mark_bit == head {
+                        return Some(None);
+                    }
+                }
+                RecvStep::Read => {
+                    let array = &token.array;
+                    // The slot must have been written.
+                    let stamp = array.stamp.wrapping_sub(self.one_lap).wrapping_add(1);
+                    assert!(self.slot_has_stamp(array.slot, stamp));
+                    // SAFETY: the slot was reserved, and the channel is not disconnected.
+                    let msg = unsafe { self.read(token).unwrap() };
+                    *step = RecvStep::LoadHead;
+                    return Some(Some(msg));
+                }
+            }
+            None
+        }
+
+        fn disconnect(&self) {
+            self.disconnect_senders();
+        }
+
+        fn try_recv(&self) -> Result<u8, TryRecvError> {
+            self.try_recv()
+        }
+    }
+
+    #[kani::proof]
+    #[kani::unwind(17)]
+    fn check_two_senders_one_receiver() {
+        check_interleavings::<_, 2, 1, 16>(&any_empty_channel());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(17)]
+    fn check_one_sender_two_receivers() {
+        check_interleavings::<_, 1, 2, 16>(&any_empty_channel());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(17)]
+    fn check_two_senders_two_receivers() {
+        check_interleavings::<_, 2, 2, 16>(&any_empty_channel());
+    }
+
+    #[kani::proof_for_contract(Channel::<u8>::start_send)]
+    #[kani::unwind(4)]
+    fn check_start_send() {
+        let chan = any_channel();
+        let _ = chan.start_send(&mut Token::default());
+    }
+
+    #[kani::proof_for_contract(Channel::<u8>::write)]
+    #[kani::unwind(4)]
+    fn check_write() {
+        let chan = any_channel();
+        let mut token = Token::default();
+        if chan.start_send(&mut token) {
+            let _ = unsafe { chan.write(&mut token, kani::any()) };
+        }
+    }
+
+    #[kani::proof_for_contract(Channel::<u8>::start_recv)]
+    #[kani::unwind(4)]
+    fn check_start_recv() {
+        let chan = any_channel();
+        let _ = chan.start_recv(&mut Token::default());
+    }
+
+    #[kani::proof_for_contract(Channel::<u8>::read)]
+    #[kani::unwind(4)]
+    fn check_read() {
+        let chan = any_channel();
+        let mut token = Token::default();
+        if chan.start_recv(&mut token) {
+            let _ = unsafe { chan.read(&mut token) };
+        }
+    }
+
+    #[kani::proof_for_contract(Channel::<u8>::discard_all_messages)]
+    #[kani::unwind(4)]
+    fn check_discard_all_messages() {
+        let chan = any_channel();
+        let tail = chan.tail.fetch_or(chan.mark_bit, Ordering::SeqCst);
+        unsafe { chan.discard_all_messages(tail) };
+    }
+}
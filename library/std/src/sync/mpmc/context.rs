@@ -1,5 +1,7 @@
 //! Thread-local channel context.
 
+use safety::requires;
+
 use super::select::Selected;
 use super::waker::current_thread_id;
 use crate::cell::Cell;
@@ -116,6 +118,7 @@ impl Context {
     /// # Safety
     /// This may only be called from the thread this `Context` belongs to.
     #[inline]
+    #[requires(self.thread_id() == current_thread_id())]
     pub unsafe fn wait_until(&self, deadline: Option<Instant>) -> Selected {
         loop {
             // Check whether an operation has been selected.
@@ -156,4 +159,41 @@ impl Context {
     pub fn thread_id(&self) -> usize {
         self.inner.thread_id
     }
+
+    /// Creates a new `Context` which claims to belong to the thread with id `thread_id`, so that
+    /// a harness may run operations on behalf of several threads.
+    #[cfg(kani)]
+    pub fn for_thread(thread_id: usize) -> Context {
+        Context {
+            inner: Arc::new(Inner {
+                select: AtomicUsize::new(Selected::Waiting.into()),
+                packet: AtomicPtr::new(ptr::null_mut()),
+                thread: thread::current_or_unnamed(),
+                thread_id,
+            }),
+        }
+    }
+
+    /// Returns the selected operation, without waiting for one.
+    #[cfg(kani)]
+    pub fn selected(&self) -> Selected {
+        Selected::from(self.inner.select.load(Ordering::Acquire))
+    }
+}
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::kani;
+
+    use super::*;
+
+    #[kani::proof_for_contract(Context::wait_until)]
+    fn check_wait_until() {
+        let cx = Context::new();
+        let sel = if kani::any() { Selected::Aborted } else { Selected::Disconnected };
+        cx.try_select(sel).unwrap();
+        // Once an operation is selected, waiting returns it without parking.
+        assert_eq!(unsafe { cx.wait_until(None) }, sel);
+    }
 }
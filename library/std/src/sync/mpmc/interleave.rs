@@ -0,0 +1,182 @@
+//! Interleavings of senders and receivers shared by the harnesses of the channel flavors.
+//!
+//! Kani does not model threads, so the harnesses explore interleavings instead. Each flavor splits
+//! its send and receive operations into steps, such that each step makes at most one access to
+//! memory shared with the other actors: an atomic access, a critical section, or the access to a
+//! message slot the actor reserved. Each step of an interleaving lets an arbitrary actor run its
+//! next step, so actors which spin until another one makes progress just take more steps.
+//! Finally, the senders are disconnected, every operation is run to completion, and the channel
+//! is drained.
+
+use core::kani;
+
+use super::error::TryRecvError;
+
+/// Number of messages each sender sends.
+pub(super) const MSGS: usize = 2;
+
+/// The operations of a channel flavor on `u8` messages, split into steps.
+///
+/// Each step mirrors the code of the flavor up to its next access to shared memory, and asserts
+/// that the slot or packet an operation accesses is not accessed by another actor at the same
+/// time.
+pub(super) trait Flavor {
+    /// The state of a sender between two steps.
+    type Sender;
+
+    /// The state of a receiver between two steps.
+    type Receiver;
+
+    /// Returns an idle sender, for the actor numbered `id`.
+    fn sender(&self, id: usize) -> Self::Sender;
+
+    /// Returns an idle receiver, for the actor numbered `id`.
+    fn receiver(&self, id: usize) -> Self::Receiver;
+
+    /// Runs the next step of sending `msg`.
+    ///
+    /// Returns `None` while the operation is in progress, and then whether `msg` was sent. The
+    /// sender is idle again once the operation is complete.
+    fn send_step(&self, sender: &mut Self::Sender, msg: u8) -> Option<bool>;
+
+    /// Runs the next step of receiving a message.
+    ///
+    /// Returns `None` while the operation is in progress, and then the message received, if any.
+    /// The receiver is idle again once the operation is complete. Receiving from an empty channel
+    /// completes at once, unless the flavor only hands messages over from sender to receiver, in
+    /// which case it waits for a sender, or for the channel to be disconnected.
+    fn recv_step(&self, receiver: &mut Self::Receiver) -> Option<Option<u8>>;
+
+    /// Disconnects the senders.
+    fn disconnect(&self);
+
+    /// Receives a message without blocking.
+    fn try_recv(&self) -> Result<u8, TryRecvError>;
+}
+
+/// An actor of an interleaving, with the state of its current operation.
+struct Actor<S> {
+    state: S,
+    /// Whether an operation is in progress.
+    busy: bool,
+}
+
+impl<S> Actor<S> {
+    fn new(state: S) -> Self {
+        Actor { state, busy: false }
+    }
+}
+
+/// Records that a receiver got `msg`, where `next[s]` is the index of the next message from
+/// sender `s` this receiver may get.
+fn record<const SENDERS: usize>(
+    msg: u8,
+    sent: &[usize; SENDERS],
+    seen: &mut [[bool; MSGS]; SENDERS],
+    next: &mut [usize; SENDERS],
+) {
+    let (s, k) = (msg as usize / MSGS, msg as usize % MSGS);
+    assert!(s < SENDERS);
+    // The message was sent, and is received once and in order.
+    assert!(k < sent[s]);
+    assert!(!seen[s][k]);
+    assert!(k >= next[s]);
+    seen[s][k] = true;
+    next[s] = k + 1;
+}
+
+/// Runs the next step of sender `s`, which already sent `sent[s]` messages.
+fn send_step<F: Flavor, const SENDERS: usize>(
+    chan: &F,
+    sender: &mut Actor<F::Sender>,
+    s: usize,
+    sent: &mut [usize; SENDERS],
+) {
+    let msg = (s * MSGS + sent[s]) as u8;
+    match chan.send_step(&mut sender.state, msg) {
+        None => sender.busy = true,
+        Some(ok) => {
+            sender.busy = false;
+            sent[s] += ok as usize;
+        }
+    }
+}
+
+/// Runs the next step of a receiver, where `next` is as in [`record`].
+fn recv_step<F: Flavor, const SENDERS: usize>(
+    chan: &F,
+    receiver: &mut Actor<F::Receiver>,
+    sent: &[usize; SENDERS],
+    seen: &mut [[bool; MSGS]; SENDERS],
+    next: &mut [usize; SENDERS],
+) {
+    match chan.recv_step(&mut receiver.state) {
+        None => receiver.busy = true,
+        Some(msg) => {
+            receiver.busy = false;
+            if let Some(msg) = msg {
+                record(msg, sent, seen, next);
+            }
+        }
+    }
+}
+
+/// Runs `SENDERS` senders, sending up to `MSGS` messages each, and `RECEIVERS` receivers over
+/// `chan` for `STEPS` steps, then checks that every message sent was received exactly once, and
+/// in order for each pair of sender and receiver.
+pub(super) fn check_interleavings<
+    F: Flavor,
+    const SENDERS: usize,
+    const RECEIVERS: usize,
+    const STEPS: usize,
+>(
+    chan: &F,
+) {
+    let mut senders: [_; SENDERS] = core::array::from_fn(|s| Actor::new(chan.sender(s)));
+    let mut receivers: [_; RECEIVERS] =
+        core::array::from_fn(|r| Actor::new(chan.receiver(SENDERS + r)));
+    // Number of messages each sender sent.
+    let mut sent = [0; SENDERS];
+    let mut seen = [[false; MSGS]; SENDERS];
+    let mut next = [[0; SENDERS]; RECEIVERS];
+
+    for _ in 0..STEPS {
+        if kani::any() {
+            let s = kani::any_where(|s: &usize| *s < SENDERS);
+            if senders[s].busy || sent[s] < MSGS {
+                send_step(chan, &mut senders[s], s, &mut sent);
+            }
+        } else {
+            let r = kani::any_where(|r: &usize| *r < RECEIVERS);
+            recv_step(chan, &mut receivers[r], &sent, &mut seen, &mut next[r]);
+        }
+    }
+
+    // Every operation in progress completes once the senders are disconnected, within the
+    // unwinding bound of the harness.
+    chan.disconnect();
+    while senders.iter().any(|s| s.busy) || receivers.iter().any(|r| r.busy) {
+        for (s, sender) in senders.iter_mut().enumerate() {
+            if sender.busy {
+                send_step(chan, sender, s, &mut sent);
+            }
+        }
+        for (r, receiver) in receivers.iter_mut().enumerate() {
+            if receiver.busy {
+                recv_step(chan, receiver, &sent, &mut seen, &mut next[r]);
+            }
+        }
+    }
+
+    let mut drained = [0; SENDERS];
+    while let Ok(msg) = chan.try_recv() {
+        record(msg, &sent, &mut seen, &mut drained);
+    }
+
+    // No message was lost.
+    for s in 0..SENDERS {
+        for k in 0..MSGS {
+            assert_eq!(seen[s][k], k < sent[s]);
+        }
+    }
+}
@@ -1,5 +1,9 @@
 //! Unbounded channel implemented as a linked list.
 
+use core::ub_checks;
+
+use safety::{ensures, requires};
+
 use super::context::Context;
 use super::error::*;
 use super::select::{Operation, Selected, Token};
@@ -86,6 +90,7 @@ impl<T> Block<T> {
     }
 
     /// Sets the `DESTROY` bit in slots starting from `start` and destroys the block.
+    #[requires(start < BLOCK_CAP && ub_checks::can_dereference(this))]
     unsafe fn destroy(this: *mut Block<T>, start: usize) {
         // It is not necessary to set the `DESTROY` bit in the last slot because that slot has
         // begun destruction of the block.
@@ -171,7 +176,23 @@ impl<T> Channel<T> {
         }
     }
 
+    /// Returns the state of the slot `token` points to, or `None` if `token` holds no slot.
+    #[allow(dead_code)] // Only used by contracts.
+    fn slot_state(token: &ListToken) -> Option<usize> {
+        let block = token.block as *const Block<T>;
+        if block.is_null() || token.offset >= BLOCK_CAP || !ub_checks::can_dereference(block) {
+            return None;
+        }
+        // SAFETY: `block` can be dereferenced.
+        Some(unsafe { (*block).slots[token.offset].state.load(Ordering::Relaxed) })
+    }
+
     /// Attempts to reserve a slot for sending a message.
+    ///
+    /// This always succeeds, and the reserved slot has not been written into yet.
+    #[ensures(|ready| *ready
+        && (token.list.block.is_null()
+            || Self::slot_state(&token.list).is_some_and(|state| state & WRITE == 0)))]
     fn start_send(&self, token: &mut Token) -> bool {
         let backoff = Backoff::new();
         let mut tail = self.tail.index.load(Ordering::Acquire);
@@ -260,6 +281,8 @@ impl<T> Channel<T> {
     }
 
     /// Writes a message into the channel.
+    #[requires(token.list.block.is_null()
+        || Self::slot_state(&token.list).is_some_and(|state| state & WRITE == 0))]
     pub(crate) unsafe fn write(&self, token: &mut Token, msg: T) -> Result<(), T> {
         // If there is no slot, the channel is disconnected.
         if token.list.block.is_null() {
@@ -281,6 +304,12 @@ impl<T> Channel<T> {
     }
 
     /// Attempts to reserve a slot for receiving a message.
+    ///
+    /// On success, the reserved slot has not been read from yet. It may still be waiting for its
+    /// message to be written, which `read` waits for.
+    #[ensures(|ready| !*ready
+        || token.list.block.is_null()
+        || Self::slot_state(&token.list).is_some_and(|state| state & READ == 0))]
     fn start_recv(&self, token: &mut Token) -> bool {
         let backoff = Backoff::new();
         let mut head = self.head.index.load(Ordering::Acquire);
@@ -366,6 +395,8 @@ impl<T> Channel<T> {
     }
 
     /// Reads a message from the channel.
+    #[requires(token.list.block.is_null()
+        || Self::slot_state(&token.list).is_some_and(|state| state & READ == 0))]
     pub(crate) unsafe fn read(&self, token: &mut Token) -> Result<T, ()> {
         if token.list.block.is_null() {
             // The channel is disconnected.
@@ -666,3 +697,433 @@ impl<T> Drop for Channel<T> {
         }
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::kani;
+
+    use super::*;
+    use crate::sync::mpmc::interleave::{Flavor, check_interleavings};
+
+    /// Returns an empty channel whose head and tail are at `offset` in the first block, as if
+    /// `offset` messages had been sent and received already.
+    fn channel_at(offset: usize) -> Channel<u8> {
+        let chan = Channel::new();
+        let block = Block::<u8>::new();
+        for slot in &block.slots[..offset] {
+            slot.state.store(WRITE | READ, Ordering::Relaxed);
+        }
+        let block = Box::into_raw(block);
+        chan.head.block.store(block, Ordering::Relaxed);
+        chan.tail.block.store(block, Ordering::Relaxed);
+        chan.head.index.store(offset << SHIFT, Ordering::Relaxed);
+        chan.tail.index.store(offset << SHIFT, Ordering::Relaxed);
+        chan
+    }
+
+    /// Returns an empty channel which is either new, or whose next messages cross into a second
+    /// block.
+    fn any_channel() -> Channel<u8> {
+        if kani::any() { Channel::new() } else { channel_at(BLOCK_CAP - 2) }
+    }
+
+    /// The next step of a send, named after the access it makes.
+    enum SendStep {
+        LoadTailIndex,
+        LoadTailBlock { tail: usize },
+        CompareExchangeTailBlock,
+        StoreHeadBlock { tail: usize, block: *mut Block<u8> },
+        CompareExchangeTailIndex { tail: usize, block: *mut Block<u8> },
+        StoreTailBlock { offset: usize, block: *mut Block<u8> },
+        IncrementTailIndex { offset: usize, block: *mut Block<u8>, next: *mut Block<u8> },
+        StoreNext { offset: usize, block: *mut Block<u8>, next: *mut Block<u8> },
+        Write,
+    }
+
+    pub(in crate::sync::mpmc) struct Sender {
+        step: SendStep,
+        token: Token,
+        /// The block allocated in advance by `start_send`.
+        next_block: Option<Box<Block<u8>>>,
+    }
+
+    /// The next step of a receive, named after the access it makes.
+    enum RecvStep {
+        LoadHeadIndex,
+        LoadHeadBlock { head: usize },
+        LoadTailIndex { head: usize, block: *mut Block<u8> },
+        CompareExchangeHeadIndex { head: usize, block: *mut Block<u8>, new_head: usize },
+        LoadNext { block: *mut Block<u8>, new_head: usize },
+        LoadNextNext { next: *mut Block<u8>, new_head: usize },
+        StoreHeadBlock { next: *mut Block<u8>, next_index: usize },
+        StoreHeadIndex { next_index: usize },
+        LoadState,
+        ReadMessage,
+        SetRead { msg: u8 },
+        LoadDestroyedState { i: usize, msg: u8 },
+        SetDestroy { i: usize, msg: u8 },
+    }
+
+    pub(in crate::sync::mpmc) struct Receiver {
+        step: RecvStep,
+        token: Token,
+    }
+
+    impl Channel<u8> {
+        /// Returns the slot `token` points to.
+        fn token_slot(token: &Token) -> &Slot<u8> {
+            let block = token.list.block as *const Block<u8>;
+            // SAFETY: the token points to a slot of a live block.
+            unsafe { &(*block).slots[token.list.offset] }
+        }
+    }
+
+    // The steps mirror the loops of `start_send`, `start_recv` and `read`, including the one in
+    // `Block::destroy`, while `write`, which makes a single atomic access, is a step of its own.
+    impl Flavor for Channel<u8> {
+        type Sender = Sender;
+        type Receiver = Receiver;
+
+        fn sender(&self, _id: usize) -> Sender {
+            Sender { step: SendStep::LoadTailIndex, token: Token::default(), next_block: None }
+        }
+
+        fn receiver(&self, _id: usize) -> Receiver {
+            Receiver { step: RecvStep::LoadHeadIndex, token: Token::default() }
+        }
+
+        fn send_step(&self, sender: &mut Sender, msg: u8) -> Option<bool> {
+            let Sender { step, token, next_block } = sender;
+            match *step {
+                SendStep::LoadTailIndex => {
+                    let tail = self.tail.index.load(Ordering::Acquire);
+                    *step = SendStep::LoadTailBlock { tail };
+                }
+                SendStep::LoadTailBlock { tail } => {
+                    let block = self.tail.block.load(Ordering::Acquire);
+                    // The channel is disconnected.
+                    if tail & MARK_BIT != 0 {
+                        *next_block = None;
+                        *step = SendStep::LoadTailIndex;
+                        return Some(false);
+                    }
+                    let offset = (tail >> SHIFT) % LAP;
+                    *step = if offset == BLOCK_CAP {
+                        // Wait until the next block is installed.
+                        SendStep::LoadTailIndex
+                    } else if block.is_null() {
+                        SendStep::CompareExchangeTailBlock
+                    } else {
+                        SendStep::CompareExchangeTailIndex { tail, block }
+                    };
+                    if offset + 1 == BLOCK_CAP && next_block.is_none() {
+                        *next_block = Some(Block::<u8>::new());
+                    }
+                }
+                SendStep::CompareExchangeTailBlock => {
+                    // Install the first block.
+                    let new = Box::into_raw(Block::<u8>::new());
+                    *step = match self.tail.block.compare_exchange(
+                        ptr::null_mut(),
+                        new,
+                        Ordering::Release,
+                        Ordering::Relaxed,
+                    ) {
+                        Ok(_) => SendStep::StoreHeadBlock {
+                            tail: self.tail.index.load(Ordering::Relaxed),
+                            block: new,
+                        },
+                        Err(_) => {
+                            *next_block = Some(unsafe { Box::from_raw(new) });
+                            SendStep::LoadTailIndex
+                        }
+                    };
+                }
+                SendStep::StoreHeadBlock { tail, block } => {
+                    self.head.block.store(block, Ordering::Release);
+                    *step = SendStep::CompareExchangeTailIndex { tail, block };
+                }
+                SendStep::CompareExchangeTailIndex { tail, block } => {
+                    let offset = (tail >> SHIFT) % LAP;
+                    *step = match self.tail.index.compare_exchange_weak(
+                        tail,
+                        tail + (1 << SHIFT),
+                        Ordering::SeqCst,
+                        Ordering::Acquire,
+                    ) {
+                        // Install the next block after reserving the last slot of this one.
+                        Ok(_) if offset + 1 == BLOCK_CAP => {
+                            SendStep::StoreTailBlock { offset, block }
+                        }
+                        Ok(_) => {
+                            token.list.block = block as *const u8;
+                            token.list.offset = offset;
+                            SendStep::Write
+                        }
+                        Err(_) => SendStep::LoadTailIndex,
+                    };
+                }
+                SendStep::StoreTailBlock { offset, block } => {
+                    let next = Box::into_raw(next_block.take().unwrap());
+                    self.tail.block.store(next, Ordering::Release);
+                    *step = SendStep::IncrementTailIndex { offset, block, next };
+                }
+                SendStep::IncrementTailIndex { offset, block, next } => {
+                    self.tail.index.fetch_add(1 << SHIFT, Ordering::Release);
+                    *step = SendStep::StoreNext { offset, block, next };
+                }
+                SendStep::StoreNext { offset, block, next } => {
+                    unsafe { (*block).next.store(next, Ordering::Release) };
+                    token.list.block = block as *const u8;
+                    token.list.offset = offset;
+                    *step = SendStep::Write;
+                }
+                SendStep::Write => {
+                    *next_block = None;
+                    assert!(
+                        Channel::<u8>::slot_state(&token.list)
+                            .is_some_and(|state| state & WRITE == 0)
+                    );
+                    // SAFETY: the slot was reserved, and the channel is not disconnected.
+                    unsafe { self.write(token, msg).unwrap() };
+                    *step = SendStep::LoadTailIndex;
+                    return Some(true);
+                }
+            }
+            None
+        }
+
+        fn recv_step(&self, receiver: &mut Receiver) -> Option<Option<u8>> {
+            let Receiver { step, token } = receiver;
+            match *step {
+                RecvStep::LoadHeadIndex => {
+                    let head = self.head.index.load(Ordering::Acquire);
+                    *step = RecvStep::LoadHeadBlock { head };
+                }
+                RecvStep::LoadHeadBlock { head } => {
+                    let block = self.head.block.load(Ordering::Acquire);
+                    let new_head = head + (1 << SHIFT);
+                    *step = if (head >> SHIFT) % LAP == BLOCK_CAP {
+                        // Wait until the next block is installed.
+                        RecvStep::LoadHeadIndex
+                    } else if new_head & MARK_BIT == 0 {
+                        RecvStep::LoadTailIndex { head, block }
+                    } else if block.is_null() {
+                        RecvStep::LoadHeadIndex
+                    } else {
+                        RecvStep::CompareExchangeHeadIndex { head, block, new_head }
+                    };
+                }
+                RecvStep::LoadTailIndex { head, block } => {
+                    let tail = self.tail.index.load(Ordering::Relaxed);
+                    // The channel is empty, and maybe disconnected.
+                    if head >> SHIFT == tail >> SHIFT {
+                        *step = RecvStep::LoadHeadIndex;
+                        return Some(None);
+                    }
+                    let mut new_head = head + (1 << SHIFT);
+                    if (head >> SHIFT) / LAP != (tail >> SHIFT) / LAP {
+                        new_head |= MARK_BIT;
+                    }
+                    *step = if block.is_null() {
+                        // Wait until the first block is installed.
+                        RecvStep::LoadHeadIndex
+                    } else {
+                        RecvStep::CompareExchangeHeadIndex { head, block, new_head }
+                    };
+                }
+                RecvStep::CompareExchangeHeadIndex { head, block, new_head } => {
+                    let offset = (head >> SHIFT) % LAP;
+                    *step = match self.head.index.compare_exchange_weak(
+                        head,
+                        new_head,
+                        Ordering::SeqCst,
+                        Ordering::Acquire,
+                    ) {
+                        Ok(_) => {
+                            token.list.block = block as *const u8;
+                            token.list.offset = offset;
+                            if offset + 1 == BLOCK_CAP {
+                                // Move to the next block after reserving the last slot of this one.
+                                RecvStep::LoadNext { block, new_head }
+                            } else {
+                                RecvStep::LoadState
+                            }
+                        }
+                        Err(_) => RecvStep::LoadHeadIndex,
+                    };
+                }
+                RecvStep::LoadNext { block, new_head } => {
+                    // Wait until the next block is installed.
+                    let next = unsafe { (*block).next.load(Ordering::Acquire) };
+                    if !next.is_null() {
+                        *step = RecvStep::LoadNextNext { next, new_head };
+                    }
+                }
+                RecvStep::LoadNextNext { next, new_head } => {
+                    let mut next_index = (new_head & !MARK_BIT).wrapping_add(1 << SHIFT);
+                    if !unsafe { (*next).next.load(Ordering::Relaxed) }.is_null() {
+                        next_index |= MARK_BIT;
+                    }
+                    *step = RecvStep::StoreHeadBlock { next, next_index };
+                }
+                RecvStep::StoreHeadBlock { next, next_index } => {
+                    self.head.block.store(next, Ordering::Release);
+                    *step = RecvStep::StoreHeadIndex { next_index };
+                }
+                RecvStep::StoreHeadIndex { next_index } => {
+                    self.head.index.store(next_index, Ordering::Release);
+                    *step = RecvStep::LoadState;
+                }
+                RecvStep::LoadState => {
+                    // Wait until the message is written.
+                    let slot = Channel::<u8>::token_slot(token);
+                    if slot.state.load(Ordering::Acquire) & WRITE != 0 {
+                        *step = RecvStep::ReadMessage;
+                    }
+                }
+                RecvStep::ReadMessage => {
+                    assert!(
+                        Channel::<u8>::slot_state(&token.list)
+                            .is_some_and(|state| state & READ == 0)
+                    );
+                    let slot = Channel::<u8>::token_slot(token);
+                    let msg = unsafe { slot.msg.get().read().assume_init() };
+                    *step = if token.list.offset + 1 == BLOCK_CAP {
+                        // Destroy the block after reading its last slot.
+                        RecvStep::LoadDestroyedState { i: 0, msg }
+                    } else {
+                        RecvStep::SetRead { msg }
+                    };
+                }
+                RecvStep::SetRead { msg } => {
+                    let slot = Channel::<u8>::token_slot(token);
+                    if slot.state.fetch_or(READ, Ordering::AcqRel) & DESTROY == 0 {
+                        *step = RecvStep::LoadHeadIndex;
+                        return Some(Some(msg));
+                    }
+                    // Continue the destruction of the block another receiver started.
+                    *step = RecvStep::LoadDestroyedState { i: token.list.offset + 1, msg };
+                }
+                RecvStep::LoadDestroyedState { i, msg } => {
+                    let block = token.list.block as *mut Block<u8>;
+                    if i == BLOCK_CAP - 1 {
+                        // No receiver is using the block any more.
+                        drop(unsafe { Box::from_raw(block) });
+                        *step = RecvStep::LoadHeadIndex;
+                        return Some(Some(msg));
+                    }
+                    let state = unsafe { (*block).slots[i].state.load(Ordering::Acquire) };
+                    *step = if state & READ == 0 {
+                        RecvStep::SetDestroy { i, msg }
+                    } else {
+                        RecvStep::LoadDestroyedState { i: i + 1, msg }
+                    };
+                }
+                RecvStep::SetDestroy { i, msg } => {
+                    let block = token.list.block as *mut Block<u8>;
+                    let state =
+                        unsafe { (*block).slots[i].state.fetch_or(DESTROY, Ordering::AcqRel) };
+                    if state & READ == 0 {
+                        // The receiver still using slot `i` continues the destruction.
+                        *step = RecvStep::LoadHeadIndex;
+                        return Some(Some(msg));
+                    }
+                    *step = RecvStep::LoadDestroyedState { i: i + 1, msg };
+                }
+            }
+            None
+        }
+
+        fn disconnect(&self) {
+            self.disconnect_senders();
+        }
+
+        fn try_recv(&self) -> Result<u8, TryRecvError> {
+            self.try_recv()
+        }
+    }
+
+    // Destroying a block takes a step per slot.
+    #[kani::proof]
+    #[kani::unwind(40)]
+    fn check_two_senders_one_receiver() {
+        check_interleavings::<_, 2, 1, 16>(&any_channel());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(40)]
+    fn check_one_sender_two_receivers() {
+        check_interleavings::<_, 1, 2, 16>(&any_channel());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(40)]
+    fn check_two_senders_two_receivers() {
+        check_interleavings::<_, 2, 2, 16>(&any_channel());
+    }
+
+    /// Returns `any_channel` after an arbitrary sequence of completed operations.
+    fn any_used_channel() -> Channel<u8> {
+        let chan = any_channel();
+        for _ in 0..3 {
+            if kani::any() {
+                let _ = chan.try_send(kani::any());
+            } else {
+                let _ = chan.try_recv();
+            }
+        }
+        if kani::any() {
+            chan.disconnect_senders();
+        }
+        chan
+    }
+
+    #[kani::proof_for_contract(Channel::<u8>::start_send)]
+    #[kani::unwind(32)]
+    fn check_start_send() {
+        let chan = any_used_channel();
+        let _ = chan.start_send(&mut Token::default());
+    }
+
+    #[kani::proof_for_contract(Channel::<u8>::write)]
+    #[kani::unwind(32)]
+    fn check_write() {
+        let chan = any_used_channel();
+        let mut token = Token::default();
+        if chan.start_send(&mut token) {
+            let _ = unsafe { chan.write(&mut token, kani::any()) };
+        }
+    }
+
+    #[kani::proof_for_contract(Channel::<u8>::start_recv)]
+    #[kani::unwind(32)]
+    fn check_start_recv() {
+        let chan = any_used_channel();
+        let _ = chan.start_recv(&mut Token::default());
+    }
+
+    #[kani::proof_for_contract(Channel::<u8>::read)]
+    #[kani::unwind(32)]
+    fn check_read() {
+        let chan = any_used_channel();
+        let mut token = Token::default();
+        if chan.start_recv(&mut token) {
+            let _ = unsafe { chan.read(&mut token) };
+        }
+    }
+
+    #[kani::proof_for_contract(Block::<u8>::destroy)]
+    #[kani::unwind(32)]
+    fn check_destroy() {
+        let block = Block::<u8>::new();
+        for slot in &block.slots {
+            if kani::any() {
+                slot.state.store(READ, Ordering::Relaxed);
+            }
+        }
+        let start = kani::any_where(|start: &usize| *start < BLOCK_CAP);
+        unsafe { Block::destroy(Box::into_raw(block), start) };
+    }
+}
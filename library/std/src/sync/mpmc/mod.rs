@@ -140,6 +140,8 @@ mod array;
 mod context;
 mod counter;
 mod error;
+#[cfg(kani)]
+mod interleave;
 mod list;
 mod select;
 mod utils;
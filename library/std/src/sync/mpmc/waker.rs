@@ -1,5 +1,7 @@
 //! Waking mechanism for threads blocked on channel operations.
 
+use safety::ensures;
+
 use super::context::Context;
 use super::select::{Operation, Selected};
 use crate::ptr;
@@ -45,12 +47,16 @@ impl Waker {
 
     /// Registers a select operation and a packet.
     #[inline]
+    #[ensures(|_| self.selectors.len() == old(self.selectors.len()) + 1
+        && self.selectors.last().is_some_and(|entry| entry.oper == oper && entry.packet == packet))]
     pub(crate) fn register_with_packet(&mut self, oper: Operation, packet: *mut (), cx: &Context) {
         self.selectors.push(Entry { oper, packet, cx: cx.clone() });
     }
 
     /// Unregisters a select operation.
     #[inline]
+    #[ensures(|entry| entry.as_ref().is_none_or(|entry| entry.oper == oper)
+        && self.selectors.len() == old(self.selectors.len()) - entry.is_some() as usize)]
     pub(crate) fn unregister(&mut self, oper: Operation) -> Option<Entry> {
         if let Some((i, _)) =
             self.selectors.iter().enumerate().find(|&(_, entry)| entry.oper == oper)
@@ -64,6 +70,8 @@ impl Waker {
 
     /// Attempts to find another thread's entry, select the operation, and wake it up.
     #[inline]
+    #[ensures(|entry| entry.as_ref().is_none_or(|entry| entry.cx.thread_id() != current_thread_id())
+        && self.selectors.len() == old(self.selectors.len()) - entry.is_some() as usize)]
     pub(crate) fn try_select(&mut self) -> Option<Entry> {
         if self.selectors.is_empty() {
             None
@@ -95,6 +103,7 @@ impl Waker {
 
     /// Notifies all operations waiting to be ready.
     #[inline]
+    #[ensures(|_| self.observers.is_empty())]
     pub(crate) fn notify(&mut self) {
         for entry in self.observers.drain(..) {
             if entry.cx.try_select(Selected::Operation(entry.oper)).is_ok() {
@@ -105,6 +114,7 @@ impl Waker {
 
     /// Notifies all registered operations that the channel is disconnected.
     #[inline]
+    #[ensures(|_| self.selectors.len() == old(self.selectors.len()) && self.observers.is_empty())]
     pub(crate) fn disconnect(&mut self) {
         for entry in self.selectors.iter() {
             if entry.cx.try_select(Selected::Disconnected).is_ok() {
@@ -207,3 +217,137 @@ pub fn current_thread_id() -> usize {
     thread_local! { static DUMMY: u8 = const { 0 } }
     DUMMY.with(|x| (x as *const u8).addr())
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::kani;
+
+    use super::*;
+
+    /// Maximum number of entries of `any_waker`.
+    const ENTRIES: usize = 2;
+
+    /// Returns a waker whose selectors and observers are operations hooked to `hooks`, registered
+    /// by the current thread or by other ones, and maybe already selected.
+    fn any_waker(hooks: &mut [u8; ENTRIES]) -> Waker {
+        let mut waker = Waker::new();
+        for (i, hook) in hooks.iter_mut().enumerate() {
+            let thread_id = if kani::any() { i } else { current_thread_id() };
+            let cx = Context::for_thread(thread_id);
+            if kani::any() {
+                cx.try_select(Selected::Aborted).unwrap();
+            }
+            let entry = Entry { oper: Operation::hook(hook), packet: ptr::null_mut(), cx };
+            match kani::any::<u8>() {
+                0 => waker.selectors.push(entry),
+                1 => waker.observers.push(entry),
+                _ => {}
+            }
+        }
+        waker
+    }
+
+    /// Unregisters every entry of `waker`, as the threads owning them would.
+    fn clear(mut waker: Waker) {
+        waker.selectors.clear();
+        waker.observers.clear();
+    }
+
+    #[kani::proof_for_contract(Waker::register_with_packet)]
+    #[kani::unwind(3)]
+    fn check_register_with_packet() {
+        let mut hooks = [0; ENTRIES];
+        let mut waker = any_waker(&mut hooks);
+        let mut hook = 0u8;
+        let cx = Context::for_thread(kani::any());
+        waker.register_with_packet(
+            Operation::hook(&mut hook),
+            kani::any::<usize>() as *mut (),
+            &cx,
+        );
+        clear(waker);
+    }
+
+    #[kani::proof_for_contract(Waker::unregister)]
+    #[kani::unwind(3)]
+    fn check_unregister() {
+        let mut hooks = [0; ENTRIES];
+        let mut other = 0u8;
+        let oper = Operation::hook(&mut other);
+        let mut waker = any_waker(&mut hooks);
+        let oper = if kani::any() {
+            Operation::hook(&mut hooks[kani::any_where(|i: &usize| *i < ENTRIES)])
+        } else {
+            oper
+        };
+        let registered = waker.selectors.iter().any(|entry| entry.oper == oper);
+        assert_eq!(waker.unregister(oper).is_some(), registered);
+        assert!(waker.selectors.iter().all(|entry| entry.oper != oper));
+        clear(waker);
+    }
+
+    // The selected entry belongs to another thread, and its operation is selected by this call.
+    #[kani::proof_for_contract(Waker::try_select)]
+    #[kani::unwind(3)]
+    fn check_try_select() {
+        let mut hooks = [0; ENTRIES];
+        let mut waker = any_waker(&mut hooks);
+        let selectable = waker.selectors.iter().any(|entry| {
+            entry.cx.thread_id() != current_thread_id() && entry.cx.selected() == Selected::Waiting
+        });
+        let entry = waker.try_select();
+        assert_eq!(entry.is_some(), selectable);
+        if let Some(entry) = entry {
+            assert_eq!(entry.cx.selected(), Selected::Operation(entry.oper));
+        }
+        clear(waker);
+    }
+
+    #[kani::proof_for_contract(Waker::notify)]
+    #[kani::unwind(3)]
+    fn check_notify() {
+        let mut hooks = [0; ENTRIES];
+        let mut waker = any_waker(&mut hooks);
+        let observers: Vec<Context> =
+            waker.observers.iter().map(|entry| entry.cx.clone()).collect();
+        waker.notify();
+        // Every observer was woken up, unless it was already selected.
+        assert!(observers.iter().all(|cx| cx.selected() != Selected::Waiting));
+        clear(waker);
+    }
+
+    #[kani::proof_for_contract(Waker::disconnect)]
+    #[kani::unwind(3)]
+    fn check_disconnect() {
+        let mut hooks = [0; ENTRIES];
+        let mut waker = any_waker(&mut hooks);
+        waker.disconnect();
+        // The selectors stay registered until their threads unregister them.
+        assert!(waker.selectors.iter().all(|entry| entry.cx.selected() != Selected::Waiting));
+        clear(waker);
+    }
+
+    // `is_empty` follows the operations registered with the inner waker.
+    #[kani::proof]
+    #[kani::unwind(3)]
+    fn check_sync_waker() {
+        let waker = SyncWaker::new();
+        let mut hook = 0u8;
+        let oper = Operation::hook(&mut hook);
+        let cx = Context::for_thread(kani::any());
+        waker.register(oper, &cx);
+        assert!(!waker.is_empty.load(Ordering::SeqCst));
+        if kani::any() {
+            waker.notify();
+            // The operation is selected, unless it belongs to the current thread.
+            let selected = cx.selected() == Selected::Operation(oper);
+            assert_eq!(selected, cx.thread_id() != current_thread_id());
+            assert_eq!(waker.is_empty.load(Ordering::SeqCst), selected);
+        }
+        if !waker.is_empty.load(Ordering::SeqCst) {
+            assert!(waker.unregister(oper).is_some());
+        }
+        assert!(waker.is_empty.load(Ordering::SeqCst));
+    }
+}
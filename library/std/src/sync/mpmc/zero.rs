@@ -2,6 +2,10 @@
 //!
 //! This kind of channel is also known as *rendezvous* channel.
 
+use core::ub_checks;
+
+use safety::requires;
+
 use super::context::Context;
 use super::error::*;
 use super::select::{Operation, Selected, Token};
@@ -52,6 +56,17 @@ impl<T> Packet<T> {
         Packet { on_stack: true, ready: AtomicBool::new(false), msg: UnsafeCell::new(Some(msg)) }
     }
 
+    /// Returns the packet `token` points to, or `None` if `token` holds no packet.
+    #[allow(dead_code)] // Only used by contracts.
+    fn from_token(token: &ZeroToken) -> Option<&Packet<T>> {
+        let packet = token.0 as *const Packet<T>;
+        if packet.is_null() || !ub_checks::can_dereference(packet) {
+            return None;
+        }
+        // SAFETY: `packet` can be dereferenced.
+        Some(unsafe { &*packet })
+    }
+
     /// Waits until the packet becomes ready for reading or writing.
     fn wait_ready(&self) {
         let backoff = Backoff::new();
@@ -96,6 +111,10 @@ impl<T> Channel<T> {
     }
 
     /// Writes a message into the packet.
+    #[requires(token.zero.0.is_null()
+        || Packet::<T>::from_token(&token.zero).is_some_and(|packet| {
+            !packet.ready.load(Ordering::Relaxed) && unsafe { (*packet.msg.get()).is_none() }
+        }))]
     pub(crate) unsafe fn write(&self, token: &mut Token, msg: T) -> Result<(), T> {
         // If there is no packet, the channel is disconnected.
         if token.zero.0.is_null() {
@@ -111,6 +130,10 @@ impl<T> Channel<T> {
     }
 
     /// Reads a message from the packet.
+    #[requires(token.zero.0.is_null()
+        || Packet::<T>::from_token(&token.zero).is_some_and(|packet| {
+            !packet.on_stack || unsafe { (*packet.msg.get()).is_some() }
+        }))]
     pub(crate) unsafe fn read(&self, token: &mut Token) -> Result<T, ()> {
         // If there is no packet, the channel is disconnected.
         if token.zero.0.is_null() {
@@ -317,3 +340,233 @@ impl<T> Channel<T> {
         true
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::kani;
+
+    use super::*;
+    use crate::sync::mpmc::interleave::{Flavor, check_interleavings};
+
+    /// Returns a token pointing to `packet`, or a null token as if the channel was disconnected.
+    fn any_token(packet: &mut Packet<u8>) -> Token {
+        let mut token = Token::default();
+        if kani::any() {
+            token.zero.0 = (packet as *mut Packet<u8>).cast();
+        }
+        token
+    }
+
+    // A receiver waiting on an empty packet gets the message once the sender marks it ready.
+    #[kani::proof_for_contract(Channel::<u8>::write)]
+    fn check_write() {
+        let chan = Channel::<u8>::new();
+        let mut packet = Packet::empty_on_stack();
+        let mut token = any_token(&mut packet);
+        let msg = kani::any();
+        if unsafe { chan.write(&mut token, msg) }.is_ok() {
+            assert!(packet.ready.load(Ordering::Acquire));
+            assert_eq!(packet.msg.into_inner(), Some(msg));
+        } else {
+            assert!(token.zero.0.is_null());
+        }
+    }
+
+    // A receiver takes the message out of a waiting sender's packet, exactly once, and marks the
+    // packet ready so the sender may destroy it.
+    #[kani::proof_for_contract(Channel::<u8>::read)]
+    fn check_read() {
+        let chan = Channel::<u8>::new();
+        let msg = kani::any();
+        let mut packet = Packet::message_on_stack(msg);
+        let mut token = any_token(&mut packet);
+        match unsafe { chan.read(&mut token) } {
+            Ok(read) => {
+                assert_eq!(read, msg);
+                assert!(packet.ready.load(Ordering::Acquire));
+                assert!(packet.msg.into_inner().is_none());
+            }
+            Err(()) => assert!(token.zero.0.is_null()),
+        }
+    }
+
+    /// The next step of a send or receive, named after the access it makes.
+    enum Step {
+        Lock,
+        Transfer,
+        Wait,
+        Unregister,
+        WaitReady,
+    }
+
+    /// A sender or receiver, which blocks on its own packet if there is no one to pair up with.
+    pub(in crate::sync::mpmc) struct Peer {
+        id: usize,
+        cx: Context,
+        step: Step,
+        token: Token,
+        /// The packet of a blocked operation, boxed so that its address is stable.
+        packet: Box<Packet<u8>>,
+    }
+
+    impl Peer {
+        fn new(id: usize) -> Peer {
+            Peer {
+                id,
+                cx: Context::for_thread(id),
+                step: Step::Lock,
+                token: Token::default(),
+                packet: Box::new(Packet::empty_on_stack()),
+            }
+        }
+
+        /// Returns the operation of this peer.
+        fn oper(&mut self) -> Operation {
+            Operation::hook(&mut *self.packet)
+        }
+    }
+
+    // The steps mirror `send` and `recv`: the critical section which pairs up with a blocked peer
+    // or registers the operation, the transfer through the packet of the blocked peer, and the
+    // loops of `wait_until` and `wait_ready`.
+    impl Flavor for Channel<u8> {
+        type Sender = Peer;
+        type Receiver = Peer;
+
+        fn sender(&self, id: usize) -> Peer {
+            Peer::new(id)
+        }
+
+        fn receiver(&self, id: usize) -> Peer {
+            Peer::new(id)
+        }
+
+        fn send_step(&self, sender: &mut Peer, msg: u8) -> Option<bool> {
+            match sender.step {
+                Step::Lock => {
+                    let mut inner = self.inner.lock().unwrap();
+                    if let Some(operation) = inner.receivers.try_select() {
+                        sender.token.zero.0 = operation.packet;
+                        sender.step = Step::Transfer;
+                    } else if inner.is_disconnected {
+                        return Some(false);
+                    } else {
+                        sender.cx = Context::for_thread(sender.id);
+                        *sender.packet = Packet::message_on_stack(msg);
+                        let oper = sender.oper();
+                        let packet = (&raw mut *sender.packet) as *mut ();
+                        inner.senders.register_with_packet(oper, packet, &sender.cx);
+                        inner.receivers.notify();
+                        sender.step = Step::Wait;
+                    }
+                }
+                Step::Transfer => {
+                    assert!(Packet::<u8>::from_token(&sender.token.zero).is_some_and(|packet| {
+                        !packet.ready.load(Ordering::Relaxed)
+                            && unsafe { (*packet.msg.get()).is_none() }
+                    }));
+                    unsafe { self.write(&mut sender.token, msg) }.unwrap();
+                    sender.step = Step::Lock;
+                    return Some(true);
+                }
+                Step::Wait => match sender.cx.selected() {
+                    Selected::Waiting => {}
+                    Selected::Aborted => unreachable!(),
+                    Selected::Disconnected => sender.step = Step::Unregister,
+                    Selected::Operation(_) => sender.step = Step::WaitReady,
+                },
+                Step::Unregister => {
+                    let oper = sender.oper();
+                    self.inner.lock().unwrap().senders.unregister(oper).unwrap();
+                    assert_eq!(unsafe { sender.packet.msg.get().replace(None) }, Some(msg));
+                    sender.step = Step::Lock;
+                    return Some(false);
+                }
+                Step::WaitReady => {
+                    if sender.packet.ready.load(Ordering::Acquire) {
+                        sender.step = Step::Lock;
+                        return Some(true);
+                    }
+                }
+            }
+            None
+        }
+
+        fn recv_step(&self, receiver: &mut Peer) -> Option<Option<u8>> {
+            match receiver.step {
+                Step::Lock => {
+                    let mut inner = self.inner.lock().unwrap();
+                    if let Some(operation) = inner.senders.try_select() {
+                        receiver.token.zero.0 = operation.packet;
+                        receiver.step = Step::Transfer;
+                    } else if inner.is_disconnected {
+                        return Some(None);
+                    } else {
+                        receiver.cx = Context::for_thread(receiver.id);
+                        *receiver.packet = Packet::empty_on_stack();
+                        let oper = receiver.oper();
+                        let packet = (&raw mut *receiver.packet) as *mut ();
+                        inner.receivers.register_with_packet(oper, packet, &receiver.cx);
+                        inner.senders.notify();
+                        receiver.step = Step::Wait;
+                    }
+                }
+                Step::Transfer => {
+                    assert!(Packet::<u8>::from_token(&receiver.token.zero).is_some_and(|packet| {
+                        packet.on_stack && unsafe { (*packet.msg.get()).is_some() }
+                    }));
+                    let msg = unsafe { self.read(&mut receiver.token) }.unwrap();
+                    receiver.step = Step::Lock;
+                    return Some(Some(msg));
+                }
+                Step::Wait => match receiver.cx.selected() {
+                    Selected::Waiting => {}
+                    Selected::Aborted => unreachable!(),
+                    Selected::Disconnected => receiver.step = Step::Unregister,
+                    Selected::Operation(_) => receiver.step = Step::WaitReady,
+                },
+                Step::Unregister => {
+                    let oper = receiver.oper();
+                    self.inner.lock().unwrap().receivers.unregister(oper).unwrap();
+                    receiver.step = Step::Lock;
+                    return Some(None);
+                }
+                Step::WaitReady => {
+                    if receiver.packet.ready.load(Ordering::Acquire) {
+                        let msg = unsafe { receiver.packet.msg.get().replace(None) }.unwrap();
+                        receiver.step = Step::Lock;
+                        return Some(Some(msg));
+                    }
+                }
+            }
+            None
+        }
+
+        fn disconnect(&self) {
+            self.disconnect();
+        }
+
+        fn try_recv(&self) -> Result<u8, TryRecvError> {
+            self.try_recv()
+        }
+    }
+
+    #[kani::proof]
+    #[kani::unwind(13)]
+    fn check_two_senders_one_receiver() {
+        check_interleavings::<_, 2, 1, 12>(&Channel::new());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(13)]
+    fn check_one_sender_two_receivers() {
+        check_interleavings::<_, 1, 2, 12>(&Channel::new());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(13)]
+    fn check_two_senders_two_receivers() {
+        check_interleavings::<_, 2, 2, 12>(&Channel::new());
+    }
+}
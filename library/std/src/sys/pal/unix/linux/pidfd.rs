@@ -4,6 +4,7 @@ use crate::sys::cvt;
 use crate::sys::fd::FileDesc;
 use crate::sys::process::ExitStatus;
 use crate::sys_common::{AsInner, FromInner, IntoInner};
+use crate::time::Instant;
 
 #[cfg(test)]
 mod tests;
@@ -12,12 +13,27 @@ mod tests;
 pub(crate) struct PidFd(FileDesc);
 
 impl PidFd {
+    /// Opens a pidfd referring to the process `pid`.
+    ///
+    /// The caller must ensure that `pid` cannot be recycled concurrently, e.g. because it is an
+    /// unreaped child of this process.
+    pub fn open(pid: libc::pid_t) -> io::Result<Self> {
+        // pidfd_open sets CLOEXEC by default
+        let fd = cvt(unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) } as libc::c_int)?;
+        // SAFETY: the syscall just returned this file descriptor, which nothing else owns.
+        Ok(unsafe { Self::from_raw_fd(fd) })
+    }
+
     pub fn kill(&self) -> io::Result<()> {
+        self.send_signal(libc::SIGKILL)
+    }
+
+    pub fn send_signal(&self, signal: i32) -> io::Result<()> {
         cvt(unsafe {
             libc::syscall(
                 libc::SYS_pidfd_send_signal,
                 self.0.as_raw_fd(),
-                libc::SIGKILL,
+                signal,
                 crate::ptr::null::<()>(),
                 0,
             )
@@ -50,6 +66,29 @@ impl PidFd {
             Ok(Some(ExitStatus::from_waitid_siginfo(siginfo)))
         }
     }
+
+    /// Waits for the process to exit until `deadline`, returning `None` if it is still running.
+    pub fn wait_deadline(&self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        loop {
+            if let Some(status) = self.try_wait()? {
+                return Ok(Some(status));
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+            // A pidfd becomes readable once the process has exited. Round the timeout up so that
+            // we do not wake up just before the deadline.
+            let mut pollfd =
+                libc::pollfd { fd: self.0.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+            let timeout = remaining.as_nanos().div_ceil(1_000_000).min(libc::c_int::MAX as u128);
+            if let Err(err) = cvt(unsafe { libc::poll(&mut pollfd, 1, timeout as libc::c_int) }) {
+                if !err.is_interrupted() {
+                    return Err(err);
+                }
+            }
+        }
+    }
 }
 
 impl AsInner<FileDesc> for PidFd {
@@ -3,6 +3,7 @@ use crate::os::fd::{AsRawFd, RawFd};
 use crate::os::linux::process::{ChildExt, CommandExt as _};
 use crate::os::unix::process::{CommandExt as _, ExitStatusExt};
 use crate::process::Command;
+use crate::time::Duration;
 
 #[test]
 fn test_command_pidfd() {
@@ -86,6 +87,43 @@ fn test_pidfd() {
     assert_matches!(res, Err(e) if e.raw_os_error() == Some(libc::ESRCH));
 }
 
+#[test]
+fn test_wait_timeout() {
+    // Exercise both a pidfd created at spawn time and one opened on demand by `wait_timeout`. The
+    // polling fallback for kernels without pidfd support is tested in `sys::process::unix::common`.
+    for create_pidfd in [true, false] {
+        let mut child =
+            Command::new("sleep").arg("1000").create_pidfd(create_pidfd).spawn().unwrap();
+        assert_matches!(child.wait_timeout(Duration::from_millis(10)), Ok(None));
+        child.kill().expect("failed to kill child");
+        let status = child.wait_timeout(Duration::from_secs(60)).unwrap();
+        assert_eq!(status.and_then(|status| status.signal()), Some(libc::SIGKILL));
+        // The status is cached once collected, so waiting again returns it.
+        assert_eq!(child.wait().unwrap().signal(), Some(libc::SIGKILL));
+    }
+}
+
+#[test]
+fn test_pidfd_send_signal() {
+    if !probe_pidfd_support() {
+        return;
+    }
+
+    let child = Command::new("sleep")
+        .arg("1000")
+        .create_pidfd(true)
+        .spawn()
+        .expect("executing 'sleep' failed");
+
+    let fd = child.into_pidfd().unwrap();
+    fd.send_signal(libc::SIGTERM).expect("send_signal failed");
+    let status = fd.wait().expect("wait failed");
+    assert_eq!(status.signal(), Some(libc::SIGTERM));
+
+    let res = fd.send_signal(libc::SIGTERM);
+    assert_matches!(res, Err(e) if e.raw_os_error() == Some(libc::ESRCH));
+}
+
 fn probe_pidfd_support() -> bool {
     // pidfds require the pidfd_open syscall
     let our_pid = crate::process::id();
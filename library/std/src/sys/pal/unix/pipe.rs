@@ -1,9 +1,10 @@
 use crate::io::{self, BorrowedCursor, IoSlice, IoSliceMut};
 use crate::mem;
 use crate::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, RawFd};
+use crate::sys::cvt;
 use crate::sys::fd::FileDesc;
-use crate::sys::{cvt, cvt_r};
 use crate::sys_common::{FromInner, IntoInner};
+use crate::time::Instant;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Anonymous pipes
@@ -103,48 +104,77 @@ impl IntoInner<FileDesc> for AnonPipe {
 }
 
 pub fn read2(p1: AnonPipe, v1: &mut Vec<u8>, p2: AnonPipe, v2: &mut Vec<u8>) -> io::Result<()> {
-    // Set both pipes into nonblocking mode as we're gonna be reading from both
-    // in the `select` loop below, and we wouldn't want one to block the other!
-    let p1 = p1.into_inner();
-    let p2 = p2.into_inner();
-    p1.set_nonblocking(true)?;
-    p2.set_nonblocking(true)?;
-
-    let mut fds: [libc::pollfd; 2] = unsafe { mem::zeroed() };
-    fds[0].fd = p1.as_raw_fd();
-    fds[0].events = libc::POLLIN;
-    fds[1].fd = p2.as_raw_fd();
-    fds[1].events = libc::POLLIN;
-    loop {
-        // wait for either pipe to become readable using `poll`
-        cvt_r(|| unsafe { libc::poll(fds.as_mut_ptr(), 2, -1) })?;
+    read2_until(Some(p1), Some(p2), None, &mut |index, chunk| {
+        let dst = if index == 0 { &mut *v1 } else { &mut *v2 };
+        dst.extend_from_slice(chunk);
+    })
+    .map(drop)
+}
 
-        if fds[0].revents != 0 && read(&p1, v1)? {
-            p2.set_nonblocking(false)?;
-            return p2.read_to_end(v2).map(drop);
+/// Reads from whichever of `p1` and `p2` are present until both reach EOF or `deadline` passes,
+/// handing every chunk read to `sink` along with the index (0 or 1) of the pipe it came from.
+///
+/// Chunks are delivered in the order in which they were read. Returns `Ok(false)` if the
+/// deadline passed before both pipes reached EOF.
+pub fn read2_until(
+    p1: Option<AnonPipe>,
+    p2: Option<AnonPipe>,
+    deadline: Option<Instant>,
+    sink: &mut dyn FnMut(usize, &[u8]),
+) -> io::Result<bool> {
+    let mut pipes = [p1.map(IntoInner::into_inner), p2.map(IntoInner::into_inner)];
+    for fd in pipes.iter().flatten() {
+        fd.set_nonblocking(true)?;
+    }
+
+    let mut buf = [0; 8 * 1024];
+    loop {
+        let mut fds: [libc::pollfd; 2] = unsafe { mem::zeroed() };
+        let mut indices = [0; 2];
+        let mut len = 0;
+        for (index, fd) in pipes.iter().enumerate() {
+            if let Some(fd) = fd {
+                fds[len].fd = fd.as_raw_fd();
+                fds[len].events = libc::POLLIN;
+                indices[len] = index;
+                len += 1;
+            }
         }
-        if fds[1].revents != 0 && read(&p2, v2)? {
-            p1.set_nonblocking(false)?;
-            return p1.read_to_end(v1).map(drop);
+        if len == 0 {
+            return Ok(true);
         }
-    }
 
-    // Read as much as we can from each pipe, ignoring EWOULDBLOCK or
-    // EAGAIN. If we hit EOF, then this will happen because the underlying
-    // reader will return Ok(0), in which case we'll see `Ok` ourselves. In
-    // this case we flip the other fd back into blocking mode and read
-    // whatever's leftover on that file descriptor.
-    fn read(fd: &FileDesc, dst: &mut Vec<u8>) -> Result<bool, io::Error> {
-        match fd.read_to_end(dst) {
-            Ok(_) => Ok(true),
-            Err(e) => {
-                if e.raw_os_error() == Some(libc::EWOULDBLOCK)
-                    || e.raw_os_error() == Some(libc::EAGAIN)
-                {
-                    Ok(false)
-                } else {
-                    Err(e)
+        let timeout = match deadline {
+            None => -1,
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Ok(false);
                 }
+                // Round up so that we do not wake up just before the deadline.
+                remaining.as_nanos().div_ceil(1_000_000).min(libc::c_int::MAX as u128)
+                    as libc::c_int
+            }
+        };
+        if let Err(err) = cvt(unsafe { libc::poll(fds.as_mut_ptr(), len as _, timeout) }) {
+            if err.is_interrupted() {
+                continue;
+            }
+            return Err(err);
+        }
+
+        // Read at most one chunk from each ready pipe per iteration so that a
+        // chatty pipe can neither starve the other one nor overrun the deadline.
+        for (pollfd, &index) in fds[..len].iter().zip(&indices) {
+            if pollfd.revents == 0 {
+                continue;
+            }
+            let Some(fd) = &pipes[index] else { continue };
+            match fd.read(&mut buf) {
+                Ok(0) => pipes[index] = None,
+                Ok(n) => sink(index, &buf[..n]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock || e.is_interrupted() => {}
+                Err(e) => return Err(e),
             }
         }
     }
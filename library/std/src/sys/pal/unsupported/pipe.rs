@@ -1,6 +1,7 @@
 use crate::fmt;
 use crate::io::{self, BorrowedCursor, IoSlice, IoSliceMut};
 use crate::sys_common::{FromInner, IntoInner};
+use crate::time::Instant;
 
 pub struct AnonPipe(!);
 
@@ -56,6 +57,18 @@ pub fn read2(p1: AnonPipe, _v1: &mut Vec<u8>, _p2: AnonPipe, _v2: &mut Vec<u8>)
     match p1.0 {}
 }
 
+pub fn read2_until(
+    p1: Option<AnonPipe>,
+    p2: Option<AnonPipe>,
+    _deadline: Option<Instant>,
+    _sink: &mut dyn FnMut(usize, &[u8]),
+) -> io::Result<bool> {
+    if let Some(pipe) = p1.or(p2) {
+        match pipe.0 {}
+    }
+    Ok(true)
+}
+
 impl FromInner<!> for AnonPipe {
     fn from_inner(inner: !) -> Self {
         inner
@@ -9,7 +9,9 @@ use crate::sys::c;
 use crate::sys::fs::{File, OpenOptions};
 use crate::sys::handle::Handle;
 use crate::sys::pal::windows::api::{self, WinError};
+use crate::sys::pal::windows::dur2timeout;
 use crate::sys_common::{FromInner, IntoInner};
+use crate::time::Instant;
 use crate::{mem, ptr};
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -388,33 +390,81 @@ impl AnonPipe {
 }
 
 pub fn read2(p1: AnonPipe, v1: &mut Vec<u8>, p2: AnonPipe, v2: &mut Vec<u8>) -> io::Result<()> {
-    let p1 = p1.into_handle();
-    let p2 = p2.into_handle();
+    read2_until(Some(p1), Some(p2), None, &mut |index, chunk| {
+        let dst = if index == 0 { &mut *v1 } else { &mut *v2 };
+        dst.extend_from_slice(chunk);
+    })
+    .map(drop)
+}
 
-    let mut p1 = AsyncPipe::new(p1, v1)?;
-    let mut p2 = AsyncPipe::new(p2, v2)?;
-    let objs = [p1.event.as_raw_handle(), p2.event.as_raw_handle()];
+/// Reads from whichever of `p1` and `p2` are present until both reach EOF or `deadline` passes,
+/// handing every chunk read to `sink` along with the index (0 or 1) of the pipe it came from.
+///
+/// Chunks are delivered in the order in which their reads completed. Returns `Ok(false)` if the
+/// deadline passed before both pipes reached EOF.
+pub fn read2_until(
+    p1: Option<AnonPipe>,
+    p2: Option<AnonPipe>,
+    deadline: Option<Instant>,
+    sink: &mut dyn FnMut(usize, &[u8]),
+) -> io::Result<bool> {
+    // Each pipe reads into its own buffer, which is handed to `sink` and
+    // cleared once a read completes. The buffers must outlive the pipes, whose
+    // destructors may wait for a pending read to finish.
+    let mut buf1 = Vec::with_capacity(8 * 1024);
+    let mut buf2 = Vec::with_capacity(8 * 1024);
+    let mut pipes = [
+        p1.map(|p| AsyncPipe::new(p.into_handle(), &mut buf1)).transpose()?,
+        p2.map(|p| AsyncPipe::new(p.into_handle(), &mut buf2)).transpose()?,
+    ];
 
-    // In a loop we wait for either pipe's scheduled read operation to complete.
-    // If the operation completes with 0 bytes, that means EOF was reached, in
-    // which case we just finish out the other pipe entirely.
-    //
-    // Note that overlapped I/O is in general super unsafe because we have to
-    // be careful to ensure that all pointers in play are valid for the entire
-    // duration of the I/O operation (where tons of operations can also fail).
-    // The destructor for `AsyncPipe` ends up taking care of most of this.
     loop {
-        let res = unsafe { c::WaitForMultipleObjects(2, objs.as_ptr(), c::FALSE, c::INFINITE) };
-        if res == c::WAIT_OBJECT_0 {
-            if !p1.result()? || !p1.schedule_read()? {
-                return p2.finish();
+        let mut objs = [ptr::null_mut(); 2];
+        let mut indices = [0; 2];
+        let mut len = 0;
+        for (index, pipe) in pipes.iter().enumerate() {
+            if let Some(pipe) = pipe {
+                objs[len] = pipe.event.as_raw_handle();
+                indices[len] = index;
+                len += 1;
             }
-        } else if res == c::WAIT_OBJECT_0 + 1 {
-            if !p2.result()? || !p2.schedule_read()? {
-                return p1.finish();
+        }
+        if len == 0 {
+            return Ok(true);
+        }
+
+        let timeout = match deadline {
+            None => c::INFINITE,
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Ok(false);
+                }
+                dur2timeout(remaining)
             }
-        } else {
+        };
+        let res =
+            unsafe { c::WaitForMultipleObjects(len as u32, objs.as_ptr(), c::FALSE, timeout) };
+        if res == c::WAIT_TIMEOUT {
+            continue;
+        }
+        let Some(&index) = indices[..len].get(res.wrapping_sub(c::WAIT_OBJECT_0) as usize) else {
             return Err(io::Error::last_os_error());
+        };
+
+        // The events start out signaled, so the first wakeup of each pipe
+        // completes no read and merely schedules the first one.
+        let pipe = pipes[index].as_mut().unwrap();
+        if !pipe.result()? {
+            pipes[index] = None;
+            continue;
+        }
+        if !pipe.dst.is_empty() {
+            sink(index, &pipe.dst[..]);
+            pipe.dst.clear();
+        }
+        if !pipe.schedule_read()? {
+            pipes[index] = None;
         }
     }
 }
@@ -507,17 +557,6 @@ impl<'a> AsyncPipe<'a> {
         }
         Ok(amt != 0)
     }
-
-    /// Finishes out reading this pipe entirely.
-    ///
-    /// Waits for any pending and schedule read, and then calls `read_to_end`
-    /// if necessary to read all the remaining information.
-    fn finish(&mut self) -> io::Result<()> {
-        while self.result()? && self.schedule_read()? {
-            // ...
-        }
-        Ok(())
-    }
 }
 
 impl<'a> Drop for AsyncPipe<'a> {
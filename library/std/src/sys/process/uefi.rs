@@ -11,6 +11,7 @@ use crate::sys::pal::helpers;
 use crate::sys::pal::os::error_string;
 use crate::sys::pipe::AnonPipe;
 use crate::sys::unsupported;
+use crate::time::Instant;
 use crate::{fmt, io};
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -74,6 +75,14 @@ impl Command {
         self.stdout = Some(stdout);
     }
 
+    pub fn stdin_mut(&mut self) -> &mut Option<Stdio> {
+        &mut self.stdin
+    }
+
+    pub fn stdout_mut(&mut self) -> &mut Option<Stdio> {
+        &mut self.stdout
+    }
+
     pub fn stderr(&mut self, stderr: Stdio) {
         self.stderr = Some(stderr);
     }
@@ -325,6 +334,10 @@ impl Process {
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         self.0
     }
+
+    pub fn wait_deadline(&mut self, _deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        self.0
+    }
 }
 
 pub struct CommandArgs<'a> {
@@ -16,7 +16,8 @@ use crate::sys::fs::OpenOptions;
 use crate::sys::pipe::{self, AnonPipe};
 use crate::sys::process::env::{CommandEnv, CommandEnvs};
 use crate::sys_common::{FromInner, IntoInner};
-use crate::{fmt, io};
+use crate::time::{Duration, Instant};
+use crate::{cmp, fmt, io, thread};
 
 mod cstring_array;
 
@@ -98,6 +99,12 @@ pub struct Command {
     #[cfg(target_os = "linux")]
     create_pidfd: bool,
     pgroup: Option<pid_t>,
+    setsid: bool,
+    rlimits: Vec<(c_int, u64, u64)>,
+    umask: Option<u32>,
+    #[cfg(target_os = "linux")]
+    parent_death_signal: Option<c_int>,
+    close_other_fds: bool,
 }
 
 // passed back to std::process with the pipes connected to the child, if any
@@ -185,6 +192,12 @@ impl Command {
             #[cfg(target_os = "linux")]
             create_pidfd: false,
             pgroup: None,
+            setsid: false,
+            rlimits: Vec::new(),
+            umask: None,
+            #[cfg(target_os = "linux")]
+            parent_death_signal: None,
+            close_other_fds: false,
         }
     }
 
@@ -214,6 +227,22 @@ impl Command {
     pub fn pgroup(&mut self, pgroup: pid_t) {
         self.pgroup = Some(pgroup);
     }
+    pub fn setsid(&mut self, setsid: bool) {
+        self.setsid = setsid;
+    }
+    pub fn rlimit(&mut self, resource: c_int, soft: u64, hard: u64) {
+        self.rlimits.push((resource, soft, hard));
+    }
+    pub fn umask(&mut self, mask: u32) {
+        self.umask = Some(mask);
+    }
+    #[cfg(target_os = "linux")]
+    pub fn parent_death_signal(&mut self, signal: c_int) {
+        self.parent_death_signal = Some(signal);
+    }
+    pub fn close_other_fds(&mut self, close: bool) {
+        self.close_other_fds = close;
+    }
     pub fn chroot(&mut self, dir: &Path) {
         self.chroot = Some(os2c(dir.as_os_str(), &mut self.saw_nul));
         if self.cwd.is_none() {
@@ -298,6 +327,31 @@ impl Command {
     pub fn get_chroot(&self) -> Option<&CStr> {
         self.chroot.as_deref()
     }
+    #[allow(dead_code)]
+    pub fn get_setsid(&self) -> bool {
+        self.setsid
+    }
+    #[allow(dead_code)]
+    pub fn get_rlimits(&self) -> &[(c_int, u64, u64)] {
+        &self.rlimits
+    }
+    #[allow(dead_code)]
+    pub fn get_umask(&self) -> Option<u32> {
+        self.umask
+    }
+    #[cfg(not(target_os = "linux"))]
+    #[allow(dead_code)]
+    pub fn get_parent_death_signal(&self) -> Option<c_int> {
+        None
+    }
+    #[cfg(target_os = "linux")]
+    pub fn get_parent_death_signal(&self) -> Option<c_int> {
+        self.parent_death_signal
+    }
+    #[allow(dead_code)]
+    pub fn get_close_other_fds(&self) -> bool {
+        self.close_other_fds
+    }
 
     pub fn get_closures(&mut self) -> &mut Vec<Box<dyn FnMut() -> io::Result<()> + Send + Sync>> {
         &mut self.closures
@@ -315,6 +369,14 @@ impl Command {
         self.stdout = Some(stdout);
     }
 
+    pub fn stdin_mut(&mut self) -> &mut Option<Stdio> {
+        &mut self.stdin
+    }
+
+    pub fn stdout_mut(&mut self) -> &mut Option<Stdio> {
+        &mut self.stdout
+    }
+
     pub fn stderr(&mut self, stderr: Stdio) {
         self.stderr = Some(stderr);
     }
@@ -519,6 +581,22 @@ impl fmt::Debug for Command {
             if self.pgroup.is_some() {
                 debug_command.field("pgroup", &self.pgroup);
             }
+            if self.setsid {
+                debug_command.field("setsid", &self.setsid);
+            }
+            if !self.rlimits.is_empty() {
+                debug_command.field("rlimits", &self.rlimits);
+            }
+            if self.umask.is_some() {
+                debug_command.field("umask", &self.umask);
+            }
+            #[cfg(target_os = "linux")]
+            if self.parent_death_signal.is_some() {
+                debug_command.field("parent_death_signal", &self.parent_death_signal);
+            }
+            if self.close_other_fds {
+                debug_command.field("close_other_fds", &self.close_other_fds);
+            }
 
             #[cfg(target_os = "linux")]
             {
@@ -592,6 +670,32 @@ impl From<u8> for ExitCode {
     }
 }
 
+/// Repeatedly calls `try_wait` until it returns an exit status or `deadline` has passed.
+///
+/// This is the fallback for waiting with a timeout when there is no handle to block on. It does
+/// not install a `SIGCHLD` handler, which would interfere with the rest of the program; instead it
+/// sleeps between attempts, backing off exponentially so that children that exit quickly are still
+/// reaped promptly.
+#[cfg_attr(any(target_os = "fuchsia", target_os = "vita"), allow(dead_code))]
+pub fn wait_deadline_by_polling<T>(
+    deadline: Instant,
+    mut try_wait: impl FnMut() -> io::Result<Option<T>>,
+) -> io::Result<Option<T>> {
+    const MAX_DELAY: Duration = Duration::from_millis(50);
+    let mut delay = Duration::from_micros(100);
+    loop {
+        if let Some(status) = try_wait()? {
+            return Ok(Some(status));
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(None);
+        }
+        thread::sleep(cmp::min(delay, deadline - now));
+        delay = cmp::min(delay * 2, MAX_DELAY);
+    }
+}
+
 pub struct CommandArgs<'a> {
     iter: CStringIter<'a>,
 }
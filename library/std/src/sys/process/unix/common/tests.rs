@@ -190,3 +190,39 @@ fn unix_exit_statuses() {
         }
     }
 }
+
+#[test]
+fn wait_deadline_by_polling_retries() {
+    // The status becomes available on the third attempt, well before the deadline.
+    let mut attempts = 0;
+    let status = wait_deadline_by_polling(Instant::now() + Duration::from_secs(60), || {
+        attempts += 1;
+        Ok((attempts == 3).then_some(attempts))
+    });
+    assert_eq!(status.unwrap(), Some(3));
+    assert_eq!(attempts, 3);
+}
+
+#[test]
+fn wait_deadline_by_polling_times_out() {
+    let start = Instant::now();
+    let deadline = start + Duration::from_millis(20);
+    let mut attempts = 0;
+    let status = wait_deadline_by_polling(deadline, || {
+        attempts += 1;
+        Ok(None::<()>)
+    });
+    assert_eq!(status.unwrap(), None);
+    assert!(Instant::now() >= deadline);
+    // The delay backs off exponentially, from 100µs up to 50ms.
+    assert!(attempts > 1);
+
+    // A past deadline still tries once, and errors are returned as is.
+    let mut attempts = 0;
+    let status = wait_deadline_by_polling(start, || {
+        attempts += 1;
+        Err::<Option<()>, _>(io::const_error!(io::ErrorKind::Other, "try_wait failed"))
+    });
+    assert_eq!(status.unwrap_err().kind(), io::ErrorKind::Other);
+    assert_eq!(attempts, 1);
+}
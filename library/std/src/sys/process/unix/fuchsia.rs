@@ -3,6 +3,7 @@ use libc::{c_int, size_t};
 use super::common::*;
 use crate::num::NonZero;
 use crate::sys::pal::fuchsia::*;
+use crate::time::Instant;
 use crate::{fmt, io, mem, ptr};
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -23,6 +24,16 @@ impl Command {
                 "nul byte found in provided data",
             ));
         }
+        if self.get_setsid()
+            || !self.get_rlimits().is_empty()
+            || self.get_umask().is_some()
+            || self.get_close_other_fds()
+        {
+            return Err(io::const_error!(
+                io::ErrorKind::Unsupported,
+                "setsid, rlimit, umask and close_other_fds not supported by fuchsia",
+            ));
+        }
 
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
 
@@ -152,6 +163,17 @@ impl Process {
         Ok(())
     }
 
+    pub fn send_signal(&self, _signal: i32) -> io::Result<()> {
+        Err(io::const_error!(io::ErrorKind::Unsupported, "signals are not supported on Fuchsia"))
+    }
+
+    pub fn kill_process_group(&self, _signal: i32) -> io::Result<()> {
+        Err(io::const_error!(
+            io::ErrorKind::Unsupported,
+            "process groups are not supported on Fuchsia",
+        ))
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         let mut proc_info: zx_info_process_t = Default::default();
         let mut actual: size_t = 0;
@@ -183,13 +205,31 @@ impl Process {
     }
 
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        self.wait_until(0)
+    }
+
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        // Wait forever if the deadline is further away than fits in a i64.
+        let deadline = i64::try_from(deadline.saturating_duration_since(Instant::now()).as_nanos())
+            .ok()
+            .and_then(|d| d.checked_add(zx_clock_get_monotonic()))
+            .unwrap_or(ZX_TIME_INFINITE);
+        self.wait_until(deadline)
+    }
+
+    /// Waits for the process to terminate until the monotonic clock reaches `deadline`.
+    fn wait_until(&mut self, deadline: zx_time_t) -> io::Result<Option<ExitStatus>> {
         let mut proc_info: zx_info_process_t = Default::default();
         let mut actual: size_t = 0;
         let mut avail: size_t = 0;
 
         unsafe {
-            let status =
-                zx_object_wait_one(self.handle.raw(), ZX_TASK_TERMINATED, 0, ptr::null_mut());
+            let status = zx_object_wait_one(
+                self.handle.raw(),
+                ZX_TASK_TERMINATED,
+                deadline,
+                ptr::null_mut(),
+            );
             match status {
                 0 => {} // Success
                 x if x == ZX_ERR_TIMED_OUT => {
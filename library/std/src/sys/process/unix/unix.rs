@@ -13,9 +13,11 @@ use libc::{gid_t, uid_t};
 use super::common::*;
 use crate::io::{self, Error, ErrorKind};
 use crate::num::NonZero;
+use crate::os::fd::AsRawFd;
 use crate::sys::cvt;
 #[cfg(target_os = "linux")]
 use crate::sys::pal::linux::pidfd::PidFd;
+use crate::time::Instant;
 use crate::{fmt, mem, sys};
 
 cfg_if::cfg_if! {
@@ -89,6 +91,7 @@ impl Command {
         // The child calls `mem::forget` to leak the lock, which is crucial because
         // releasing a lock is not async-signal-safe.
         let env_lock = sys::env::env_read_lock();
+        let parent = unsafe { libc::getpid() };
         let pid = unsafe { self.do_fork()? };
 
         if pid == 0 {
@@ -99,7 +102,8 @@ impl Command {
             if self.get_create_pidfd() {
                 self.send_pidfd(&output);
             }
-            let Err(err) = unsafe { self.do_exec(theirs, envp.as_ref()) };
+            let report_fd = output.as_raw_fd();
+            let Err(err) = unsafe { self.do_exec(theirs, envp.as_ref(), parent, Some(report_fd)) };
             let errno = err.raw_os_error().unwrap_or(libc::EINVAL) as u32;
             let errno = errno.to_be_bytes();
             let bytes = [
@@ -234,7 +238,7 @@ impl Command {
                     // environment lock before we try to exec.
                     let _lock = sys::env::env_read_lock();
 
-                    let Err(e) = self.do_exec(theirs, envp.as_ref());
+                    let Err(e) = self.do_exec(theirs, envp.as_ref(), libc::getppid(), None);
                     e
                 }
             }
@@ -272,11 +276,17 @@ impl Command {
     // allocation). Instead we just close it manually. This will never
     // have the drop glue anyway because this code never returns (the
     // child will either exec() or invoke libc::exit)
+    //
+    // `parent` is the process whose death `parent_death_signal` refers to.
+    // `report_fd` is the descriptor errors are reported to the parent on, if
+    // this runs in a child rather than replacing the current process.
     #[cfg(not(any(target_os = "tvos", target_os = "watchos")))]
     unsafe fn do_exec(
         &mut self,
         stdio: ChildPipes,
         maybe_envp: Option<&CStringArray>,
+        #[cfg_attr(not(target_os = "linux"), allow(unused_variables))] parent: pid_t,
+        report_fd: Option<c_int>,
     ) -> Result<!, io::Error> {
         use crate::sys::{self, cvt_r};
 
@@ -290,6 +300,27 @@ impl Command {
             cvt_r(|| libc::dup2(fd, libc::STDERR_FILENO))?;
         }
 
+        // Rather than closing the other descriptors right away, mark them
+        // close-on-exec: this keeps the pipe used to report errors back to
+        // the parent working, and lets `pre_exec` closures still use them.
+        // Without closures, nothing but that pipe is needed until exec, so
+        // the descriptors above it may be closed where that is cheaper.
+        if self.get_close_other_fds() {
+            let close_above = report_fd.filter(|_| self.get_closures().is_empty());
+            set_cloexec_from(3, close_above)?;
+        }
+
+        // Resource limits are set before dropping privileges below, since
+        // raising a hard limit requires them.
+        // Limits too large for `rlim_t` are treated as unlimited.
+        for &(resource, soft, hard) in self.get_rlimits() {
+            let limit = libc::rlimit {
+                rlim_cur: soft.try_into().unwrap_or(libc::RLIM_INFINITY),
+                rlim_max: hard.try_into().unwrap_or(libc::RLIM_INFINITY),
+            };
+            cvt(libc::setrlimit(resource as _, &limit))?;
+        }
+
         #[cfg(not(target_os = "l4re"))]
         {
             if let Some(_g) = self.get_groups() {
@@ -335,11 +366,28 @@ impl Command {
         if let Some(cwd) = self.get_cwd() {
             cvt(libc::chdir(cwd.as_ptr()))?;
         }
+        if let Some(mask) = self.get_umask() {
+            libc::umask(mask as libc::mode_t);
+        }
 
+        if self.get_setsid() {
+            cvt(libc::setsid())?;
+        }
         if let Some(pgroup) = self.get_pgroup() {
             cvt(libc::setpgid(0, pgroup))?;
         }
 
+        // This must come after changing credentials above, which clears it.
+        #[cfg(target_os = "linux")]
+        if let Some(signal) = self.get_parent_death_signal() {
+            cvt(libc::prctl(libc::PR_SET_PDEATHSIG, signal as libc::c_ulong))?;
+            // If the parent already died, the signal will never be delivered,
+            // so deliver it ourselves.
+            if libc::getppid() != parent {
+                cvt(libc::raise(signal))?;
+            }
+        }
+
         // emscripten has no signal support.
         #[cfg(not(target_os = "emscripten"))]
         {
@@ -408,6 +456,8 @@ impl Command {
         &mut self,
         _stdio: ChildPipes,
         _maybe_envp: Option<&CStringArray>,
+        _parent: pid_t,
+        _report_fd: Option<c_int>,
     ) -> Result<!, io::Error> {
         return Err(Self::ERR_APPLE_TV_WATCH_NO_FORK_EXEC);
     }
@@ -457,6 +507,10 @@ impl Command {
             || !self.get_closures().is_empty()
             || self.get_groups().is_some()
             || self.get_chroot().is_some()
+            || !self.get_rlimits().is_empty()
+            || self.get_umask().is_some()
+            || self.get_parent_death_signal().is_some()
+            || self.get_close_other_fds()
         {
             return Ok(None);
         }
@@ -541,6 +595,18 @@ impl Command {
             }
         }
 
+        // A new session can be requested with POSIX_SPAWN_SETSID, which glibc supports since 2.26.
+        // On other platforms the flag is missing or unknown to `libc`, so use the fork/exec path.
+        let setsid = self.get_setsid();
+        #[cfg(not(all(target_os = "linux", any(target_env = "gnu", target_env = "musl"))))]
+        if setsid {
+            return Ok(None);
+        }
+        #[cfg(all(target_os = "linux", target_env = "gnu"))]
+        if setsid && sys::os::glibc_version().is_none_or(|version| version < (2, 26)) {
+            return Ok(None);
+        }
+
         // On QNX Neutrino, posix_spawnp can fail with EBADF in case "another thread might have opened
         // or closed a file descriptor while the posix_spawn() was occurring".
         // Documentation says "... or try calling posix_spawn() again". This is what we do here.
@@ -714,6 +780,11 @@ impl Command {
                 cvt_nz(f(file_actions.0.as_mut_ptr(), cwd.as_ptr()))?;
             }
 
+            // Like the fork/exec path, the session is created before joining `pgroup`.
+            #[cfg(all(target_os = "linux", any(target_env = "gnu", target_env = "musl")))]
+            if setsid {
+                flags |= libc::POSIX_SPAWN_SETSID;
+            }
             if let Some(pgroup) = pgroup {
                 flags |= libc::POSIX_SPAWN_SETPGROUP;
                 cvt_nz(libc::posix_spawnattr_setpgroup(attrs.0.as_mut_ptr(), pgroup))?;
@@ -922,6 +993,84 @@ impl Command {
     }
 }
 
+/// Marks every file descriptor from `lowfd` upwards as close-on-exec.
+///
+/// If `close_above` is given, the descriptors above it may be closed right
+/// away instead, on platforms that can only act on all of them at once by
+/// closing them.
+///
+/// This runs in the child between fork and exec, so it must not allocate.
+#[cfg(not(any(target_os = "tvos", target_os = "watchos")))]
+fn set_cloexec_from(
+    lowfd: c_int,
+    #[cfg_attr(
+        not(any(target_os = "dragonfly", target_os = "netbsd", target_os = "openbsd")),
+        allow(unused_variables)
+    )]
+    close_above: Option<c_int>,
+) -> io::Result<()> {
+    // close_range(2) can do this in one go since Linux 5.11. Older kernels
+    // fail with ENOSYS, or EINVAL for the unknown flag.
+    #[cfg(target_os = "linux")]
+    if unsafe {
+        libc::syscall(
+            libc::SYS_close_range,
+            lowfd as libc::c_uint,
+            libc::c_uint::MAX,
+            libc::CLOSE_RANGE_CLOEXEC as libc::c_uint,
+        )
+    } == 0
+    {
+        return Ok(());
+    }
+
+    // FreeBSD has close_range(2) since 12.2, and its CLOEXEC flag since 13.3.
+    // Older releases lack the function or fail with EINVAL for the flag.
+    #[cfg(target_os = "freebsd")]
+    {
+        use crate::sys::weak::weak;
+
+        weak!(
+            fn close_range(lowfd: libc::c_uint, highfd: libc::c_uint, flags: c_int) -> c_int;
+        );
+
+        if let Some(close_range) = close_range.get() {
+            let flags = libc::CLOSE_RANGE_CLOEXEC as c_int;
+            if unsafe { close_range(lowfd as libc::c_uint, libc::c_uint::MAX, flags) } == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    // sysconf only fails if the limit is indeterminate, in which case fall
+    // back to a common default.
+    let max = unsafe { libc::sysconf(libc::_SC_OPEN_MAX) };
+    #[cfg_attr(
+        not(any(target_os = "dragonfly", target_os = "netbsd", target_os = "openbsd")),
+        allow(unused_mut)
+    )]
+    let mut max = if max < 0 { 1024 } else { max.min(c_int::MAX as libc::c_long) as c_int };
+
+    // These only have closefrom(2), which closes the descriptors rather than
+    // marking them, so only those above `close_above` can go this way.
+    // OpenBSD fails with EBADF if none of them is open, which is as good.
+    #[cfg(any(target_os = "dragonfly", target_os = "netbsd", target_os = "openbsd"))]
+    if let Some(fd) = close_above {
+        if unsafe { libc::closefrom(fd + 1) } == 0 || sys::os::errno() == libc::EBADF {
+            max = max.min(fd + 1);
+        }
+    }
+
+    for fd in lowfd..max {
+        let flags = unsafe { libc::fcntl(fd, libc::F_GETFD) };
+        // Descriptors that are not open fail with EBADF.
+        if flags >= 0 && flags & libc::FD_CLOEXEC == 0 {
+            cvt(unsafe { libc::fcntl(fd, libc::F_SETFD, flags | libc::FD_CLOEXEC) })?;
+        }
+    }
+    Ok(())
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Processes
 ////////////////////////////////////////////////////////////////////////////////
@@ -964,8 +1113,12 @@ impl Process {
     }
 
     pub fn kill(&mut self) -> io::Result<()> {
+        self.send_signal(libc::SIGKILL)
+    }
+
+    pub fn send_signal(&self, signal: i32) -> io::Result<()> {
         // If we've already waited on this process then the pid can be recycled
-        // and used for another process, and we probably shouldn't be killing
+        // and used for another process, and we probably shouldn't be signaling
         // random processes, so return Ok because the process has exited already.
         if self.status.is_some() {
             return Ok(());
@@ -973,9 +1126,31 @@ impl Process {
         #[cfg(target_os = "linux")]
         if let Some(pid_fd) = self.pidfd.as_ref() {
             // pidfd_send_signal predates pidfd_open. so if we were able to get an fd then sending signals will work too
-            return pid_fd.kill();
+            return pid_fd.send_signal(signal);
         }
-        cvt(unsafe { libc::kill(self.pid, libc::SIGKILL) }).map(drop)
+        cvt(unsafe { libc::kill(self.pid, signal) }).map(drop)
+    }
+
+    pub fn kill_process_group(&self, signal: i32) -> io::Result<()> {
+        // Once the process has been reaped its pid, and with it the id of a
+        // group it led, may be recycled, so the group can no longer be
+        // identified reliably.
+        if self.status.is_some() {
+            return Err(io::const_error!(
+                ErrorKind::InvalidInput,
+                "cannot signal the process group of a reaped child",
+            ));
+        }
+        let pgid = cvt(unsafe { libc::getpgid(self.pid) })?;
+        // Never signal our own group, which the child is still in unless it
+        // was moved to another one, e.g. with `CommandExt::process_group`.
+        if pgid == unsafe { libc::getpgrp() } {
+            return Err(io::const_error!(
+                ErrorKind::InvalidInput,
+                "child is in the process group of the current process",
+            ));
+        }
+        cvt(unsafe { libc::kill(-pgid, signal) }).map(drop)
     }
 
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
@@ -1016,6 +1191,40 @@ impl Process {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        if let Some(status) = self.status {
+            return Ok(Some(status));
+        }
+        #[cfg(target_os = "linux")]
+        {
+            let opened;
+            let pid_fd = match self.pidfd.as_ref() {
+                Some(pid_fd) => Some(pid_fd),
+                None => {
+                    // The child has not been reaped yet, so its pid cannot have been recycled.
+                    // If pidfds are not supported, fall back to polling below.
+                    opened = PidFd::open(self.pid).ok();
+                    opened.as_ref()
+                }
+            };
+            // waitid(P_PIDFD) fails with EINVAL before Linux 5.4, although pidfd_open(2) is
+            // available since 5.3. Fall back to polling there as well.
+            if let Some(pid_fd) = pid_fd {
+                match pid_fd.wait_deadline(deadline) {
+                    Err(err) if err.raw_os_error() == Some(libc::EINVAL) => {}
+                    status => {
+                        let status = status?;
+                        if status.is_some() {
+                            self.status = status;
+                        }
+                        return Ok(status);
+                    }
+                }
+            }
+        }
+        wait_deadline_by_polling(deadline, || self.try_wait())
+    }
 }
 
 /// Unix exit statuses
@@ -1,6 +1,8 @@
-use crate::os::unix::process::{CommandExt, ExitStatusExt};
+use crate::io::ErrorKind;
+use crate::os::unix::process::{ChildExt, CommandExt, ExitStatusExt};
 use crate::panic::catch_unwind;
 use crate::process::Command;
+use crate::str;
 
 // Many of the other aspects of this situation, including heap alloc concurrency
 // safety etc., are tested in tests/ui/process/process-panic-after-fork.rs
@@ -73,3 +75,124 @@ fn test_command_fork_no_unwind() {
             || signal == libc::SIGSEGV
     );
 }
+
+#[test]
+#[cfg_attr(target_os = "emscripten", ignore)]
+fn test_send_signal() {
+    let mut child = Command::new("sleep").arg("1000").spawn().unwrap();
+    child.send_signal(libc::SIGTERM).unwrap();
+    let status = child.wait().unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGTERM));
+    // Signaling a reaped child is a no-op, like `kill`.
+    child.send_signal(libc::SIGTERM).unwrap();
+}
+
+#[test]
+#[cfg_attr(target_os = "emscripten", ignore)]
+fn test_kill_process_group() {
+    // The group contains both the shell and its background `sleep`.
+    let mut child =
+        Command::new("sh").args(["-c", "sleep 1000 & wait"]).process_group(0).spawn().unwrap();
+    child.kill_process_group(libc::SIGTERM).unwrap();
+    let status = child.wait().unwrap();
+    assert!(!status.success());
+
+    let err = child.kill_process_group(libc::SIGTERM).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+#[cfg_attr(target_os = "emscripten", ignore)]
+fn test_kill_process_group_refuses_own_group() {
+    let mut child = Command::new("sleep").arg("1000").spawn().unwrap();
+    let err = child.kill_process_group(libc::SIGTERM).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[test]
+#[cfg_attr(target_os = "emscripten", ignore)]
+fn test_command_setsid() {
+    let mut child = Command::new("sleep").arg("1000").setsid(true).spawn().unwrap();
+    let pid = child.id() as libc::pid_t;
+    assert_eq!(unsafe { libc::getsid(pid) }, pid);
+    assert_eq!(unsafe { libc::getpgid(pid) }, pid);
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[test]
+#[cfg(all(target_os = "linux", any(target_env = "gnu", target_env = "musl")))]
+fn test_command_setsid_posix_spawn() {
+    use crate::ffi::OsStr;
+
+    // `setsid` keeps the posix_spawn path where the C library supports POSIX_SPAWN_SETSID.
+    #[cfg(target_env = "gnu")]
+    let supported = crate::sys::os::glibc_version().is_some_and(|version| version >= (2, 26));
+    #[cfg(target_env = "musl")]
+    let supported = true;
+
+    let mut cmd = super::Command::new(OsStr::new("sleep"));
+    cmd.arg(OsStr::new("1000"));
+    cmd.setsid(true);
+    let (_ours, theirs) = cmd.setup_io(super::Stdio::Null, false).unwrap();
+    let process = cmd.posix_spawn(&theirs, None).unwrap();
+    assert_eq!(process.is_some(), supported);
+    if let Some(mut process) = process {
+        let pid = process.id() as libc::pid_t;
+        assert_eq!(unsafe { libc::getsid(pid) }, pid);
+        process.kill().unwrap();
+        process.wait().unwrap();
+    }
+
+    // Options that posix_spawn cannot express take the fork/exec path.
+    cmd.umask(0o022);
+    let (_ours, theirs) = cmd.setup_io(super::Stdio::Null, false).unwrap();
+    assert!(cmd.posix_spawn(&theirs, None).unwrap().is_none());
+}
+
+#[test]
+#[cfg_attr(target_os = "emscripten", ignore)]
+fn test_command_rlimit_and_umask() {
+    let output = Command::new("sh")
+        .args(["-c", "ulimit -n; umask"])
+        .rlimit(libc::RLIMIT_NOFILE as i32, 64, 64)
+        .umask(0o027)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    let mut lines = stdout.lines();
+    assert_eq!(lines.next(), Some("64"));
+    assert_eq!(lines.next().map(|mask| u32::from_str_radix(mask, 8)), Some(Ok(0o027)));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_command_close_other_fds() {
+    // A descriptor that is not close-on-exec, as a C library might create.
+    let fd = unsafe { libc::fcntl(libc::STDERR_FILENO, libc::F_DUPFD, 100) };
+    assert!(fd >= 100);
+    let script = format!("test -e /dev/fd/{fd}");
+    let leaked = Command::new("sh").args(["-c", &script]).status().unwrap();
+    let closed = Command::new("sh").args(["-c", &script]).close_other_fds(true).status().unwrap();
+    unsafe { libc::close(fd) };
+    assert!(leaked.success());
+    assert_eq!(closed.code(), Some(1));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_command_parent_death_signal() {
+    use crate::os::linux::process::CommandExt as _;
+
+    // The signal is sent when the spawning thread exits.
+    let mut child = crate::thread::spawn(|| {
+        Command::new("sleep").arg("1000").parent_death_signal(libc::SIGTERM).spawn().unwrap()
+    })
+    .join()
+    .unwrap();
+    let status = child.wait().unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGTERM));
+}
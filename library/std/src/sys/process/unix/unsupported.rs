@@ -4,6 +4,7 @@ use super::common::*;
 use crate::io;
 use crate::num::NonZero;
 use crate::sys::pal::unsupported::*;
+use crate::time::Instant;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Command
@@ -44,6 +45,14 @@ impl Process {
         unsupported()
     }
 
+    pub fn send_signal(&self, _signal: i32) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn kill_process_group(&self, _signal: i32) -> io::Result<()> {
+        unsupported()
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         unsupported()
     }
@@ -51,6 +60,10 @@ impl Process {
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         unsupported()
     }
+
+    pub fn wait_deadline(&mut self, _deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        unsupported()
+    }
 }
 
 mod wait_status;
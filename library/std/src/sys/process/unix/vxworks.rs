@@ -6,6 +6,7 @@ use crate::io::{self, ErrorKind};
 use crate::num::NonZero;
 use crate::sys::cvt;
 use crate::sys::pal::thread;
+use crate::time::Instant;
 use crate::{fmt, sys};
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -33,6 +34,16 @@ impl Command {
                 "chroot not supported by vxworks",
             ));
         }
+        if self.get_setsid()
+            || !self.get_rlimits().is_empty()
+            || self.get_umask().is_some()
+            || self.get_close_other_fds()
+        {
+            return Err(io::const_error!(
+                ErrorKind::Unsupported,
+                "setsid, rlimit, umask and close_other_fds not supported by vxworks",
+            ));
+        }
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
         let mut p = Process { pid: 0, status: None };
 
@@ -157,6 +168,22 @@ impl Process {
         }
     }
 
+    pub fn send_signal(&self, signal: i32) -> io::Result<()> {
+        // See `kill` above for why a reaped process is not signaled.
+        if self.status.is_some() {
+            Ok(())
+        } else {
+            cvt(unsafe { libc::kill(self.pid, signal) }).map(drop)
+        }
+    }
+
+    pub fn kill_process_group(&self, _signal: i32) -> io::Result<()> {
+        Err(io::const_error!(
+            ErrorKind::Unsupported,
+            "process groups are not supported on this platform",
+        ))
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         use crate::sys::cvt_r;
         if let Some(status) = self.status {
@@ -181,6 +208,10 @@ impl Process {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        wait_deadline_by_polling(deadline, || self.try_wait())
+    }
 }
 
 /// Unix exit statuses
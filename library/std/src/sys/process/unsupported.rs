@@ -6,6 +6,7 @@ use crate::path::Path;
 use crate::sys::fs::File;
 use crate::sys::pipe::AnonPipe;
 use crate::sys::unsupported;
+use crate::time::Instant;
 use crate::{fmt, io};
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -75,6 +76,14 @@ impl Command {
         self.stdout = Some(stdout);
     }
 
+    pub fn stdin_mut(&mut self) -> &mut Option<Stdio> {
+        &mut self.stdin
+    }
+
+    pub fn stdout_mut(&mut self) -> &mut Option<Stdio> {
+        &mut self.stdout
+    }
+
     pub fn stderr(&mut self, stderr: Stdio) {
         self.stderr = Some(stderr);
     }
@@ -290,6 +299,10 @@ impl Process {
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         self.0
     }
+
+    pub fn wait_deadline(&mut self, _deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        self.0
+    }
 }
 
 pub struct CommandArgs<'a> {
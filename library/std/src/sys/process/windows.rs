@@ -21,10 +21,11 @@ use crate::sys::c::{self, EXIT_FAILURE, EXIT_SUCCESS};
 use crate::sys::fs::{File, OpenOptions};
 use crate::sys::handle::Handle;
 use crate::sys::pal::api::{self, WinError, utf16};
-use crate::sys::pal::{ensure_no_nuls, fill_utf16_buf};
+use crate::sys::pal::{dur2timeout, ensure_no_nuls, fill_utf16_buf};
 use crate::sys::pipe::{self, AnonPipe};
 use crate::sys::{cvt, path, stdio};
 use crate::sys_common::IntoInner;
+use crate::time::Instant;
 use crate::{cmp, env, fmt, ptr};
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -210,6 +211,12 @@ impl Command {
     pub fn stdout(&mut self, stdout: Stdio) {
         self.stdout = Some(stdout);
     }
+    pub fn stdin_mut(&mut self) -> &mut Option<Stdio> {
+        &mut self.stdin
+    }
+    pub fn stdout_mut(&mut self) -> &mut Option<Stdio> {
+        &mut self.stdout
+    }
     pub fn stderr(&mut self, stderr: Stdio) {
         self.stderr = Some(stderr);
     }
@@ -733,6 +740,23 @@ impl Process {
         }
     }
 
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        // Deadlines more than `u32::MAX` milliseconds away are rounded up to an infinite wait.
+        let timeout = dur2timeout(deadline.saturating_duration_since(Instant::now()));
+        unsafe {
+            match c::WaitForSingleObject(self.handle.as_raw_handle(), timeout) {
+                c::WAIT_OBJECT_0 => {}
+                c::WAIT_TIMEOUT => {
+                    return Ok(None);
+                }
+                _ => return Err(io::Error::last_os_error()),
+            }
+            let mut status = 0;
+            cvt(c::GetExitCodeProcess(self.handle.as_raw_handle(), &mut status))?;
+            Ok(Some(ExitStatus(status)))
+        }
+    }
+
     pub fn handle(&self) -> &Handle {
         &self.handle
     }
@@ -0,0 +1,41 @@
+#!/bin/bash
+# Builds core, alloc and std with the safety contracts checked at runtime, checks that a contract
+# violation is reported when running a program against them, and runs the slice sorts with their
+# contracts checked.
+
+set -eu
+
+REPO_DIR=$(git rev-parse --show-toplevel)
+TARGET=$(rustc -vV | sed -n -e "s/^host: //p")
+
+cd "${REPO_DIR}/scripts/runtime_contracts"
+
+# The safety crate turns the contracts into runtime checks when this variable is set.
+export SAFETY_RUNTIME_CONTRACTS=1
+# Build the standard library from this repository instead of the toolchain's `rust-src`.
+export __CARGO_TESTS_ONLY_SRC_ROOT="${REPO_DIR}/library"
+
+echo "Building core, alloc and std with runtime contracts..."
+cargo build -Zbuild-std --target "${TARGET}"
+BIN="target/${TARGET}/debug/runtime_contracts"
+
+echo "Checking that a satisfied precondition passes..."
+"${BIN}" valid
+
+echo "Checking that a violated precondition aborts..."
+if "${BIN}" invalid 2> stderr.txt; then
+    cat stderr.txt
+    echo "Error: the violated precondition of String::from_utf8_unchecked was not detected"
+    exit 1
+fi
+cat stderr.txt
+if ! grep -q "unsafe precondition(s) violated: alloc::string::from_utf8_unchecked" stderr.txt; then
+    echo "Error: the program did not fail because of the violated precondition"
+    exit 1
+fi
+rm stderr.txt
+
+echo "Checking the contracts of the slice sorts..."
+"${BIN}" sort
+
+echo "Runtime contract checks passed."
@@ -0,0 +1,154 @@
+use anyhow::Result;
+use std::{collections::BTreeSet, fs, path::Path};
+
+use crate::scan::UnsafeFn;
+
+/// The scope of a verification challenge, as described in its Markdown document.
+#[derive(Debug, Clone)]
+pub struct Challenge {
+    /// Title of the challenge, e.g. "Challenge 5: Verify functions iterating over ...".
+    pub title: String,
+    pub status: Option<String>,
+    /// Source files or directories linked from the challenge, relative to the library directory.
+    pub paths: Vec<String>,
+    /// Modules listed in a "Location" column, e.g. `core::intrinsics`.
+    pub modules: Vec<String>,
+    /// Functions listed in the challenge's tables. If empty, the challenge covers every function
+    /// in `paths` and `modules`.
+    pub functions: BTreeSet<String>,
+}
+
+impl Challenge {
+    /// Whether we could find which source files this challenge is about.
+    pub fn has_scope(&self) -> bool {
+        !self.paths.is_empty() || !self.modules.is_empty()
+    }
+
+    pub fn contains(&self, f: &UnsafeFn) -> bool {
+        let in_scope = self
+            .paths
+            .iter()
+            .any(|path| f.file.starts_with(path.as_str()))
+            || self
+                .modules
+                .iter()
+                .any(|m| f.module == *m || f.module.starts_with(&format!("{m}::")));
+        in_scope && (self.functions.is_empty() || self.functions.contains(&f.name))
+    }
+}
+
+/// Parse all the challenge documents in `challenges_dir`, ordered by file name.
+pub fn parse_challenges(challenges_dir: &Path) -> Result<Vec<Challenge>> {
+    let mut files: Vec<_> = fs::read_dir(challenges_dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()?;
+    files.retain(|path| path.extension().is_some_and(|ext| ext == "md"));
+    files.sort();
+
+    files
+        .iter()
+        .map(|path| Ok(parse_challenge(&fs::read_to_string(path)?)))
+        .collect()
+}
+
+fn parse_challenge(doc: &str) -> Challenge {
+    let mut challenge = Challenge {
+        title: String::new(),
+        status: None,
+        paths: Vec::new(),
+        modules: Vec::new(),
+        functions: BTreeSet::new(),
+    };
+    // Header cells of the table we are in, if any.
+    let mut table_header: Option<Vec<String>> = None;
+
+    for line in doc.lines() {
+        let line = line.trim();
+        if challenge.title.is_empty() {
+            if let Some(title) = line.strip_prefix("# ") {
+                challenge.title = title.trim().to_string();
+            }
+        }
+        if let Some(status) = line.strip_prefix("- **Status:**") {
+            challenge.status = Some(status.trim().to_string());
+        }
+        for path in linked_source_paths(line) {
+            if !challenge.paths.contains(&path) {
+                challenge.paths.push(path);
+            }
+        }
+
+        if !line.starts_with('|') {
+            table_header = None;
+            continue;
+        }
+        let cells: Vec<String> = line
+            .trim_matches('|')
+            .split('|')
+            .map(|cell| cell.trim().replace('`', ""))
+            .collect();
+        let Some(header) = &table_header else {
+            table_header = Some(cells);
+            continue;
+        };
+        if cells
+            .iter()
+            .all(|cell| cell.chars().all(|c| matches!(c, '-' | ':' | ' ')))
+        {
+            continue;
+        }
+        for (column, cell) in header.iter().zip(&cells) {
+            let column = column.to_lowercase();
+            if column.starts_with("function") || column.starts_with("method") {
+                challenge
+                    .functions
+                    .extend(cell.split(',').filter_map(function_name));
+            } else if column == "location"
+                && cell.contains("::")
+                && !challenge.modules.contains(cell)
+            {
+                challenge.modules.push(cell.clone());
+            }
+        }
+    }
+    challenge
+}
+
+/// Paths of the library sources linked from a line, e.g. `alloc/src/vec/mod.rs` for a link to
+/// `https://github.com/rust-lang/rust/blob/<commit>/library/alloc/src/vec/mod.rs`.
+fn linked_source_paths(line: &str) -> Vec<String> {
+    line.match_indices("library/")
+        .filter_map(|(i, prefix)| {
+            let rest = &line[i + prefix.len()..];
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/')))
+                .unwrap_or(rest.len());
+            let path = rest[..end].trim_end_matches(['.', '/']);
+            path.contains("/src/").then(|| path.to_string())
+        })
+        .collect()
+}
+
+/// Name of a function as listed in a challenge table, e.g. `add` for `*const T::add`.
+fn function_name(cell: &str) -> Option<String> {
+    // Drop generic arguments and function arguments.
+    let mut depth = 0;
+    let path: String = cell
+        .split('(')
+        .next()?
+        .chars()
+        .filter(|&c| {
+            match c {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                _ => return depth == 0,
+            }
+            false
+        })
+        .collect();
+    let name = path.rsplit("::").next()?.trim();
+    let is_ident = !name.is_empty()
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && !name.starts_with(|c: char| c.is_ascii_digit());
+    is_ident.then(|| name.to_string())
+}
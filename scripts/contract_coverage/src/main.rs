@@ -0,0 +1,56 @@
+use anyhow::Result;
+use challenges::parse_challenges;
+use clap::Parser;
+use make_tables::write_report;
+use scan::scan_library;
+use std::path::{Path, PathBuf};
+
+mod challenges;
+mod make_tables;
+mod scan;
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Path to the library directory, e.g. `../../library`
+    #[arg(required = true)]
+    library_path: PathBuf,
+
+    /// Crates to analyze
+    #[arg(long, value_delimiter = ',', default_value = "core,alloc,std")]
+    crates: Vec<String>,
+
+    /// Directory with the challenge documents
+    /// [default: <LIBRARY_PATH>/../doc/src/challenges, if it exists]
+    #[arg(long)]
+    challenges_dir: Option<PathBuf>,
+
+    /// Output file
+    #[arg(long, default_value = "contract_coverage.md")]
+    output: PathBuf,
+
+    /// Also list every unverified unsafe function
+    #[arg(long)]
+    list_unverified: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let results = scan_library(&args.library_path, &args.crates)?;
+    println!(
+        "Found {} unsafe functions in {}",
+        results.unsafe_fns.len(),
+        args.crates.join(", ")
+    );
+
+    let challenges_dir = args.challenges_dir.clone().or_else(|| {
+        let default_dir = args.library_path.join("../doc/src/challenges");
+        Path::exists(&default_dir).then_some(default_dir)
+    });
+    let challenges = match challenges_dir {
+        Some(dir) => parse_challenges(&dir)?,
+        None => Vec::new(),
+    };
+
+    write_report(&args.output, &results, &challenges, args.list_unverified)
+}
@@ -0,0 +1,189 @@
+use anyhow::Result;
+
+use std::{collections::BTreeMap, fs::File, io::Write, path::Path};
+
+use to_markdown_table::MarkdownTable;
+
+use crate::{challenges::Challenge, scan::ScanResults, scan::UnsafeFn};
+
+/// Contract coverage of a group of unsafe functions.
+#[derive(Debug, Default, Clone, Copy)]
+struct Coverage {
+    unsafe_fns: usize,
+    with_requires: usize,
+    with_ensures: usize,
+    with_harness: usize,
+}
+
+impl Coverage {
+    fn add(&mut self, f: &UnsafeFn) {
+        self.unsafe_fns += 1;
+        self.with_requires += f.has_requires as usize;
+        self.with_ensures += f.has_ensures as usize;
+        self.with_harness += f.has_harness as usize;
+    }
+
+    fn row(&self, name: String) -> Vec<String> {
+        vec![
+            name,
+            self.unsafe_fns.to_string(),
+            self.with_requires.to_string(),
+            self.with_ensures.to_string(),
+            self.with_harness.to_string(),
+            (self.unsafe_fns - self.with_harness).to_string(),
+        ]
+    }
+}
+
+const COVERAGE_HEADERS: [&str; 5] = [
+    "Unsafe Functions",
+    "With `requires`",
+    "With `ensures`",
+    "With Harness",
+    "Unverified",
+];
+
+fn coverage_headers(first: &str) -> Vec<String> {
+    std::iter::once(first)
+        .chain(COVERAGE_HEADERS)
+        .map(String::from)
+        .collect()
+}
+
+/// Group the functions by `key`, and compute the coverage of each group and of all functions.
+fn coverage_table<'a>(
+    first_header: &str,
+    fns: impl Iterator<Item = &'a UnsafeFn>,
+    key: impl Fn(&UnsafeFn) -> String,
+) -> Result<MarkdownTable> {
+    let mut groups: BTreeMap<String, Coverage> = BTreeMap::new();
+    let mut total = Coverage::default();
+    for f in fns {
+        groups.entry(key(f)).or_default().add(f);
+        total.add(f);
+    }
+    let rows = groups
+        .into_iter()
+        .map(|(name, coverage)| coverage.row(format!("`{name}`")))
+        .chain(std::iter::once(total.row("Total".to_string())))
+        .collect();
+    Ok(MarkdownTable::new(
+        Some(coverage_headers(first_header)),
+        rows,
+    )?)
+}
+
+fn challenge_table(challenges: &[Challenge], unsafe_fns: &[UnsafeFn]) -> Result<MarkdownTable> {
+    let mut headers = coverage_headers("Challenge");
+    headers.insert(1, "Status".to_string());
+    let rows = challenges
+        .iter()
+        .map(|challenge| {
+            let mut row = if challenge.has_scope() {
+                let mut coverage = Coverage::default();
+                unsafe_fns
+                    .iter()
+                    .filter(|f| challenge.contains(f))
+                    .for_each(|f| coverage.add(f));
+                coverage.row(challenge.title.clone())
+            } else {
+                // We could not find which sources the challenge is about.
+                std::iter::once(challenge.title.clone())
+                    .chain(COVERAGE_HEADERS.iter().map(|_| "-".to_string()))
+                    .collect()
+            };
+            row.insert(1, challenge.status.clone().unwrap_or_default());
+            row
+        })
+        .collect();
+    Ok(MarkdownTable::new(Some(headers), rows)?)
+}
+
+fn unverified_table(unsafe_fns: &[UnsafeFn]) -> Result<MarkdownTable> {
+    let mut unverified: Vec<_> = unsafe_fns.iter().filter(|f| !f.has_harness).collect();
+    unverified.sort_by_key(|f| (f.module.clone(), f.qualified_name()));
+    let yes_no = |b: bool| if b { "Yes" } else { "No" }.to_string();
+    let rows = unverified
+        .into_iter()
+        .map(|f| {
+            vec![
+                format!("`{}`", f.module),
+                format!("`{}`", f.qualified_name()),
+                yes_no(f.has_requires),
+                yes_no(f.has_ensures),
+            ]
+        })
+        .collect();
+    Ok(MarkdownTable::new(
+        Some(vec![
+            "Module",
+            "Function",
+            "Has `requires`",
+            "Has `ensures`",
+        ]),
+        rows,
+    )?)
+}
+
+/// Write the coverage tables to `out_path`.
+pub fn write_report(
+    out_path: &Path,
+    results: &ScanResults,
+    challenges: &[Challenge],
+    list_unverified: bool,
+) -> Result<()> {
+    let unsafe_fns = &results.unsafe_fns;
+    let mut out_file = File::create(out_path)?;
+
+    writeln!(out_file, "# Contract Coverage\n")?;
+    writeln!(
+        out_file,
+        "An unsafe function is unverified if no `#[kani::proof_for_contract]` harness targets it."
+    )?;
+    writeln!(
+        out_file,
+        "Functions defined in a macro are counted once per macro definition.\n"
+    )?;
+
+    writeln!(out_file, "## Crates\n")?;
+    writeln!(
+        out_file,
+        "{}",
+        coverage_table("Crate", unsafe_fns.iter(), |f| f.krate.clone())?
+    )?;
+    writeln!(
+        out_file,
+        "{} harness(es) could not be matched with an unsafe function: they verify a safe function, \
+        or their target is a macro metavariable.",
+        results.unmatched_harnesses
+    )?;
+    if !results.unparsed_files.is_empty() {
+        writeln!(
+            out_file,
+            "\nThe following files could not be parsed and are not included:\n"
+        )?;
+        for file in &results.unparsed_files {
+            writeln!(out_file, "- `{file}`")?;
+        }
+    }
+
+    if !challenges.is_empty() {
+        writeln!(out_file, "\n## Challenges\n")?;
+        writeln!(out_file, "{}", challenge_table(challenges, unsafe_fns)?)?;
+    }
+
+    writeln!(out_file, "\n## Modules\n")?;
+    writeln!(
+        out_file,
+        "{}",
+        coverage_table("Module", unsafe_fns.iter(), |f| f.module.clone())?
+    )?;
+
+    if list_unverified {
+        writeln!(out_file, "\n## Unverified Functions\n")?;
+        writeln!(out_file, "{}", unverified_table(unsafe_fns)?)?;
+    }
+
+    println!("Wrote contract coverage report to {}", out_path.display());
+    Ok(())
+}
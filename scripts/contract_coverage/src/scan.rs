@@ -0,0 +1,526 @@
+use anyhow::Result;
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::ToTokens;
+use std::{
+    fs,
+    path::{Component, Path},
+};
+use syn::visit::{self, Visit};
+use walkdir::WalkDir;
+
+/// An `unsafe fn` found in the library sources.
+#[derive(Debug, Clone)]
+pub struct UnsafeFn {
+    /// Crate the function is defined in, e.g. `core`.
+    pub krate: String,
+    /// Module the function is defined in, e.g. `core::ptr::non_null`.
+    pub module: String,
+    /// Path of the source file, relative to the library directory.
+    pub file: String,
+    /// Type of the enclosing `impl` or trait, if any and if we could name it.
+    pub self_ty: Option<String>,
+    pub name: String,
+    /// Name of the macro the function is defined in, if any.
+    pub in_macro: Option<String>,
+    pub has_requires: bool,
+    pub has_ensures: bool,
+    /// Whether some `#[kani::proof_for_contract]` harness targets this function.
+    pub has_harness: bool,
+}
+
+impl UnsafeFn {
+    /// Name of the function qualified with its `impl` type, e.g. `NonNull::add`.
+    pub fn qualified_name(&self) -> String {
+        let name = match &self.self_ty {
+            Some(ty) => format!("{ty}::{}", self.name),
+            None => self.name.clone(),
+        };
+        match &self.in_macro {
+            Some(mac) => format!("{name} (in {mac}!)"),
+            None => name,
+        }
+    }
+}
+
+/// A `#[kani::proof_for_contract(target)]` harness.
+#[derive(Debug, Clone)]
+struct Harness {
+    krate: String,
+    module: String,
+    /// Path segments of the target, without generic arguments. Macro metavariables are `*`.
+    target: Vec<String>,
+}
+
+/// Results of scanning the library sources.
+#[derive(Debug, Default)]
+pub struct ScanResults {
+    pub unsafe_fns: Vec<UnsafeFn>,
+    /// Number of harnesses whose target could not be matched with an `unsafe fn`.
+    /// These are mostly harnesses for safe functions with contracts.
+    pub unmatched_harnesses: usize,
+    /// Files that could not even be tokenized.
+    pub unparsed_files: Vec<String>,
+}
+
+/// Scan the `src` directory of each crate in `crates` under `library_path`.
+pub fn scan_library(library_path: &Path, crates: &[String]) -> Result<ScanResults> {
+    let mut results = ScanResults::default();
+    let mut harnesses = Vec::new();
+
+    for krate in crates {
+        let src_dir = library_path.join(krate).join("src");
+        for entry in WalkDir::new(&src_dir).sort_by_file_name() {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "rs") {
+                continue;
+            }
+            let file = path
+                .strip_prefix(library_path)?
+                .to_string_lossy()
+                .into_owned();
+            let rel_path = path.strip_prefix(&src_dir)?;
+            // Unit tests are not part of the library.
+            if rel_path.iter().any(|c| c == "tests" || c == "tests.rs") {
+                continue;
+            }
+            let contents = fs::read_to_string(path)?;
+            let mut visitor = FileVisitor {
+                krate,
+                file: &file,
+                modules: file_module_path(krate, rel_path),
+                self_ty: None,
+                unsafe_fns: &mut results.unsafe_fns,
+                harnesses: &mut harnesses,
+            };
+            match syn::parse_file(&contents) {
+                Ok(ast) => visitor.visit_file(&ast),
+                // `syn` does not support some of the unstable syntax used in the library. Fall back
+                // to scanning the tokens of the file, which does not track inline modules.
+                Err(_) => match contents.parse::<TokenStream>() {
+                    Ok(tokens) => visitor.scan_tokens(tokens, None),
+                    Err(err) => {
+                        println!("[WARNING] Unable to parse {file}: {err}");
+                        results.unparsed_files.push(file);
+                    }
+                },
+            }
+        }
+    }
+
+    for harness in &harnesses {
+        if !mark_harness_target(&mut results.unsafe_fns, harness) {
+            results.unmatched_harnesses += 1;
+        }
+    }
+    Ok(results)
+}
+
+/// Module path of a source file, given its path relative to the crate's `src` directory.
+/// For example, `ptr/mod.rs` in `core` is `core::ptr`.
+fn file_module_path(krate: &str, rel_path: &Path) -> Vec<String> {
+    let mut modules = vec![krate.to_string()];
+    let components: Vec<_> = rel_path
+        .with_extension("")
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+    let n = components.len();
+    for (i, name) in components.into_iter().enumerate() {
+        let is_last = i + 1 == n;
+        if is_last && (name == "mod" || (n == 1 && name == "lib")) {
+            continue;
+        }
+        modules.push(name);
+    }
+    modules
+}
+
+struct FileVisitor<'a> {
+    krate: &'a str,
+    file: &'a str,
+    modules: Vec<String>,
+    self_ty: Option<String>,
+    unsafe_fns: &'a mut Vec<UnsafeFn>,
+    harnesses: &'a mut Vec<Harness>,
+}
+
+impl FileVisitor<'_> {
+    fn record_fn(
+        &mut self,
+        name: String,
+        attrs: &[TokenStream],
+        is_unsafe: bool,
+        in_macro: Option<&str>,
+    ) {
+        for attr in attrs {
+            self.record_harnesses(attr);
+        }
+        if !is_unsafe {
+            return;
+        }
+        self.unsafe_fns.push(UnsafeFn {
+            krate: self.krate.to_string(),
+            module: self.modules.join("::"),
+            file: self.file.to_string(),
+            self_ty: self.self_ty.clone(),
+            name,
+            in_macro: in_macro.map(str::to_string),
+            has_requires: attrs.iter().any(|attr| contains_call(attr, "requires")),
+            has_ensures: attrs.iter().any(|attr| contains_call(attr, "ensures")),
+            has_harness: false,
+        });
+    }
+
+    fn record_harnesses(&mut self, tokens: &TokenStream) {
+        for target in harness_targets(tokens) {
+            self.harnesses.push(Harness {
+                krate: self.krate.to_string(),
+                module: self.modules.join("::"),
+                target,
+            });
+        }
+    }
+
+    /// Find the functions and harnesses defined in the body of a macro, or in an item that `syn`
+    /// cannot represent (e.g., a function declaration without a body).
+    fn scan_tokens(&mut self, tokens: TokenStream, in_macro: Option<&str>) {
+        let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+        let mut attrs = Vec::new();
+        let mut saw_unsafe = false;
+        let mut i = 0;
+        while i < tokens.len() {
+            match &tokens[i] {
+                TokenTree::Punct(p) if p.as_char() == '#' => {
+                    if let Some(TokenTree::Group(g)) = tokens.get(i + 1) {
+                        if g.delimiter() == Delimiter::Bracket {
+                            attrs.push(g.stream());
+                            i += 2;
+                            continue;
+                        }
+                    }
+                }
+                TokenTree::Punct(p) if p.as_char() == ';' => {
+                    attrs.clear();
+                    saw_unsafe = false;
+                }
+                TokenTree::Ident(ident) if ident == "unsafe" => saw_unsafe = true,
+                TokenTree::Ident(ident) if ident == "impl" => {
+                    let Some(body) = tokens[i + 1..].iter().position(is_brace_group) else {
+                        i += 1;
+                        continue;
+                    };
+                    let body = i + 1 + body;
+                    let self_ty = impl_self_ty(&tokens[i + 1..body]);
+                    let outer_self_ty = std::mem::replace(&mut self.self_ty, self_ty);
+                    if let TokenTree::Group(g) = &tokens[body] {
+                        self.scan_tokens(g.stream(), in_macro);
+                    }
+                    self.self_ty = outer_self_ty;
+                    attrs.clear();
+                    saw_unsafe = false;
+                    i = body + 1;
+                    continue;
+                }
+                TokenTree::Ident(ident) if ident == "fn" => {
+                    // Functions named by a metavariable, e.g. `fn $name`, are skipped.
+                    if let Some(TokenTree::Ident(name)) = tokens.get(i + 1) {
+                        let attrs = std::mem::take(&mut attrs);
+                        self.record_fn(name.to_string(), &attrs, saw_unsafe, in_macro);
+                    }
+                    saw_unsafe = false;
+                }
+                TokenTree::Group(g) => {
+                    // Attributes that are not followed by a function, e.g. on a `const`.
+                    for attr in attrs.drain(..) {
+                        self.record_harnesses(&attr);
+                    }
+                    self.scan_tokens(g.stream(), in_macro);
+                    if g.delimiter() == Delimiter::Brace {
+                        saw_unsafe = false;
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        for attr in attrs {
+            self.record_harnesses(&attr);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for FileVisitor<'_> {
+    fn visit_item_mod(&mut self, item: &'ast syn::ItemMod) {
+        if item.attrs.iter().any(|attr| is_cfg_test(&attr.meta)) {
+            return;
+        }
+        self.modules.push(item.ident.to_string());
+        visit::visit_item_mod(self, item);
+        self.modules.pop();
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast syn::ItemImpl) {
+        let outer_self_ty = std::mem::replace(&mut self.self_ty, type_name(&item.self_ty));
+        visit::visit_item_impl(self, item);
+        self.self_ty = outer_self_ty;
+    }
+
+    fn visit_item_trait(&mut self, item: &'ast syn::ItemTrait) {
+        let outer_self_ty = self.self_ty.replace(item.ident.to_string());
+        visit::visit_item_trait(self, item);
+        self.self_ty = outer_self_ty;
+    }
+
+    fn visit_item_fn(&mut self, item: &'ast syn::ItemFn) {
+        // Nested functions are not methods of the enclosing `impl`.
+        let outer_self_ty = self.self_ty.take();
+        self.record_fn(
+            item.sig.ident.to_string(),
+            &attr_tokens(&item.attrs),
+            item.sig.unsafety.is_some(),
+            None,
+        );
+        visit::visit_item_fn(self, item);
+        self.self_ty = outer_self_ty;
+    }
+
+    fn visit_impl_item_fn(&mut self, item: &'ast syn::ImplItemFn) {
+        self.record_fn(
+            item.sig.ident.to_string(),
+            &attr_tokens(&item.attrs),
+            item.sig.unsafety.is_some(),
+            None,
+        );
+        let outer_self_ty = self.self_ty.take();
+        visit::visit_impl_item_fn(self, item);
+        self.self_ty = outer_self_ty;
+    }
+
+    fn visit_trait_item_fn(&mut self, item: &'ast syn::TraitItemFn) {
+        // Required methods have no body to verify.
+        if item.default.is_some() {
+            self.record_fn(
+                item.sig.ident.to_string(),
+                &attr_tokens(&item.attrs),
+                item.sig.unsafety.is_some(),
+                None,
+            );
+        }
+        let outer_self_ty = self.self_ty.take();
+        visit::visit_trait_item_fn(self, item);
+        self.self_ty = outer_self_ty;
+    }
+
+    fn visit_item_macro(&mut self, item: &'ast syn::ItemMacro) {
+        let name = match &item.ident {
+            Some(ident) => ident.to_string(),
+            None => item
+                .mac
+                .path
+                .segments
+                .last()
+                .map(|seg| seg.ident.to_string())
+                .unwrap_or_default(),
+        };
+        self.scan_tokens(item.mac.tokens.clone(), Some(&name));
+    }
+
+    fn visit_item(&mut self, item: &'ast syn::Item) {
+        if let syn::Item::Verbatim(tokens) = item {
+            self.scan_tokens(tokens.clone(), None);
+        }
+        visit::visit_item(self, item);
+    }
+
+    fn visit_impl_item(&mut self, item: &'ast syn::ImplItem) {
+        if let syn::ImplItem::Verbatim(tokens) = item {
+            self.scan_tokens(tokens.clone(), None);
+        }
+        visit::visit_impl_item(self, item);
+    }
+}
+
+fn attr_tokens(attrs: &[syn::Attribute]) -> Vec<TokenStream> {
+    attrs
+        .iter()
+        .map(|attr| attr.meta.to_token_stream())
+        .collect()
+}
+
+fn is_cfg_test(meta: &syn::Meta) -> bool {
+    let syn::Meta::List(list) = meta else {
+        return false;
+    };
+    list.path.is_ident("cfg") && list.tokens.to_string() == "test"
+}
+
+fn is_brace_group(token: &TokenTree) -> bool {
+    matches!(token, TokenTree::Group(g) if g.delimiter() == Delimiter::Brace)
+}
+
+/// Name of a type, e.g. `NonNull` for `NonNull<T>` and `*const T` for a raw pointer.
+fn type_name(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Path(path) => path.path.segments.last().map(|seg| seg.ident.to_string()),
+        syn::Type::Ptr(ptr) if ptr.mutability.is_some() => Some("*mut T".to_string()),
+        syn::Type::Ptr(_) => Some("*const T".to_string()),
+        syn::Type::Slice(_) => Some("[T]".to_string()),
+        syn::Type::Reference(reference) => type_name(&reference.elem),
+        _ => None,
+    }
+}
+
+/// Name of the type of an `impl` block in a macro, given the tokens between `impl` and the body.
+/// Returns `None` if the type is a metavariable.
+fn impl_self_ty(tokens: &[TokenTree]) -> Option<String> {
+    // Skip the `impl` generics, and the trait name if there is one.
+    let mut depth = 0;
+    let mut start = None;
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+            TokenTree::Punct(p) if p.as_char() == '>' => depth -= 1,
+            TokenTree::Ident(ident) if depth == 0 && ident == "for" => start = Some(i + 1),
+            TokenTree::Ident(ident) if depth == 0 && ident == "where" => break,
+            TokenTree::Ident(ident)
+                if depth == 0 && start.is_none() && ident != "const" && ident != "unsafe" =>
+            {
+                start = Some(i);
+            }
+            _ => {}
+        }
+    }
+    let start = start?;
+    match tokens.get(start) {
+        Some(TokenTree::Ident(ident)) => {
+            let is_metavar = start > 0
+                && matches!(&tokens[start - 1], TokenTree::Punct(p) if p.as_char() == '$');
+            (!is_metavar).then(|| ident.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Whether `tokens` contain a call to `name`, e.g. `requires(...)` or `safety::requires(...)`.
+fn contains_call(tokens: &TokenStream, name: &str) -> bool {
+    let tokens: Vec<TokenTree> = tokens.clone().into_iter().collect();
+    tokens.iter().enumerate().any(|(i, token)| match token {
+        TokenTree::Ident(ident) => {
+            ident == name
+                && matches!(tokens.get(i + 1), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis)
+        }
+        TokenTree::Group(g) => contains_call(&g.stream(), name),
+        _ => false,
+    })
+}
+
+/// Targets of the `proof_for_contract(...)` attributes in `tokens`.
+fn harness_targets(tokens: &TokenStream) -> Vec<Vec<String>> {
+    let tokens: Vec<TokenTree> = tokens.clone().into_iter().collect();
+    let mut targets = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Ident(ident) if ident == "proof_for_contract" => {
+                if let Some(TokenTree::Group(g)) = tokens.get(i + 1) {
+                    targets.push(target_segments(g.stream()));
+                }
+            }
+            TokenTree::Group(g) => targets.extend(harness_targets(&g.stream())),
+            _ => {}
+        }
+    }
+    targets
+}
+
+/// Path segments of a harness target, skipping generic arguments and crate-relative prefixes.
+/// For example, `crate::ptr::NonNull::<i32>::add` is `["ptr", "NonNull", "add"]`, and
+/// `<*const u8>::add` is `["*const T", "add"]`.
+fn target_segments(tokens: TokenStream) -> Vec<String> {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut segments = Vec::new();
+    if let [TokenTree::Punct(lt), TokenTree::Punct(star), TokenTree::Ident(mutability), ..] =
+        &tokens[..]
+    {
+        if lt.as_char() == '<' && star.as_char() == '*' {
+            segments.push(format!("*{mutability} T"));
+        }
+    }
+    let mut depth = 0;
+    let mut metavar = false;
+    for token in tokens {
+        match token {
+            TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+            TokenTree::Punct(p) if p.as_char() == '>' => depth -= 1,
+            TokenTree::Punct(p) if p.as_char() == '$' => metavar = true,
+            TokenTree::Ident(ident) if depth == 0 => {
+                segments.push(if metavar {
+                    "*".to_string()
+                } else {
+                    ident.to_string()
+                });
+                metavar = false;
+            }
+            _ => {}
+        }
+    }
+    let prefix = segments
+        .iter()
+        .take_while(|seg| {
+            matches!(
+                seg.as_str(),
+                "crate" | "self" | "super" | "core" | "alloc" | "std"
+            )
+        })
+        .count();
+    segments.split_off(prefix)
+}
+
+/// Mark the functions targeted by `harness`. Returns whether any function matched.
+///
+/// Harness targets are paths as written in the harness's module, so we cannot resolve them
+/// exactly. Instead, we match the function name and the last qualifier (a type or a module), and
+/// among the candidates, pick the ones closest to the harness: in the same crate, and with the
+/// longest common module prefix.
+fn mark_harness_target(unsafe_fns: &mut [UnsafeFn], harness: &Harness) -> bool {
+    let Some((name, qualifiers)) = harness.target.split_last() else {
+        return false;
+    };
+    if name == "*" {
+        return false;
+    }
+    let matches = |f: &UnsafeFn| {
+        if &f.name != name {
+            return false;
+        }
+        match qualifiers.last().map(String::as_str) {
+            None | Some("*") => true,
+            Some(q) => match &f.self_ty {
+                Some(ty) => ty == q,
+                None => f.in_macro.is_some() || f.module.split("::").any(|m| m == q),
+            },
+        }
+    };
+    let score = |f: &UnsafeFn| {
+        let same_crate = f.krate == harness.krate;
+        let common_prefix = f
+            .module
+            .split("::")
+            .zip(harness.module.split("::"))
+            .take_while(|(a, b)| a == b)
+            .count();
+        (same_crate, common_prefix)
+    };
+    let Some(best) = unsafe_fns.iter().filter(|f| matches(f)).map(score).max() else {
+        return false;
+    };
+    for f in unsafe_fns.iter_mut() {
+        if matches(f) && score(f) == best {
+            f.has_harness = true;
+        }
+    }
+    true
+}
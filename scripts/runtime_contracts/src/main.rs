@@ -0,0 +1,120 @@
+//! Exercises the standard library built with its contracts checked at runtime. The first argument
+//! selects what to run:
+//!
+//! - `valid` and `invalid` call `String::from_utf8_unchecked`, whose contract requires valid
+//!   UTF-8, with valid and invalid bytes. The `invalid` case must abort with a precondition
+//!   violation.
+//! - `sort` runs the slice sorts and `select_nth_unstable`, checking the loop invariants and
+//!   preconditions of their implementations. It checks that the results are sorted permutations of
+//!   the inputs, and that they are still permutations when the comparison function panics.
+
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+
+fn main() {
+    match std::env::args().nth(1).as_deref() {
+        Some("valid") => from_utf8_unchecked(b"valid".to_vec()),
+        Some("invalid") => from_utf8_unchecked(vec![0xff]),
+        Some("sort") => sort(),
+        _ => {
+            eprintln!("usage: runtime_contracts <valid|invalid|sort>");
+            std::process::exit(2);
+        }
+    }
+}
+
+fn from_utf8_unchecked(bytes: Vec<u8>) {
+    // SAFETY: not in the `invalid` case, which is the contract violation under test.
+    let s = unsafe { String::from_utf8_unchecked(bytes) };
+    println!("created a string of {} bytes", s.len());
+}
+
+/// The ways of sorting a slice that are checked, in the order of `SORTS`.
+const SORTS: [&str; 3] = ["sort_by", "sort_unstable_by", "select_nth_unstable_by"];
+
+/// Sorts `v` with the sort `SORTS[sort]`, and the comparison function `compare`.
+fn sort_with<T>(v: &mut [T], sort: usize, compare: impl FnMut(&T, &T) -> std::cmp::Ordering) {
+    match sort {
+        0 => v.sort_by(compare),
+        1 => v.sort_unstable_by(compare),
+        _ if v.is_empty() => {}
+        _ => {
+            v.select_nth_unstable_by(v.len() / 2, compare);
+        }
+    }
+}
+
+/// Returns how many times each value occurs in `v`.
+fn counts(v: &[u32]) -> Vec<usize> {
+    let mut counts = vec![0; 64];
+    for &x in v {
+        counts[x as usize] += 1;
+    }
+    counts
+}
+
+/// Generates `len` values below 64 with a linear congruential generator, so that the inputs hold
+/// runs and duplicates.
+fn input(len: usize, seed: u32) -> Vec<u32> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (state >> 16) % 64
+        })
+        .collect()
+}
+
+fn sort() {
+    // Silence the panics of the comparison functions below.
+    panic::set_hook(Box::new(|_| {}));
+    for len in [0, 1, 2, 3, 7, 16, 17, 20, 33, 64, 100] {
+        for seed in 0..4 {
+            let original = input(len, seed);
+            for (sort, name) in SORTS.iter().enumerate() {
+                // `u32` takes the small-sorts for `Freeze` types, `Cell<u32>` the fallback ones.
+                let mut v = original.clone();
+                sort_with(&mut v, sort, u32::cmp);
+                let mut cells: Vec<_> = original.iter().copied().map(Cell::new).collect();
+                sort_with(&mut cells, sort, |a, b| a.get().cmp(&b.get()));
+                let from_cells: Vec<_> = cells.into_iter().map(Cell::into_inner).collect();
+                for result in [&v, &from_cells] {
+                    assert_eq!(
+                        counts(result),
+                        counts(&original),
+                        "{name} is not a permutation"
+                    );
+                    if sort < 2 {
+                        assert!(result.is_sorted(), "{name} did not sort");
+                    }
+                }
+
+                // Panic in each of the comparisons that a total order takes.
+                let mut comparisons = 0;
+                sort_with(&mut original.clone(), sort, |a, b| {
+                    comparisons += 1;
+                    a.cmp(b)
+                });
+                for panic_at in 0..comparisons {
+                    let mut v = original.clone();
+                    let mut count = 0;
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                        sort_with(&mut v, sort, |a, b| {
+                            assert!(count != panic_at);
+                            count += 1;
+                            a.cmp(b)
+                        })
+                    }));
+                    assert!(result.is_err());
+                    assert_eq!(
+                        counts(&v),
+                        counts(&original),
+                        "{name} lost elements in a panic"
+                    );
+                }
+            }
+        }
+    }
+    let _ = panic::take_hook();
+    println!("sorted slices with runtime contracts");
+}